@@ -0,0 +1,211 @@
+use crate::error::ChronoError;
+use crate::fetch::ConnectionStatus;
+use crate::provider::WorkItem;
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, Live, Node, NodeId, Role, Tree, TreeUpdate,
+};
+use std::sync::{Arc, Mutex};
+
+const WINDOW_ID: NodeId = NodeId(0);
+const TIME_ID: NodeId = NodeId(1);
+const DATE_ID: NodeId = NodeId(2);
+const ANNOUNCEMENT_ID: NodeId = NodeId(3);
+
+// What assistive tech can read out: the clock itself plus a polite live
+// region for status changes (GitHub connection, new PRs).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub time: String,
+    pub date: String,
+    pub announcement: String,
+}
+
+fn build_tree(snapshot: &Snapshot) -> TreeUpdate {
+    let mut window = Node::new(Role::Window);
+    window.set_label("Chrono");
+    window.set_children(vec![TIME_ID, DATE_ID, ANNOUNCEMENT_ID]);
+
+    let mut time = Node::new(Role::Label);
+    time.set_label("Time");
+    time.set_value(snapshot.time.clone());
+
+    let mut date = Node::new(Role::Label);
+    date.set_label("Date");
+    date.set_value(snapshot.date.clone());
+
+    let mut announcement = Node::new(Role::Status);
+    announcement.set_live(Live::Polite);
+    announcement.set_value(snapshot.announcement.clone());
+
+    TreeUpdate {
+        nodes: vec![
+            (WINDOW_ID, window),
+            (TIME_ID, time),
+            (DATE_ID, date),
+            (ANNOUNCEMENT_ID, announcement),
+        ],
+        tree: Some(Tree::new(WINDOW_ID)),
+        focus: WINDOW_ID,
+    }
+}
+
+struct TreeSource {
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl ActivationHandler for TreeSource {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        let snapshot = self.snapshot.lock().ok()?;
+        Some(build_tree(&snapshot))
+    }
+}
+
+struct IgnoreActions;
+
+impl ActionHandler for IgnoreActions {
+    fn do_action(&mut self, _request: ActionRequest) {}
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+struct IgnoreDeactivation;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl accesskit::DeactivationHandler for IgnoreDeactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+type PlatformAdapter = accesskit_unix::Adapter;
+
+#[cfg(target_os = "macos")]
+type PlatformAdapter = accesskit_macos::SubclassingAdapter;
+
+#[cfg(not(unix))]
+type PlatformAdapter = ();
+
+pub struct Announcer {
+    snapshot: Arc<Mutex<Snapshot>>,
+    adapter: Option<PlatformAdapter>,
+}
+
+impl Default for Announcer {
+    fn default() -> Self {
+        Announcer::new()
+    }
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+        let source = TreeSource {
+            snapshot: snapshot.clone(),
+        };
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        let adapter = Some(accesskit_unix::Adapter::new(
+            source,
+            IgnoreActions,
+            IgnoreDeactivation,
+        ));
+
+        #[cfg(target_os = "macos")]
+        let adapter = {
+            // miniquad owns the NSView; we only attach accessibility methods to it.
+            let view = macroquad::miniquad::window::apple_view() as *mut std::ffi::c_void;
+            Some(unsafe { accesskit_macos::SubclassingAdapter::new(view, source, IgnoreActions) })
+        };
+
+        // miniquad does not expose the native window handle on other
+        // platforms, so there is nothing to attach an adapter to.
+        #[cfg(not(unix))]
+        let adapter: Option<PlatformAdapter> = {
+            let _ = (source, IgnoreActions);
+            None
+        };
+
+        Announcer { snapshot, adapter }
+    }
+
+    pub fn update(&mut self, next: Snapshot) {
+        {
+            // Release the lock before talking to the adapter, which may call
+            // back into the activation handler from its own thread.
+            let Ok(mut snapshot) = self.snapshot.lock() else {
+                return;
+            };
+            if *snapshot == next {
+                return;
+            }
+            *snapshot = next.clone();
+        }
+        let update = || build_tree(&next);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        if let Some(adapter) = self.adapter.as_mut() {
+            adapter.update_if_active(update);
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(adapter) = self.adapter.as_mut() {
+            if let Some(events) = adapter.update_if_active(update) {
+                events.raise();
+            }
+        }
+
+        #[cfg(not(unix))]
+        let _ = (update, &self.adapter);
+    }
+}
+
+// The live region text: the connection state, with the reason when GitHub
+// could not be reached.
+pub fn github_announcement(
+    status: ConnectionStatus,
+    error: Option<&ChronoError>,
+    prs: &[WorkItem],
+) -> String {
+    let announcement = status_announcement(status, prs);
+    match error {
+        Some(error)
+            if matches!(
+                status,
+                ConnectionStatus::Disconnected | ConnectionStatus::Unreachable
+            ) =>
+        {
+            format!("{}: {}", announcement, error)
+        }
+        _ => announcement,
+    }
+}
+
+// The GitHub button's state in a few words, for its tooltip.
+pub fn connection_label(status: ConnectionStatus) -> String {
+    match status {
+        ConnectionStatus::Connected => "GitHub connected".to_string(),
+        status => status_announcement(status, &[]),
+    }
+}
+
+pub fn status_announcement(status: ConnectionStatus, prs: &[WorkItem]) -> String {
+    match status {
+        ConnectionStatus::Unknown => "Checking GitHub".to_string(),
+        ConnectionStatus::Disconnected => "GitHub disconnected".to_string(),
+        ConnectionStatus::RateLimited => "GitHub rate limit reached".to_string(),
+        ConnectionStatus::TimedOut => "GitHub fetch timed out".to_string(),
+        ConnectionStatus::Unreachable => "GitHub unreachable".to_string(),
+        ConnectionStatus::Connected if prs.is_empty() => "No open pull requests".to_string(),
+        ConnectionStatus::Connected => {
+            let titles = prs
+                .iter()
+                .map(|pr| pr.title.as_str())
+                .collect::<Vec<&str>>()
+                .join("; ");
+            let noun = if prs.len() == 1 {
+                "pull request"
+            } else {
+                "pull requests"
+            };
+            format!("{} open {}: {}", prs.len(), noun, titles)
+        }
+    }
+}
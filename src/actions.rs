@@ -0,0 +1,104 @@
+// The latest GitHub Actions runs of the workflows in `CHRONO_WORKFLOWS`,
+// given as `owner/repo/workflow.yml`, drawn as a row of status dots each.
+use crate::provider::CiState;
+
+// Dots per workflow.
+pub const RUNS: usize = 5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkflowRuns {
+    pub label: String,
+    // (state, run page), oldest first so the newest run is on the right.
+    pub runs: Vec<(CiState, String)>,
+}
+
+// `owner/repo/workflow.yml` split into the repo and the workflow file (or
+// numeric id).
+pub fn parse_workflow(entry: &str) -> Option<(&str, &str)> {
+    let (repo, workflow) = entry.trim().rsplit_once('/')?;
+    (repo.contains('/') && !workflow.is_empty()).then_some((repo, workflow))
+}
+
+// `CHRONO CI` for `o/chrono/ci.yml`.
+pub fn workflow_label(repo: &str, workflow: &str) -> String {
+    let name = repo.rsplit('/').next().unwrap_or(repo);
+    let stem = workflow
+        .strip_suffix(".yml")
+        .or_else(|| workflow.strip_suffix(".yaml"))
+        .unwrap_or(workflow);
+    format!("{} {}", name, stem).to_uppercase()
+}
+
+// A run's `status` and `conclusion` as a CI dot.
+pub fn run_state(status: &str, conclusion: Option<&str>) -> CiState {
+    match (status, conclusion) {
+        ("completed", Some("success" | "neutral" | "skipped")) => CiState::Success,
+        ("completed", _) => CiState::Failure,
+        _ => CiState::Pending,
+    }
+}
+
+// The newest `RUNS` runs from a `workflows/{id}/runs` response.
+#[cfg(feature = "network")]
+pub fn parse_runs(json: &serde_json::Value) -> Vec<(CiState, String)> {
+    let Some(runs) = json["workflow_runs"].as_array() else {
+        return Vec::new();
+    };
+    let mut runs = runs
+        .iter()
+        .filter_map(|run| {
+            let status = run["status"].as_str()?;
+            let url = run["html_url"].as_str()?;
+            Some((
+                run_state(status, run["conclusion"].as_str()),
+                url.to_string(),
+            ))
+        })
+        .take(RUNS)
+        .collect::<Vec<_>>();
+    runs.reverse();
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_workflow_entries() {
+        assert_eq!(
+            parse_workflow("o/chrono/ci.yml"),
+            Some(("o/chrono", "ci.yml"))
+        );
+        assert_eq!(parse_workflow("chrono/ci.yml"), None);
+        assert_eq!(parse_workflow("o/chrono/"), None);
+        assert_eq!(workflow_label("o/chrono", "ci.yml"), "CHRONO CI");
+        assert_eq!(workflow_label("o/chrono", "1234"), "CHRONO 1234");
+    }
+
+    #[test]
+    fn maps_run_states() {
+        assert_eq!(run_state("completed", Some("success")), CiState::Success);
+        assert_eq!(run_state("completed", Some("skipped")), CiState::Success);
+        assert_eq!(run_state("completed", Some("cancelled")), CiState::Failure);
+        assert_eq!(run_state("in_progress", None), CiState::Pending);
+        assert_eq!(run_state("queued", None), CiState::Pending);
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn keeps_the_newest_runs_oldest_first() {
+        let json = serde_json::json!({
+            "workflow_runs": (1..=7).map(|id| serde_json::json!({
+                "status": if id == 1 { "in_progress" } else { "completed" },
+                "conclusion": if id == 2 { serde_json::json!("failure") } else { serde_json::json!("success") },
+                "html_url": format!("https://github.com/o/chrono/actions/runs/{}", id),
+            })).collect::<Vec<_>>()
+        });
+        let runs = parse_runs(&json);
+        assert_eq!(runs.len(), RUNS);
+        assert_eq!(runs[0].1, "https://github.com/o/chrono/actions/runs/5");
+        assert_eq!(runs[3].0, CiState::Failure);
+        assert_eq!(runs[4].0, CiState::Pending);
+    }
+}
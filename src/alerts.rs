@@ -0,0 +1,143 @@
+// PRs that show up in a fetch, or come back under a new title, for desktop
+// notifications (`CHRONO_DESKTOP_NOTIFICATIONS`) and the activity sound. The
+// first fetch only sets the baseline, so starting the board does not
+// announce every open PR.
+use crate::platform;
+use crate::provider::WorkItem;
+use std::collections::HashMap;
+
+// More than this at once collapses into one summary notification.
+const MAX_NOTIFICATIONS: usize = 3;
+const PULLS_URL: &str = "https://github.com/pulls";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activity {
+    Opened,
+    Renamed,
+}
+
+// What is new in `items` against the last fetch's `known` titles by URL.
+pub fn activity<'a>(
+    known: &HashMap<String, String>,
+    items: &'a [WorkItem],
+) -> Vec<(Activity, &'a WorkItem)> {
+    items
+        .iter()
+        .filter_map(|item| match known.get(&item.url) {
+            None => Some((Activity::Opened, item)),
+            Some(title) if *title != item.title => Some((Activity::Renamed, item)),
+            Some(_) => None,
+        })
+        .collect()
+}
+
+// (title, body, url) for each notification to send.
+pub fn notifications(activity: &[(Activity, &WorkItem)]) -> Vec<(String, String, String)> {
+    if activity.len() > MAX_NOTIFICATIONS {
+        return vec![(
+            format!("{} PRs updated", activity.len()),
+            activity
+                .iter()
+                .map(|(_, item)| item.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            PULLS_URL.to_string(),
+        )];
+    }
+    activity
+        .iter()
+        .map(|(kind, item)| {
+            let heading = match kind {
+                Activity::Opened => "New PR",
+                Activity::Renamed => "PR renamed",
+            };
+            let title = match (&item.repo, item.number) {
+                (Some(repo), Some(number)) => format!("{} · {}#{}", heading, repo, number),
+                _ => heading.to_string(),
+            };
+            (title, item.title.clone(), item.url.clone())
+        })
+        .collect()
+}
+
+// Tracks the last fetch's PRs and what changed since.
+#[derive(Default)]
+pub struct PrAlerts {
+    known: Option<HashMap<String, String>>,
+}
+
+impl PrAlerts {
+    // Whether anything is new, sending notifications for it if `notify`.
+    pub fn update(&mut self, items: &[WorkItem], notify: bool) -> bool {
+        let found = self
+            .known
+            .as_ref()
+            .map(|known| activity(known, items))
+            .unwrap_or_default();
+        if notify {
+            for (title, body, url) in notifications(&found) {
+                platform::notify(&title, &body, &url);
+            }
+        }
+        let new = !found.is_empty();
+        self.known = Some(
+            items
+                .iter()
+                .map(|item| (item.url.clone(), item.title.clone()))
+                .collect(),
+        );
+        new
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, title: &str) -> WorkItem {
+        WorkItem {
+            title: title.to_string(),
+            url: format!("https://github.com/o/chrono/pull/{}", number),
+            account: None,
+            repo: Some("o/chrono".to_string()),
+            number: Some(number),
+            updated_at: None,
+            avatar_url: None,
+        }
+    }
+
+    #[test]
+    fn finds_new_and_renamed_prs() {
+        let known = [pr(1, "Fix tz"), pr(2, "WIP: tests")]
+            .iter()
+            .map(|item| (item.url.clone(), item.title.clone()))
+            .collect();
+        let items = [pr(1, "Fix tz"), pr(2, "Add tests"), pr(3, "Bump deps")];
+        let found = activity(&known, &items);
+        assert_eq!(
+            found
+                .iter()
+                .map(|(kind, item)| (*kind, item.number))
+                .collect::<Vec<_>>(),
+            [(Activity::Renamed, Some(2)), (Activity::Opened, Some(3))]
+        );
+        assert_eq!(
+            notifications(&found)[1],
+            (
+                "New PR · o/chrono#3".to_string(),
+                "Bump deps".to_string(),
+                "https://github.com/o/chrono/pull/3".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn collapses_a_burst_into_one() {
+        let items = (1..=5).map(|n| pr(n, "PR")).collect::<Vec<_>>();
+        let found = activity(&HashMap::new(), &items);
+        let sent = notifications(&found);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "5 PRs updated");
+        assert_eq!(sent[0].2, PULLS_URL);
+    }
+}
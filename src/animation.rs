@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+// Full-board effects that play once and then get out of the way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Effect {
+    HourWave,
+    Fireworks,
+    // PR rows sliding in and out after a fetch changed the list.
+    ListChange,
+}
+
+impl Effect {
+    fn duration(self) -> f64 {
+        match self {
+            Effect::HourWave => 1.2,
+            Effect::Fireworks => 6.0,
+            Effect::ListChange => 0.45,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Celebration {
+    Off,
+    NewYear,
+    Daily,
+}
+
+impl FromStr for Celebration {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "false" | "0" => Ok(Celebration::Off),
+            "new-year" | "newyear" => Ok(Celebration::NewYear),
+            "daily" | "midnight" => Ok(Celebration::Daily),
+            _ => Err(format!("unknown celebration {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Scheduled {
+    effect: Effect,
+    start: f64,
+}
+
+// Times one-shot effects against the frame clock (`get_time`). Triggering
+// an effect that is already running restarts it.
+#[derive(Debug, Default)]
+pub struct Animations {
+    running: Vec<Scheduled>,
+}
+
+impl Animations {
+    pub fn trigger(&mut self, effect: Effect, now: f64) {
+        self.running.retain(|scheduled| scheduled.effect != effect);
+        self.running.push(Scheduled { effect, start: now });
+    }
+
+    // Running effects with their progress in 0..1; finished ones are dropped.
+    pub fn frame(&mut self, now: f64) -> Vec<(Effect, f32)> {
+        self.running
+            .retain(|scheduled| now - scheduled.start < scheduled.effect.duration());
+        self.running
+            .iter()
+            .map(|scheduled| {
+                let progress = (now - scheduled.start) / scheduled.effect.duration();
+                (scheduled.effect, progress.clamp(0.0, 1.0) as f32)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,66 @@
+// Follows the system's dark or light mode for `CHRONO_DARK_THEME` and
+// `CHRONO_LIGHT_THEME`.
+use crate::platform;
+use std::sync::mpsc;
+
+const APPEARANCE_CHECK_SECS: f64 = 5.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+pub struct AppearanceMonitor {
+    appearance: Option<Appearance>,
+    last_check: Option<f64>,
+    rx: Option<mpsc::Receiver<Option<Appearance>>>,
+}
+
+impl AppearanceMonitor {
+    pub fn new() -> Self {
+        AppearanceMonitor {
+            appearance: None,
+            last_check: None,
+            rx: None,
+        }
+    }
+
+    // The last known mode; None until the system has answered, or where it
+    // never does.
+    pub fn update(&mut self, now: f64) -> Option<Appearance> {
+        if let Some(rx) = &self.rx {
+            if let Ok(appearance) = rx.try_recv() {
+                if let Some(appearance) = appearance.filter(|a| Some(*a) != self.appearance) {
+                    log!("System appearance: {:?}", appearance);
+                    self.appearance = Some(appearance);
+                }
+                self.rx = None;
+            }
+        }
+
+        let stale = self
+            .last_check
+            .map(|last| now - last >= APPEARANCE_CHECK_SECS)
+            .unwrap_or(true);
+        if stale && self.rx.is_none() {
+            self.last_check = Some(now);
+            self.rx = Some(platform::background(|| {
+                platform::prefers_dark().map(|dark| {
+                    if dark {
+                        Appearance::Dark
+                    } else {
+                        Appearance::Light
+                    }
+                })
+            }));
+        }
+        self.appearance
+    }
+}
+
+impl Default for AppearanceMonitor {
+    fn default() -> Self {
+        AppearanceMonitor::new()
+    }
+}
@@ -0,0 +1,115 @@
+// Only native builds with the network feature download and cache avatars.
+#![cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+
+use std::str::FromStr;
+
+// Largest avatar accepted; GitHub serves a few kilobytes at the size asked for.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+const MAX_BYTES: u64 = 512 * 1024;
+
+// How PR author avatars replace the generic PR icon. `Pixel` renders them
+// at 8x8 so they sit with the pixel font.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AvatarStyle {
+    Off,
+    Smooth,
+    Pixel,
+}
+
+impl AvatarStyle {
+    pub fn texture_size(self) -> u32 {
+        match self {
+            AvatarStyle::Pixel => 8,
+            AvatarStyle::Off | AvatarStyle::Smooth => 32,
+        }
+    }
+}
+
+impl FromStr for AvatarStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "false" | "0" => Ok(AvatarStyle::Off),
+            "smooth" | "on" | "true" | "1" => Ok(AvatarStyle::Smooth),
+            "pixel" | "pixelated" => Ok(AvatarStyle::Pixel),
+            _ => Err(format!("unknown avatar style {}", value)),
+        }
+    }
+}
+
+// `~/.cache/chrono/avatars/<hash of the url>.img`. resvg wants an
+// extension, then sniffs the format from the bytes.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn path(url: &str) -> Option<std::path::PathBuf> {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    Some(
+        crate::platform::cache_dir()?
+            .join("avatars")
+            .join(format!("{:016x}.img", hasher.finish())),
+    )
+}
+
+// Shared by the downloads of one fetch so they reuse connections.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+}
+
+// The distinct avatar URLs not on disk yet.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn missing<'a>(urls: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for url in urls {
+        if !missing.iter().any(|seen| seen == url) && path(url).is_some_and(|path| !path.exists()) {
+            missing.push(url.to_string());
+        }
+    }
+    missing
+}
+
+// Fetches one avatar. The fetch waits for these before it completes, so
+// the UI finds the files when it gets the new list.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn download(agent: &ureq::Agent, url: &str) {
+    use std::io::Read;
+    let Some(path) = path(url) else {
+        return;
+    };
+    // GitHub resizes on request; other forges serve what they have.
+    let request_url = if url.starts_with("https://avatars.githubusercontent.com/") {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        format!("{}{}s=64", url, separator)
+    } else {
+        url.to_string()
+    };
+    let mut bytes = Vec::new();
+    let read = agent
+        .get(&request_url)
+        .set("User-Agent", "commit-clock")
+        .call()
+        .map_err(|e| e.to_string())
+        .and_then(|resp| {
+            resp.into_reader()
+                .take(MAX_BYTES)
+                .read_to_end(&mut bytes)
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = read {
+        log!("Failed to fetch avatar {}: {}", url, e);
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = std::fs::write(&path, bytes) {
+        log!("Failed to write {}: {e}", path.display());
+    }
+}
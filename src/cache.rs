@@ -0,0 +1,135 @@
+use crate::fetch::FetchResult;
+use crate::provider::WorkItem;
+use crate::provider::{CiState, CiStatus, ReviewDecision, ReviewStatus};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+// The last successful fetch, so the PR list is there right after launch
+// instead of after the first network round-trip.
+fn path() -> Option<PathBuf> {
+    Some(crate::platform::cache_dir()?.join("last_fetch.json"))
+}
+
+pub fn save(result: &FetchResult) {
+    let Some(path) = path() else {
+        return;
+    };
+    let body = json!({
+        "prs": items_json(&result.prs),
+        "issues": items_json(&result.issues),
+        "issues_supported": result.capabilities.issues,
+        "fetched_at": result.fetched_at,
+        "ci": result
+            .ci
+            .iter()
+            .map(|status| json!({ "url": status.url, "state": ci_name(status.state) }))
+            .collect::<Vec<Value>>(),
+        "decisions": result
+            .decisions
+            .iter()
+            .map(|status| json!({ "url": status.url, "decision": decision_name(status.decision) }))
+            .collect::<Vec<Value>>(),
+        "conflicts": result.conflicts,
+    });
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = std::fs::write(&path, body.to_string()) {
+        log!("Failed to write {}: {e}", path.display());
+    }
+}
+
+pub fn load() -> Option<FetchResult> {
+    let body = std::fs::read_to_string(path()?).ok()?;
+    let json: Value = serde_json::from_str(&body).ok()?;
+    let mut result = FetchResult {
+        prs: items_from_json(&json["prs"]),
+        issues: items_from_json(&json["issues"]),
+        ..Default::default()
+    };
+    result.capabilities.issues = json["issues_supported"].as_bool().unwrap_or(false);
+    result.fetched_at = json["fetched_at"].as_i64();
+    result.ci = entries(&json["ci"])
+        .filter_map(|entry| {
+            Some(CiStatus {
+                url: entry["url"].as_str()?.to_string(),
+                state: match entry["state"].as_str()? {
+                    "pending" => CiState::Pending,
+                    "success" => CiState::Success,
+                    "failure" => CiState::Failure,
+                    _ => return None,
+                },
+            })
+        })
+        .collect();
+    result.decisions = entries(&json["decisions"])
+        .filter_map(|entry| {
+            Some(ReviewStatus {
+                url: entry["url"].as_str()?.to_string(),
+                decision: match entry["decision"].as_str()? {
+                    "approved" => ReviewDecision::Approved,
+                    "changes_requested" => ReviewDecision::ChangesRequested,
+                    "pending" => ReviewDecision::Pending,
+                    _ => return None,
+                },
+            })
+        })
+        .collect();
+    result.conflicts = entries(&json["conflicts"])
+        .filter_map(|url| Some(url.as_str()?.to_string()))
+        .collect();
+    Some(result)
+}
+
+fn items_json(items: &[WorkItem]) -> Vec<Value> {
+    items
+        .iter()
+        .map(|item| {
+            json!({
+                "title": item.title,
+                "url": item.url,
+                "account": item.account,
+                "repo": item.repo,
+                "number": item.number,
+                "updated_at": item.updated_at,
+                "avatar_url": item.avatar_url,
+            })
+        })
+        .collect()
+}
+
+fn items_from_json(json: &Value) -> Vec<WorkItem> {
+    entries(json)
+        .filter_map(|entry| {
+            Some(WorkItem {
+                title: entry["title"].as_str()?.to_string(),
+                url: entry["url"].as_str()?.to_string(),
+                account: entry["account"].as_str().map(str::to_string),
+                repo: entry["repo"].as_str().map(str::to_string),
+                number: entry["number"].as_u64(),
+                updated_at: entry["updated_at"].as_i64(),
+                avatar_url: entry["avatar_url"].as_str().map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+fn entries(json: &Value) -> impl Iterator<Item = &Value> {
+    json.as_array().into_iter().flatten()
+}
+
+fn ci_name(state: CiState) -> &'static str {
+    match state {
+        CiState::Pending => "pending",
+        CiState::Success => "success",
+        CiState::Failure => "failure",
+    }
+}
+
+fn decision_name(decision: ReviewDecision) -> &'static str {
+    match decision {
+        ReviewDecision::Approved => "approved",
+        ReviewDecision::ChangesRequested => "changes_requested",
+        ReviewDecision::Pending => "pending",
+    }
+}
@@ -0,0 +1,211 @@
+// The month laid out for the calendar page, weeks starting on the locale's
+// first day (or `CHRONO_FIRST_DAY`).
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::str::FromStr;
+
+// Regions whose calendars start on Sunday (CLDR); everywhere else gets the
+// ISO Monday.
+const SUNDAY_REGIONS: [&str; 52] = [
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CO", "DM", "DO", "ET", "GT", "GU", "HK",
+    "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX", "MZ",
+    "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW", "UM",
+    "US", "VE", "VI", "ZA",
+];
+
+// Which day opens a week row, and with it how weeks are numbered: ISO 8601
+// for Monday, the US convention (week 1 holds January 1st) for Sunday.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FirstDay {
+    Monday,
+    Sunday,
+}
+
+impl FromStr for FirstDay {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "monday" | "mon" | "iso" => Ok(FirstDay::Monday),
+            "sunday" | "sun" | "us" => Ok(FirstDay::Sunday),
+            _ => Err(format!("unknown first day of week {}", value)),
+        }
+    }
+}
+
+impl FirstDay {
+    // `en_US.UTF-8`, `pt_BR`, `de-DE`; `C`, `POSIX` and bare languages
+    // fall back to Monday.
+    pub fn from_locale(locale: &str) -> FirstDay {
+        let name = locale.split(['.', '@']).next().unwrap_or_default();
+        let region = name.split(['_', '-']).nth(1).unwrap_or_default();
+        if SUNDAY_REGIONS.contains(&region.to_ascii_uppercase().as_str()) {
+            FirstDay::Sunday
+        } else {
+            FirstDay::Monday
+        }
+    }
+
+    // The locale the C library would use for dates.
+    pub fn from_env() -> FirstDay {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|key| std::env::var(key).ok())
+            .find(|value| !value.trim().is_empty())
+            .map(|locale| FirstDay::from_locale(&locale))
+            .unwrap_or(FirstDay::Monday)
+    }
+
+    fn weekday(self) -> Weekday {
+        match self {
+            FirstDay::Monday => Weekday::Mon,
+            FirstDay::Sunday => Weekday::Sun,
+        }
+    }
+
+    // Columns between the first day of the week and `day`.
+    pub fn column(self, day: Weekday) -> usize {
+        day.days_since(self.weekday()) as usize
+    }
+
+    // `M T W T F S S` as column headings.
+    pub fn weekday_letters(self) -> [char; 7] {
+        let mut day = self.weekday();
+        [(); 7].map(|_| {
+            let letter = day.to_string().chars().next().unwrap_or(' ');
+            day = day.succ();
+            letter
+        })
+    }
+}
+
+pub fn week_number(date: NaiveDate, first_day: FirstDay) -> u32 {
+    match first_day {
+        FirstDay::Monday => date.iso_week().week(),
+        FirstDay::Sunday => {
+            let new_year = NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap_or(date);
+            (date.ordinal0() + new_year.weekday().num_days_from_sunday()) / 7 + 1
+        }
+    }
+}
+
+// The month as rows of seven cells, `None` before the 1st and after the
+// last day.
+pub fn month_grid(year: i32, month: u32, first_day: FirstDay) -> Vec<[Option<u32>; 7]> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return Vec::new();
+    };
+    let days = first
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .map_or(28, |last| last.day());
+    let lead = first_day.column(first.weekday());
+    let mut rows = Vec::new();
+    let mut row = [None; 7];
+    for day in 1..=days {
+        let cell = (lead + day as usize - 1) % 7;
+        row[cell] = Some(day);
+        if cell == 6 {
+            rows.push(row);
+            row = [None; 7];
+        }
+    }
+    if row.iter().any(Option::is_some) {
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn reads_the_region_from_the_locale() {
+        assert_eq!(FirstDay::from_locale("en_US.UTF-8"), FirstDay::Sunday);
+        assert_eq!(FirstDay::from_locale("pt-BR"), FirstDay::Sunday);
+        assert_eq!(FirstDay::from_locale("en_GB.UTF-8"), FirstDay::Monday);
+        assert_eq!(FirstDay::from_locale("de_DE@euro"), FirstDay::Monday);
+        assert_eq!(FirstDay::from_locale("C"), FirstDay::Monday);
+        assert_eq!(FirstDay::from_locale("en"), FirstDay::Monday);
+    }
+
+    #[test]
+    fn heads_the_columns_from_the_first_day() {
+        assert_eq!(
+            FirstDay::Monday
+                .weekday_letters()
+                .iter()
+                .collect::<String>(),
+            "MTWTFSS"
+        );
+        assert_eq!(
+            FirstDay::Sunday
+                .weekday_letters()
+                .iter()
+                .collect::<String>(),
+            "SMTWTFS"
+        );
+    }
+
+    #[test]
+    fn parses_first_day_names() {
+        assert_eq!("Sunday".parse::<FirstDay>(), Ok(FirstDay::Sunday));
+        assert_eq!("iso".parse::<FirstDay>(), Ok(FirstDay::Monday));
+        assert!("friday".parse::<FirstDay>().is_err());
+    }
+
+    #[test]
+    fn iso_weeks_start_on_monday_and_may_belong_to_the_previous_year() {
+        // Friday, so it is in the last ISO week of 2020.
+        assert_eq!(week_number(date(2021, 1, 1), FirstDay::Monday), 53);
+        assert_eq!(week_number(date(2021, 1, 4), FirstDay::Monday), 1);
+        assert_eq!(week_number(date(2024, 12, 30), FirstDay::Monday), 1);
+        // Sunday closes the ISO week.
+        assert_eq!(week_number(date(2024, 9, 8), FirstDay::Monday), 36);
+        assert_eq!(week_number(date(2024, 9, 9), FirstDay::Monday), 37);
+    }
+
+    #[test]
+    fn us_weeks_start_on_sunday_with_january_first_in_week_one() {
+        assert_eq!(week_number(date(2021, 1, 1), FirstDay::Sunday), 1);
+        assert_eq!(week_number(date(2021, 1, 2), FirstDay::Sunday), 1);
+        assert_eq!(week_number(date(2021, 1, 3), FirstDay::Sunday), 2);
+        assert_eq!(week_number(date(2024, 12, 30), FirstDay::Sunday), 53);
+        // Sunday opens the US week.
+        assert_eq!(week_number(date(2024, 9, 7), FirstDay::Sunday), 36);
+        assert_eq!(week_number(date(2024, 9, 8), FirstDay::Sunday), 37);
+        // A leap year starting on Saturday runs into a 54th week.
+        assert_eq!(week_number(date(2000, 12, 31), FirstDay::Sunday), 54);
+    }
+
+    #[test]
+    fn month_grid_follows_the_first_day() {
+        // September 2024 starts on a Sunday.
+        let sunday = month_grid(2024, 9, FirstDay::Sunday);
+        assert_eq!(sunday.len(), 5);
+        assert_eq!(sunday[0][0], Some(1));
+        assert_eq!(sunday[4][1], Some(30));
+
+        let monday = month_grid(2024, 9, FirstDay::Monday);
+        assert_eq!(monday.len(), 6);
+        assert_eq!(monday[0], [None, None, None, None, None, None, Some(1)]);
+        assert_eq!(monday[5][0], Some(30));
+    }
+
+    #[test]
+    fn month_grid_covers_every_day_once() {
+        for first_day in [FirstDay::Monday, FirstDay::Sunday] {
+            let days = month_grid(2024, 2, first_day)
+                .iter()
+                .flatten()
+                .flatten()
+                .copied()
+                .collect::<Vec<u32>>();
+            assert_eq!(days, (1..=29).collect::<Vec<u32>>());
+        }
+    }
+}
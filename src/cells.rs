@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+use std::cell::RefCell;
+use std::str::FromStr;
+
+// Side of the cached mask texture; cells are scaled from it when drawn.
+const MASK_SIZE: u16 = 32;
+// Sub-samples per axis when rasterizing a mask, for smooth edges.
+const MASK_SAMPLES: u16 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellShape {
+    Square,
+    Rounded,
+    Dot,
+    Diamond,
+}
+
+impl FromStr for CellShape {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "square" => Ok(CellShape::Square),
+            "rounded" | "round" => Ok(CellShape::Rounded),
+            "dot" | "dots" | "circle" => Ok(CellShape::Dot),
+            "diamond" => Ok(CellShape::Diamond),
+            _ => Err(format!("unknown cell shape {}", value)),
+        }
+    }
+}
+
+thread_local! {
+    static MASKS: RefCell<Vec<(CellShape, Texture2D)>> = const { RefCell::new(Vec::new()) };
+}
+
+// `u` and `v` run from -1 to 1 across the cell.
+fn covers(shape: CellShape, u: f32, v: f32) -> bool {
+    match shape {
+        CellShape::Square => true,
+        CellShape::Rounded => {
+            let radius = 0.45;
+            let dx = (u.abs() - (1.0 - radius)).max(0.0);
+            let dy = (v.abs() - (1.0 - radius)).max(0.0);
+            dx * dx + dy * dy <= radius * radius
+        }
+        CellShape::Dot => u * u + v * v <= 1.0,
+        CellShape::Diamond => u.abs() + v.abs() <= 1.0,
+    }
+}
+
+fn build_mask(shape: CellShape) -> Texture2D {
+    let mut image = Image::gen_image_color(MASK_SIZE, MASK_SIZE, Color::new(1.0, 1.0, 1.0, 0.0));
+    let samples = MASK_SAMPLES as f32;
+    for y in 0..MASK_SIZE {
+        for x in 0..MASK_SIZE {
+            let mut hits = 0;
+            for sy in 0..MASK_SAMPLES {
+                for sx in 0..MASK_SAMPLES {
+                    let u =
+                        ((x as f32 + (sx as f32 + 0.5) / samples) / MASK_SIZE as f32) * 2.0 - 1.0;
+                    let v =
+                        ((y as f32 + (sy as f32 + 0.5) / samples) / MASK_SIZE as f32) * 2.0 - 1.0;
+                    if covers(shape, u, v) {
+                        hits += 1;
+                    }
+                }
+            }
+            let coverage = hits as f32 / (samples * samples);
+            image.set_pixel(x as u32, y as u32, Color::new(1.0, 1.0, 1.0, coverage));
+        }
+    }
+    let texture = Texture2D::from_image(&image);
+    texture.set_filter(FilterMode::Linear);
+    texture
+}
+
+fn mask(shape: CellShape) -> Texture2D {
+    MASKS.with(|masks| {
+        let mut masks = masks.borrow_mut();
+        if let Some((_, texture)) = masks.iter().find(|(cached, _)| *cached == shape) {
+            return texture.clone();
+        }
+        let texture = build_mask(shape);
+        masks.push((shape, texture.clone()));
+        texture
+    })
+}
+
+// Square cells stay plain rectangles; the other shapes are a tinted mask.
+pub fn draw(shape: CellShape, x: f32, y: f32, size: f32, color: Color) {
+    if shape == CellShape::Square {
+        draw_rectangle(x, y, size, size, color);
+        return;
+    }
+    draw_texture_ex(
+        &mask(shape),
+        x,
+        y,
+        color,
+        DrawTextureParams {
+            dest_size: Some(vec2(size, size)),
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,770 @@
+use crate::glyphs::has_glyph;
+use crate::platform;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+use std::str::FromStr;
+use std::time::Duration;
+
+// Where the board reads the time from, so tests and the demo mode can run
+// it from any instant.
+pub trait TimeSource {
+    fn now(&self) -> DateTime<FixedOffset>;
+}
+
+// The system clock and timezone.
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        platform::now()
+    }
+}
+
+// Stopped at one instant.
+pub struct FixedClock(pub DateTime<FixedOffset>);
+
+impl TimeSource for FixedClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.0
+    }
+}
+
+// Starts at `start` and runs at real speed from there, for demos and
+// screenshots (`CHRONO_DEMO_TIME`).
+pub struct DemoClock {
+    start: DateTime<FixedOffset>,
+    started_at: DateTime<FixedOffset>,
+}
+
+impl DemoClock {
+    pub fn new(start: DateTime<FixedOffset>) -> Self {
+        DemoClock {
+            start,
+            started_at: platform::now(),
+        }
+    }
+}
+
+impl TimeSource for DemoClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.start + (platform::now() - self.started_at)
+    }
+}
+
+impl FixedClock {
+    // One reading of `clock` for a whole frame. Everything formatted from it
+    // agrees, so the date cannot turn over a frame before or after the time.
+    pub fn snapshot(clock: &dyn TimeSource) -> FixedClock {
+        FixedClock(clock.now())
+    }
+}
+
+// The demo clock when `CHRONO_DEMO_TIME` is set, the system clock otherwise.
+pub fn time_source(demo_time: Option<DateTime<FixedOffset>>) -> Box<dyn TimeSource> {
+    match demo_time {
+        Some(start) => Box::new(DemoClock::new(start)),
+        None => Box::new(SystemClock),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourFormat {
+    H24,
+    H12,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimeFormat {
+    HhMmSs,
+    HhMm,
+    MmSs,
+    IsoTime,
+    // `HH:MM:SS.T` and `HH:MM:SS.HH`.
+    Tenths,
+    Hundredths,
+    Custom(ClockFormat),
+}
+
+// A chrono format string such as `%H:%M` or `%d %b %Y`, checked when the
+// config loads: it must parse, and what it prints must be in the board font.
+// Output is upper-cased, since the font has no lower case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockFormat(String);
+
+impl ClockFormat {
+    // In 12-hour mode `%H` and `%k` print the 12-hour `%I` and `%l`.
+    pub fn render(&self, time: DateTime<FixedOffset>, hour_format: HourFormat) -> String {
+        let pattern = match hour_format {
+            HourFormat::H24 => self.0.clone(),
+            HourFormat::H12 => self.0.replace("%H", "%I").replace("%k", "%l"),
+        };
+        time.format(&pattern).to_string().to_uppercase()
+    }
+}
+
+impl FromStr for ClockFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if StrftimeItems::new(value).any(|item| item == Item::Error) {
+            return Err(format!("bad format string {}", value));
+        }
+        let format = ClockFormat(value.to_string());
+        // Every weekday and month name, in both hour modes.
+        let sample = DateTime::parse_from_rfc3339("2026-01-05T17:08:09+00:00").unwrap();
+        for day in 0..365 {
+            let time = sample + chrono::Duration::days(day);
+            for hour_format in [HourFormat::H24, HourFormat::H12] {
+                if let Some(ch) = format
+                    .render(time, hour_format)
+                    .chars()
+                    .find(|ch| !has_glyph(*ch))
+                {
+                    return Err(format!("no glyph for {:?} in {}", ch, value));
+                }
+            }
+        }
+        Ok(format)
+    }
+}
+
+impl FromStr for HourFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "24" | "24h" | "h24" => Ok(HourFormat::H24),
+            "12" | "12h" | "h12" => Ok(HourFormat::H12),
+            _ => Err(format!("unknown hour format {}", value)),
+        }
+    }
+}
+
+// Regions where the 12-hour clock is the everyday one.
+const TWELVE_HOUR_REGIONS: [&str; 14] = [
+    "US", "CA", "AU", "NZ", "IN", "PH", "PK", "BD", "EG", "SA", "JO", "MY", "CO", "SV",
+];
+
+// `en_US.UTF-8` or `fr_CA`; None for `C`, `POSIX` and other locales
+// without a region.
+pub fn locale_prefers_12_hour(locale: &str) -> Option<bool> {
+    let name = locale.split(['.', '@']).next()?;
+    let (language, region) = name.split_once(['_', '-'])?;
+    // French Canada writes 24 hours.
+    if language == "fr" && region == "CA" {
+        return Some(false);
+    }
+    Some(TWELVE_HOUR_REGIONS.contains(&region.to_ascii_uppercase().as_str()))
+}
+
+impl HourFormat {
+    // The system's preference, or the locale's, falling back to 24 hours.
+    pub fn detect() -> HourFormat {
+        let locale = || {
+            ["LC_ALL", "LC_TIME", "LANG"]
+                .into_iter()
+                .filter_map(|key| std::env::var(key).ok())
+                .find(|value| !value.is_empty())
+        };
+        let twelve = platform::prefers_12_hour()
+            .or_else(|| locale_prefers_12_hour(&locale()?))
+            .unwrap_or(false);
+        if twelve {
+            HourFormat::H12
+        } else {
+            HourFormat::H24
+        }
+    }
+
+    // The H key.
+    pub fn toggled(self) -> HourFormat {
+        match self {
+            HourFormat::H24 => HourFormat::H12,
+            HourFormat::H12 => HourFormat::H24,
+        }
+    }
+}
+
+impl TimeFormat {
+    // The F key cycles through the presets in this order, then through the
+    // configured format if there is one.
+    pub fn next(&self, custom: Option<&ClockFormat>) -> TimeFormat {
+        match self {
+            TimeFormat::HhMmSs => TimeFormat::HhMm,
+            TimeFormat::HhMm => TimeFormat::MmSs,
+            TimeFormat::MmSs => TimeFormat::IsoTime,
+            TimeFormat::IsoTime => TimeFormat::Tenths,
+            TimeFormat::Tenths => TimeFormat::Hundredths,
+            TimeFormat::Hundredths => custom
+                .cloned()
+                .map_or(TimeFormat::HhMmSs, TimeFormat::Custom),
+            TimeFormat::Custom(_) => TimeFormat::HhMmSs,
+        }
+    }
+
+    // Whether the time changes more often than once a second.
+    pub fn subsecond(&self) -> bool {
+        matches!(self, TimeFormat::Tenths | TimeFormat::Hundredths)
+    }
+}
+
+// How long until `now` turns over to the next whole second. Power save and
+// e-ink sleep this long so each frame lands just after the tick instead of
+// drifting by however long the frame took.
+pub fn until_next_second(now: DateTime<FixedOffset>) -> Duration {
+    let into = Duration::from_nanos(now.timestamp_subsec_nanos().min(999_999_999) as u64);
+    Duration::from_secs(1) - into
+}
+
+// True in the first half of every second, for the blinking colon and the
+// seconds pulse. At one frame per second (power save, e-ink) every frame
+// could land in the same half, so there the even seconds count instead.
+pub fn tick_on(now: DateTime<FixedOffset>, one_fps: bool) -> bool {
+    if one_fps {
+        now.second().is_multiple_of(2)
+    } else {
+        now.timestamp_subsec_millis() < 500
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmPmMark<'a> {
+    Text(&'a str),
+    Dot { pm: bool },
+}
+
+// The board's text rows, formatted from one reading of the clock.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockText {
+    pub time: String,
+    pub am_pm: Option<String>,
+    pub day_month: String,
+    pub year: String,
+}
+
+impl ClockText {
+    // `date_format` replaces the `09MAR` date row.
+    pub fn read(
+        clock: &dyn TimeSource,
+        hour_format: HourFormat,
+        time_format: &TimeFormat,
+        date_format: Option<&ClockFormat>,
+    ) -> Self {
+        let frame = FixedClock::snapshot(clock);
+        ClockText {
+            time: format_time(&frame, hour_format, time_format),
+            am_pm: am_pm_suffix(&frame, hour_format),
+            day_month: match date_format {
+                Some(format) => format.render(frame.0, hour_format),
+                None => format_day_month(&frame),
+            },
+            year: format_year(&frame),
+        }
+    }
+}
+
+pub fn format_time(
+    clock: &dyn TimeSource,
+    hour_format: HourFormat,
+    time_format: &TimeFormat,
+) -> String {
+    let now = clock.now();
+    let mut hour = now.hour() as i32;
+    let minute = now.minute();
+    let second = now.second();
+    // A leap second reads as 1000 ms or more.
+    let millis = now.timestamp_subsec_millis().min(999);
+
+    if hour_format == HourFormat::H12 {
+        hour %= 12;
+        if hour == 0 {
+            hour = 12;
+        }
+    }
+
+    match time_format {
+        TimeFormat::HhMmSs => format!("{:02}:{:02}:{:02}", hour, minute, second),
+        TimeFormat::HhMm => format!("{:02}:{:02}", hour, minute),
+        TimeFormat::MmSs => format!("{:02}:{:02}", minute, second),
+        TimeFormat::IsoTime => format!("{:02}:{:02}:{:02}", hour, minute, second),
+        TimeFormat::Tenths => format!("{:02}:{:02}:{:02}.{}", hour, minute, second, millis / 100),
+        TimeFormat::Hundredths => {
+            format!("{:02}:{:02}:{:02}.{:02}", hour, minute, second, millis / 10)
+        }
+        TimeFormat::Custom(format) => format.render(now, hour_format),
+    }
+}
+
+// `07:12` or `07:12AM`, for times shown in the widget lines.
+pub fn short_time(time: DateTime<FixedOffset>, hour_format: HourFormat) -> String {
+    match hour_format {
+        HourFormat::H24 => time.format("%H:%M").to_string(),
+        HourFormat::H12 => time.format("%I:%M%p").to_string(),
+    }
+}
+
+pub fn am_pm_suffix(clock: &dyn TimeSource, hour_format: HourFormat) -> Option<String> {
+    if hour_format == HourFormat::H24 {
+        return None;
+    }
+    let hour = clock.now().hour();
+    if hour >= 12 {
+        Some("PM".to_string())
+    } else {
+        Some("AM".to_string())
+    }
+}
+
+// Minute resolution, so the title only changes once a minute.
+pub fn format_window_title(
+    clock: &dyn TimeSource,
+    hour_format: HourFormat,
+    pr_count: usize,
+    release: Option<&str>,
+) -> String {
+    let now = clock.now();
+    let mut title = match hour_format {
+        HourFormat::H24 => now.format("%H:%M").to_string(),
+        HourFormat::H12 => now.format("%-I:%M %p").to_string(),
+    };
+    if pr_count > 0 {
+        title.push_str(&format!(" · {} PR", pr_count));
+    }
+    if let Some(release) = release {
+        title.push_str(&format!(" · {}", release));
+    }
+    title
+}
+
+// `CET +01:00`; `UTC +04:00` where the zone has no abbreviation.
+pub fn timezone_label(abbreviation: Option<&str>, offset_secs: i32) -> String {
+    let sign = if offset_secs < 0 { '-' } else { '+' };
+    let minutes = offset_secs.unsigned_abs() / 60;
+    format!(
+        "{} {}{:02}:{:02}",
+        abbreviation.unwrap_or("UTC"),
+        sign,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+// Read for the current instant, so it follows DST changes.
+pub fn format_timezone(clock: &dyn TimeSource) -> String {
+    let now = clock.now();
+    timezone_label(
+        platform::timezone_abbreviation(now).as_deref(),
+        now.offset().local_minus_utc(),
+    )
+}
+
+pub fn format_year(clock: &dyn TimeSource) -> String {
+    clock.now().year().to_string()
+}
+
+pub fn format_day_month(clock: &dyn TimeSource) -> String {
+    let now = clock.now();
+    let day = now.day();
+    let month_name = match now.month() {
+        1 => "JAN",
+        2 => "FEB",
+        3 => "MAR",
+        4 => "APR",
+        5 => "MAY",
+        6 => "JUN",
+        7 => "JUL",
+        8 => "AUG",
+        9 => "SEP",
+        10 => "OCT",
+        11 => "NOV",
+        _ => "DEC",
+    };
+    format!("{:02}{}", day, month_name)
+}
+
+// Compact age for list items: `5m`, `3h`, `2d`.
+pub fn age_label(elapsed_secs: i64) -> String {
+    let elapsed_secs = elapsed_secs.max(0);
+    if elapsed_secs < 3600 {
+        format!("{}m", elapsed_secs / 60)
+    } else if elapsed_secs < 86_400 {
+        format!("{}h", elapsed_secs / 3600)
+    } else {
+        format!("{}d", elapsed_secs / 86_400)
+    }
+}
+
+// How finely the "UPDATED ... AGO" label next to the PR list counts.
+// `Seconds` ticks every second for the first minute; `Minutes` shows `NOW`
+// until then and changes at most once a minute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdatedPrecision {
+    Off,
+    Minutes,
+    Seconds,
+}
+
+impl FromStr for UpdatedPrecision {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "false" | "0" => Ok(UpdatedPrecision::Off),
+            "minutes" | "minute" | "m" => Ok(UpdatedPrecision::Minutes),
+            "seconds" | "second" | "s" | "on" | "true" | "1" => Ok(UpdatedPrecision::Seconds),
+            _ => Err(format!("unknown updated label precision {}", value)),
+        }
+    }
+}
+
+// `UPDATED 37S AGO`, then minutes, hours and days as the fetch ages.
+pub fn updated_label(elapsed_secs: i64, precision: UpdatedPrecision) -> Option<String> {
+    let elapsed_secs = elapsed_secs.max(0);
+    let amount = match precision {
+        UpdatedPrecision::Off => return None,
+        UpdatedPrecision::Seconds if elapsed_secs < 60 => format!("{}S", elapsed_secs),
+        UpdatedPrecision::Minutes if elapsed_secs < 60 => return Some("UPDATED NOW".to_string()),
+        _ if elapsed_secs < 3600 => format!("{}M", elapsed_secs / 60),
+        _ if elapsed_secs < 86_400 => format!("{}H", elapsed_secs / 3600),
+        _ => format!("{}D", elapsed_secs / 86_400),
+    };
+    Some(format!("UPDATED {} AGO", amount))
+}
+
+// `LAST COMMIT 3H AGO` for the time since the latest push.
+pub fn last_commit_label(elapsed_secs: i64) -> String {
+    let elapsed_secs = elapsed_secs.max(0);
+    if elapsed_secs < 60 {
+        return "LAST COMMIT NOW".to_string();
+    }
+    format!("LAST COMMIT {} AGO", age_label(elapsed_secs).to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> FixedClock {
+        FixedClock(DateTime::parse_from_rfc3339(rfc3339).unwrap())
+    }
+
+    #[test]
+    fn twelve_hour_time_has_no_zero_hour() {
+        let clock = at("2024-03-09T00:05:09+01:00");
+        assert_eq!(
+            format_time(&clock, HourFormat::H12, &TimeFormat::HhMmSs),
+            "12:05:09"
+        );
+        assert_eq!(
+            format_time(&clock, HourFormat::H24, &TimeFormat::HhMm),
+            "00:05"
+        );
+        assert_eq!(
+            format_time(&clock, HourFormat::H12, &TimeFormat::MmSs),
+            "05:09"
+        );
+        assert_eq!(am_pm_suffix(&clock, HourFormat::H12).as_deref(), Some("AM"));
+        assert_eq!(am_pm_suffix(&clock, HourFormat::H24), None);
+    }
+
+    #[test]
+    fn sleeps_to_the_next_whole_second() {
+        let clock = at("2024-03-09T12:00:00.250+00:00");
+        assert_eq!(until_next_second(clock.0), Duration::from_millis(750));
+        let clock = at("2024-03-09T12:00:00+00:00");
+        assert_eq!(until_next_second(clock.0), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn noon_is_pm() {
+        let clock = at("2024-03-09T12:00:00-05:00");
+        assert_eq!(
+            format_time(&clock, HourFormat::H12, &TimeFormat::HhMm),
+            "12:00"
+        );
+        assert_eq!(am_pm_suffix(&clock, HourFormat::H12).as_deref(), Some("PM"));
+    }
+
+    #[test]
+    fn formatted_text_has_glyphs() {
+        use crate::glyphs::has_glyph;
+        let mut text = String::new();
+        for month in 1..=12 {
+            for hour in [0, 7, 12, 13, 23] {
+                let clock = at(&format!(
+                    "2024-{:02}-{:02}T{:02}:59:09+05:30",
+                    month,
+                    month + 17,
+                    hour
+                ));
+                for hour_format in [HourFormat::H12, HourFormat::H24] {
+                    for time_format in [
+                        TimeFormat::HhMmSs,
+                        TimeFormat::HhMm,
+                        TimeFormat::MmSs,
+                        TimeFormat::IsoTime,
+                        TimeFormat::Tenths,
+                        TimeFormat::Hundredths,
+                    ] {
+                        text += &format_time(&clock, hour_format, &time_format);
+                    }
+                    text += &am_pm_suffix(&clock, hour_format).unwrap_or_default();
+                }
+                text += &format_day_month(&clock);
+                text += &format_year(&clock);
+            }
+        }
+        text += &timezone_label(Some("CEST"), 7200);
+        text += &timezone_label(None, -(3 * 3600 + 1800));
+        for secs in [5, 120, 7200, 200_000] {
+            for precision in [UpdatedPrecision::Minutes, UpdatedPrecision::Seconds] {
+                text += &updated_label(secs, precision).unwrap_or_default();
+            }
+        }
+        for ch in text.chars() {
+            assert!(has_glyph(ch), "no glyph for {:?}", ch);
+        }
+    }
+
+    #[test]
+    fn date_rows_use_the_clock() {
+        let clock = at("2024-03-09T12:00:00+00:00");
+        assert_eq!(format_year(&clock), "2024");
+        assert_eq!(format_day_month(&clock), "09MAR");
+        assert_eq!(
+            format_window_title(&clock, HourFormat::H12, 2, None),
+            "12:00 PM · 2 PR"
+        );
+    }
+
+    fn text(rfc3339: &str) -> ClockText {
+        ClockText::read(&at(rfc3339), HourFormat::H24, &TimeFormat::HhMmSs, None)
+    }
+
+    fn row(text: &ClockText) -> String {
+        format!("{} {} {}", text.year, text.day_month, text.time)
+    }
+
+    #[test]
+    fn midnight_rolls_every_row_over() {
+        assert_eq!(
+            row(&text("2026-10-16T23:59:59+02:00")),
+            "2026 16OCT 23:59:59"
+        );
+        assert_eq!(
+            row(&text("2026-10-17T00:00:00+02:00")),
+            "2026 17OCT 00:00:00"
+        );
+        // The end of a month and of the year.
+        assert_eq!(
+            row(&text("2026-04-30T23:59:59-07:00")),
+            "2026 30APR 23:59:59"
+        );
+        assert_eq!(
+            row(&text("2026-05-01T00:00:00-07:00")),
+            "2026 01MAY 00:00:00"
+        );
+        assert_eq!(
+            row(&text("2026-12-31T23:59:59+05:45")),
+            "2026 31DEC 23:59:59"
+        );
+        assert_eq!(
+            row(&text("2027-01-01T00:00:00+05:45")),
+            "2027 01JAN 00:00:00"
+        );
+    }
+
+    #[test]
+    fn leap_days_come_and_go() {
+        assert_eq!(
+            row(&text("2024-02-28T23:59:59+00:00")),
+            "2024 28FEB 23:59:59"
+        );
+        assert_eq!(
+            row(&text("2024-02-29T00:00:00+00:00")),
+            "2024 29FEB 00:00:00"
+        );
+        assert_eq!(
+            row(&text("2024-03-01T00:00:00+00:00")),
+            "2024 01MAR 00:00:00"
+        );
+        assert_eq!(
+            row(&text("2023-02-28T23:59:59+00:00")),
+            "2023 28FEB 23:59:59"
+        );
+        // The next second after 28 February 2023 is already March.
+        let next = at("2023-02-28T23:59:59+00:00").0 + chrono::Duration::seconds(1);
+        assert_eq!(
+            row(&ClockText::read(
+                &FixedClock(next),
+                HourFormat::H24,
+                &TimeFormat::HhMmSs,
+                None
+            )),
+            "2023 01MAR 00:00:00"
+        );
+    }
+
+    // Moves on a second every time it is read.
+    struct Ticking(std::cell::Cell<DateTime<FixedOffset>>);
+
+    impl TimeSource for Ticking {
+        fn now(&self) -> DateTime<FixedOffset> {
+            let now = self.0.get();
+            self.0.set(now + chrono::Duration::seconds(1));
+            now
+        }
+    }
+
+    #[test]
+    fn one_frame_reads_the_clock_once() {
+        let clock = Ticking(std::cell::Cell::new(at("2026-12-31T23:59:59+00:00").0));
+        let text = ClockText::read(&clock, HourFormat::H12, &TimeFormat::HhMmSs, None);
+        assert_eq!(row(&text), "2026 31DEC 11:59:59");
+        assert_eq!(text.am_pm.as_deref(), Some("PM"));
+        // The next frame sees the new year on every row.
+        let text = ClockText::read(&clock, HourFormat::H12, &TimeFormat::HhMmSs, None);
+        assert_eq!(row(&text), "2027 01JAN 12:00:00");
+        assert_eq!(text.am_pm.as_deref(), Some("AM"));
+    }
+
+    #[test]
+    fn custom_formats_render_upper_case() {
+        let clock = at("2024-03-09T14:05:09+01:00");
+        let time = "%H.%M".parse::<ClockFormat>().unwrap();
+        let date = "%a %d %b %Y".parse::<ClockFormat>().unwrap();
+        let text = ClockText::read(
+            &clock,
+            HourFormat::H12,
+            &TimeFormat::Custom(time.clone()),
+            Some(&date),
+        );
+        assert_eq!(text.time, "02.05");
+        assert_eq!(text.day_month, "SAT 09 MAR 2024");
+        assert_eq!(
+            format_time(&clock, HourFormat::H24, &TimeFormat::Custom(time.clone())),
+            "14.05"
+        );
+        // F reaches the configured format after the presets.
+        assert_eq!(
+            TimeFormat::Hundredths.next(Some(&time)),
+            TimeFormat::Custom(time.clone())
+        );
+        assert_eq!(TimeFormat::Hundredths.next(None), TimeFormat::HhMmSs);
+        assert_eq!(TimeFormat::Custom(time).next(None), TimeFormat::HhMmSs);
+    }
+
+    #[test]
+    fn custom_formats_are_checked() {
+        assert!("%H:%M:%S".parse::<ClockFormat>().is_ok());
+        assert!("%A %e %B".parse::<ClockFormat>().is_ok());
+        assert!("%H:%M %Q".parse::<ClockFormat>().is_err());
+        // No slash in the pixel font.
+        assert_eq!(
+            "%d/%m".parse::<ClockFormat>(),
+            Err("no glyph for '/' in %d/%m".to_string())
+        );
+    }
+
+    #[test]
+    fn shows_tenths_and_hundredths() {
+        let clock =
+            FixedClock(DateTime::parse_from_rfc3339("2026-10-16T21:04:09.378+02:00").unwrap());
+        assert_eq!(
+            format_time(&clock, HourFormat::H24, &TimeFormat::Tenths),
+            "21:04:09.3"
+        );
+        assert_eq!(
+            format_time(&clock, HourFormat::H12, &TimeFormat::Hundredths),
+            "09:04:09.37"
+        );
+        assert!(TimeFormat::Tenths.subsecond());
+        assert!(!TimeFormat::HhMmSs.subsecond());
+        assert_eq!(TimeFormat::IsoTime.next(None), TimeFormat::Tenths);
+    }
+
+    #[test]
+    fn ticks_on_the_first_half_second() {
+        let at = |time: &str| DateTime::parse_from_rfc3339(time).unwrap();
+        assert!(tick_on(at("2026-10-16T12:00:07.120+02:00"), false));
+        assert!(!tick_on(at("2026-10-16T12:00:07.620+02:00"), false));
+        assert!(!tick_on(at("2026-10-16T12:00:07.120+02:00"), true));
+        assert!(tick_on(at("2026-10-16T12:00:08.900+02:00"), true));
+    }
+
+    #[test]
+    fn locales_pick_the_hour_format() {
+        assert_eq!(locale_prefers_12_hour("en_US.UTF-8"), Some(true));
+        assert_eq!(locale_prefers_12_hour("en-AU"), Some(true));
+        assert_eq!(locale_prefers_12_hour("en_GB.UTF-8"), Some(false));
+        assert_eq!(locale_prefers_12_hour("de_DE@euro"), Some(false));
+        assert_eq!(locale_prefers_12_hour("en_CA"), Some(true));
+        assert_eq!(locale_prefers_12_hour("fr_CA.UTF-8"), Some(false));
+        assert_eq!(locale_prefers_12_hour("C.UTF-8"), None);
+        assert_eq!(locale_prefers_12_hour("POSIX"), None);
+        assert_eq!("12".parse(), Ok(HourFormat::H12));
+        assert_eq!("24H".parse(), Ok(HourFormat::H24));
+    }
+
+    #[test]
+    fn age_uses_the_largest_whole_unit() {
+        assert_eq!(age_label(59), "0m");
+        assert_eq!(age_label(3599), "59m");
+        assert_eq!(age_label(3600), "1h");
+        assert_eq!(age_label(2 * 86_400 + 5), "2d");
+        // Clock skew between the forge and this machine.
+        assert_eq!(age_label(-30), "0m");
+    }
+
+    #[test]
+    fn updated_label_coarsens_as_it_ages() {
+        let seconds = |secs| updated_label(secs, UpdatedPrecision::Seconds).unwrap();
+        assert_eq!(seconds(0), "UPDATED 0S AGO");
+        assert_eq!(seconds(37), "UPDATED 37S AGO");
+        assert_eq!(seconds(59), "UPDATED 59S AGO");
+        assert_eq!(seconds(60), "UPDATED 1M AGO");
+        assert_eq!(seconds(3599), "UPDATED 59M AGO");
+        assert_eq!(seconds(3600), "UPDATED 1H AGO");
+        assert_eq!(seconds(86_399), "UPDATED 23H AGO");
+        assert_eq!(seconds(3 * 86_400), "UPDATED 3D AGO");
+        // A fetch stamped by a clock slightly ahead of this one.
+        assert_eq!(seconds(-4), "UPDATED 0S AGO");
+    }
+
+    #[test]
+    fn last_commit_label_counts_whole_units() {
+        assert_eq!(last_commit_label(20), "LAST COMMIT NOW");
+        assert_eq!(last_commit_label(25 * 60), "LAST COMMIT 25M AGO");
+        assert_eq!(last_commit_label(3 * 3600 + 50 * 60), "LAST COMMIT 3H AGO");
+        assert_eq!(last_commit_label(9 * 86_400), "LAST COMMIT 9D AGO");
+    }
+
+    #[test]
+    fn updated_label_precision() {
+        assert_eq!(
+            updated_label(37, UpdatedPrecision::Minutes).as_deref(),
+            Some("UPDATED NOW")
+        );
+        assert_eq!(
+            updated_label(150, UpdatedPrecision::Minutes).as_deref(),
+            Some("UPDATED 2M AGO")
+        );
+        assert_eq!(updated_label(37, UpdatedPrecision::Off), None);
+        assert_eq!("minutes".parse(), Ok(UpdatedPrecision::Minutes));
+        assert_eq!("OFF".parse(), Ok(UpdatedPrecision::Off));
+        assert!("hourly".parse::<UpdatedPrecision>().is_err());
+    }
+
+    #[test]
+    fn timezone_label_shows_a_signed_offset() {
+        assert_eq!(timezone_label(Some("CET"), 3600), "CET +01:00");
+        assert_eq!(timezone_label(Some("CEST"), 7200), "CEST +02:00");
+        assert_eq!(timezone_label(Some("EST"), -5 * 3600), "EST -05:00");
+        assert_eq!(timezone_label(None, 5 * 3600 + 1800), "UTC +05:30");
+        assert_eq!(
+            timezone_label(Some("NDT"), -(2 * 3600 + 1800)),
+            "NDT -02:30"
+        );
+        assert_eq!(timezone_label(Some("GMT"), 0), "GMT +00:00");
+    }
+}
@@ -0,0 +1,107 @@
+use crate::platform;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+// A run longer than this is killed and shows nothing.
+const TIMEOUT: Duration = Duration::from_secs(5);
+// Output past this is ignored; only the first line is shown anyway.
+const MAX_BYTES: u64 = 4096;
+const MAX_CHARS: usize = 64;
+
+// Periodically runs a user command through the shell and keeps the first
+// line of its stdout, e.g. the VPN status or the current k8s context.
+// stdin is closed, stderr dropped, and runs never overlap.
+pub struct CommandWidget {
+    command: String,
+    interval: f64,
+    last_run: Option<f64>,
+    rx: Option<mpsc::Receiver<Option<String>>>,
+    line: Option<String>,
+}
+
+impl CommandWidget {
+    pub fn new(command: String, interval_secs: f64) -> Self {
+        CommandWidget {
+            command,
+            interval: interval_secs,
+            last_run: None,
+            rx: None,
+            line: None,
+        }
+    }
+
+    pub fn line(&mut self, now: f64) -> Option<&str> {
+        if let Some(rx) = &self.rx {
+            if let Ok(line) = rx.try_recv() {
+                self.line = line;
+                self.rx = None;
+            }
+        }
+        let due = self.last_run.is_none_or(|last| now - last >= self.interval);
+        if due && self.rx.is_none() {
+            self.last_run = Some(now);
+            let command = self.command.clone();
+            self.rx = Some(platform::background(move || run(&command)));
+        }
+        self.line.as_deref()
+    }
+}
+
+fn shell(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let mut shell = Command::new("cmd");
+        shell
+            .args(["/C", command])
+            .creation_flags(platform::CREATE_NO_WINDOW);
+        shell
+    }
+    #[cfg(not(windows))]
+    {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+fn run(command: &str) -> Option<String> {
+    let mut child = shell(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| log!("Failed to run `{}`: {}", command, e))
+        .ok()?;
+    let mut stdout = child.stdout.take()?;
+    // Read on the side so a chatty command cannot block on a full pipe.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = (&mut stdout).take(MAX_BYTES).read_to_end(&mut output);
+        let _ = tx.send(output);
+    });
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(50))
+            }
+            _ => {
+                log!("`{}` timed out", command);
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    // A background process the command left running may hold the pipe open.
+    let output = rx.recv_timeout(Duration::from_secs(1)).ok()?;
+    let output = String::from_utf8_lossy(&output);
+    let line = output.lines().next()?.trim();
+    (!line.is_empty()).then(|| line.chars().take(MAX_CHARS).collect())
+}
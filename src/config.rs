@@ -0,0 +1,732 @@
+use crate::animation::Celebration;
+use crate::avatars::AvatarStyle;
+use crate::calendar::FirstDay;
+use crate::cells::CellShape;
+use crate::clock::{ClockFormat, HourFormat, UpdatedPrecision};
+use crate::dashboards::{parse_dashboards, parse_settings, Dashboard};
+#[cfg(feature = "network")]
+use crate::export::ExportTarget;
+use crate::forges::{parse_instances, ForgeKind, Instance};
+use crate::layout::{AmPmStyle, Orientation, PrListPosition, SecondsStyle, TextOverflow};
+use crate::pages::{parse_pages, Page};
+use crate::platform;
+use crate::power::PowerSaveMode;
+use crate::release::{parse_calendar, Release};
+use crate::render::{StatusPalette, ThemeOverride};
+use crate::rules::{parse_rules, ColorRule};
+use crate::sound::QuietHours;
+use crate::speckles::{Intensity, Placement};
+use crate::weather::{TemperatureUnit, WeatherSource};
+use crate::webhooks::EVENT_NAMES;
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use macroquad::prelude::Color;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// One of several GitHub accounts (e.g. work and personal). Without an inline
+// token the keychain entry stored with `--store-github-token <label>` is used.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+pub struct GithubAccount {
+    pub label: String,
+    pub token: Option<String>,
+}
+
+// Where detected Jira keys in PR titles link to. A key whose project has
+// its own entry uses it, any other key the default; with neither the key
+// stays plain text.
+#[derive(Clone, Debug, Default)]
+pub struct JiraLinks {
+    pub default_url: Option<String>,
+    pub projects: Vec<(String, String)>,
+}
+
+impl JiraLinks {
+    pub fn url(&self, key: &str) -> Option<String> {
+        let project = key.split('-').next()?;
+        let base = self
+            .projects
+            .iter()
+            .find(|(prefix, _)| prefix == project)
+            .map(|(_, url)| url)
+            .or(self.default_url.as_ref())?;
+        Some(format!("{}{}", base, key))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub max_fps: f32,
+    pub power_save: PowerSaveMode,
+    pub webhook_urls: Vec<String>,
+    pub webhook_events: Vec<String>,
+    pub color_rules: Vec<ColorRule>,
+    pub menu_bar: bool,
+    pub team: Vec<String>,
+    pub release_repo: Option<String>,
+    pub main_ci: Option<String>,
+    pub release_calendar: Vec<Release>,
+    pub github_client_id: Option<String>,
+    pub forges: Vec<Instance>,
+    pub pr_list_position: PrListPosition,
+    pub orientation: Orientation,
+    pub pages: Vec<Page>,
+    pub first_day: FirstDay,
+    pub text_overflow: TextOverflow,
+    pub am_pm_style: AmPmStyle,
+    pub include_drafts: bool,
+    pub celebration: Celebration,
+    pub idle_dim_minutes: f64,
+    pub idle_brightness: f32,
+    pub github_accounts: Vec<GithubAccount>,
+    pub cell_shape: CellShape,
+    pub speckle_placement: Placement,
+    pub speckle_intensity: Intensity,
+    pub window_title: bool,
+    pub poll_secs: i64,
+    pub dock_badge: bool,
+    pub dashboards: Vec<Dashboard>,
+    // Used under the dashboards while the system is in dark or light mode.
+    pub dark_theme: Option<Dashboard>,
+    pub light_theme: Option<Dashboard>,
+    pub pr_count: usize,
+    pub watch_file: Option<PathBuf>,
+    pub avatars: AvatarStyle,
+    pub command: Option<String>,
+    pub command_secs: f64,
+    pub jira: JiraLinks,
+    pub pomodoro_minutes: f64,
+    pub share_url: Option<String>,
+    pub team_sync: bool,
+    pub team_sync_port: u16,
+    #[cfg(feature = "network")]
+    pub export: Option<ExportTarget>,
+    #[cfg(feature = "network")]
+    pub metrics_addr: Option<String>,
+    pub eink: bool,
+    pub high_contrast: bool,
+    pub frame_history: usize,
+    pub record_secs: f64,
+    pub record_fps: f64,
+    pub linear: bool,
+    pub linear_api_key: Option<String>,
+    pub wake_time: Option<NaiveTime>,
+    pub wake_ramp_minutes: f64,
+    pub show_timezone: bool,
+    pub demo_time: Option<DateTime<FixedOffset>>,
+    pub restore_session: bool,
+    pub texture_budget_kb: usize,
+    pub weather: WeatherSource,
+    // Latitude and longitude, for the weather and the sun times.
+    pub location: Option<(f64, f64)>,
+    pub weather_unit: TemperatureUnit,
+    pub weather_api_key: Option<String>,
+    pub weather_secs: f64,
+    pub sun_times: bool,
+    pub sunset_tint: bool,
+    pub night_hours: Option<QuietHours>,
+    pub night_brightness: f32,
+    pub updated_label: UpdatedPrecision,
+    pub time_format: Option<ClockFormat>,
+    pub date_format: Option<ClockFormat>,
+    // None follows the system.
+    pub hour_format: Option<HourFormat>,
+    pub blink_colon: bool,
+    pub seconds: SecondsStyle,
+    pub calendars: Vec<String>,
+    pub calendar_secs: f64,
+    pub desktop_notifications: bool,
+    pub chime: bool,
+    pub activity_sound: bool,
+    pub sound_volume: f32,
+    pub quiet_hours: Option<QuietHours>,
+    pub heatmap: bool,
+    pub last_commit: bool,
+    pub workflows: Vec<String>,
+    pub stale_days: Option<u32>,
+    pub stale_last: bool,
+    pub repos: Vec<String>,
+    pub pr_query: Option<String>,
+    pub drag_to_move: bool,
+    // Hex colors from the environment, under dashboards and color rules.
+    pub colors: ThemeOverride,
+    pub status_palette: StatusPalette,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_fps: 30.0,
+            power_save: PowerSaveMode::Auto,
+            webhook_urls: Vec::new(),
+            webhook_events: Vec::new(),
+            color_rules: Vec::new(),
+            menu_bar: false,
+            team: Vec::new(),
+            release_repo: None,
+            main_ci: None,
+            release_calendar: Vec::new(),
+            github_client_id: None,
+            forges: Vec::new(),
+            pr_list_position: PrListPosition::Bottom,
+            orientation: Orientation::Landscape,
+            pages: vec![Page::Clock, Page::Calendar, Page::Stats],
+            first_day: FirstDay::Monday,
+            text_overflow: TextOverflow::Wrap,
+            am_pm_style: AmPmStyle::Text,
+            include_drafts: true,
+            celebration: Celebration::NewYear,
+            idle_dim_minutes: 0.0,
+            idle_brightness: 0.3,
+            github_accounts: Vec::new(),
+            cell_shape: CellShape::Square,
+            speckle_placement: Placement::Checker,
+            speckle_intensity: Intensity::DEFAULT,
+            window_title: false,
+            poll_secs: 300,
+            dock_badge: true,
+            dashboards: Vec::new(),
+            dark_theme: None,
+            light_theme: None,
+            pr_count: 3,
+            watch_file: None,
+            avatars: AvatarStyle::Pixel,
+            command: None,
+            command_secs: 60.0,
+            jira: JiraLinks::default(),
+            pomodoro_minutes: 25.0,
+            share_url: None,
+            team_sync: false,
+            team_sync_port: 47474,
+            #[cfg(feature = "network")]
+            export: None,
+            #[cfg(feature = "network")]
+            metrics_addr: None,
+            eink: false,
+            high_contrast: false,
+            frame_history: 0,
+            record_secs: 5.0,
+            record_fps: 15.0,
+            linear: false,
+            linear_api_key: None,
+            wake_time: None,
+            wake_ramp_minutes: 30.0,
+            show_timezone: false,
+            demo_time: None,
+            restore_session: true,
+            texture_budget_kb: 1024,
+            weather: WeatherSource::Off,
+            location: None,
+            weather_unit: TemperatureUnit::Celsius,
+            weather_api_key: None,
+            weather_secs: 900.0,
+            sun_times: true,
+            sunset_tint: false,
+            night_hours: None,
+            night_brightness: 0.5,
+            updated_label: UpdatedPrecision::Seconds,
+            time_format: None,
+            date_format: None,
+            hour_format: None,
+            blink_colon: false,
+            seconds: SecondsStyle::Digits,
+            calendars: Vec::new(),
+            calendar_secs: 300.0,
+            desktop_notifications: false,
+            chime: false,
+            activity_sound: false,
+            sound_volume: 0.25,
+            quiet_hours: None,
+            heatmap: false,
+            last_commit: false,
+            workflows: Vec::new(),
+            stale_days: None,
+            stale_last: false,
+            repos: Vec::new(),
+            pr_query: None,
+            drag_to_move: false,
+            colors: ThemeOverride::default(),
+            status_palette: StatusPalette::DEFAULT,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let defaults = Config::default();
+        Config {
+            max_fps: env_parse::<f32>("CHRONO_MAX_FPS")
+                .filter(|fps| *fps > 0.0)
+                .unwrap_or(defaults.max_fps),
+            power_save: env_parse("CHRONO_POWER_SAVE").unwrap_or(defaults.power_save),
+            webhook_urls: env_list("CHRONO_WEBHOOK_URLS").unwrap_or(defaults.webhook_urls),
+            webhook_events: env_list("CHRONO_WEBHOOK_EVENTS")
+                .map(|events| {
+                    events
+                        .into_iter()
+                        .filter(|name| {
+                            let known = EVENT_NAMES.contains(&name.as_str());
+                            if !known {
+                                log!("Ignoring unknown webhook event in CHRONO_WEBHOOK_EVENTS: {name}");
+                            }
+                            known
+                        })
+                        .collect()
+                })
+                .unwrap_or(defaults.webhook_events),
+            color_rules: env_value("CHRONO_COLOR_RULES")
+                .map(|rules| parse_rules(&rules))
+                .unwrap_or(defaults.color_rules),
+            menu_bar: env_parse("CHRONO_MENU_BAR").unwrap_or(defaults.menu_bar),
+            team: env_list("CHRONO_TEAM").unwrap_or(defaults.team),
+            release_repo: env_value("CHRONO_RELEASE_REPO").or(defaults.release_repo),
+            main_ci: env_value("CHRONO_MAIN_CI").or(defaults.main_ci),
+            release_calendar: env_value("CHRONO_RELEASE_CALENDAR")
+                .map(|calendar| parse_calendar(&calendar, *platform::now().offset()))
+                .unwrap_or(defaults.release_calendar),
+            github_client_id: env_value("CHRONO_GITHUB_CLIENT_ID").or(defaults.github_client_id),
+            forges: env_forges().unwrap_or(defaults.forges),
+            pr_list_position: env_parse("CHRONO_PR_LIST_POSITION")
+                .unwrap_or(defaults.pr_list_position),
+            orientation: env_parse("CHRONO_ORIENTATION").unwrap_or(defaults.orientation),
+            pages: env_value("CHRONO_PAGES")
+                .map(|pages| parse_pages(&pages))
+                .unwrap_or(defaults.pages),
+            first_day: env_parse("CHRONO_FIRST_DAY").unwrap_or_else(FirstDay::from_env),
+            text_overflow: env_parse("CHRONO_TEXT_OVERFLOW").unwrap_or(defaults.text_overflow),
+            am_pm_style: env_parse("CHRONO_AM_PM_STYLE").unwrap_or(defaults.am_pm_style),
+            include_drafts: env_parse("CHRONO_INCLUDE_DRAFTS").unwrap_or(defaults.include_drafts),
+            celebration: env_parse("CHRONO_CELEBRATION").unwrap_or(defaults.celebration),
+            idle_dim_minutes: env_parse("CHRONO_IDLE_DIM_MINUTES")
+                .unwrap_or(defaults.idle_dim_minutes),
+            idle_brightness: env_parse("CHRONO_IDLE_BRIGHTNESS")
+                .unwrap_or(defaults.idle_brightness),
+            github_accounts: env_list("CHRONO_GITHUB_ACCOUNTS")
+                .map(|accounts| parse_accounts(&accounts))
+                .unwrap_or(defaults.github_accounts),
+            cell_shape: env_parse("CHRONO_CELL_SHAPE").unwrap_or(defaults.cell_shape),
+            speckle_placement: env_parse("CHRONO_SPECKLE_PLACEMENT")
+                .unwrap_or(defaults.speckle_placement),
+            speckle_intensity: Intensity {
+                density: if env_parse("CHRONO_SPECKLES").unwrap_or(true) {
+                    env_parse::<f32>("CHRONO_SPECKLE_DENSITY")
+                        .unwrap_or(defaults.speckle_intensity.density)
+                        .clamp(0.0, 4.0)
+                } else {
+                    0.0
+                },
+                brightness: env_parse::<f32>("CHRONO_SPECKLE_BRIGHTNESS")
+                    .unwrap_or(defaults.speckle_intensity.brightness)
+                    .clamp(0.0, 4.0),
+                animated: env_parse("CHRONO_SPECKLE_ANIMATION")
+                    .unwrap_or(defaults.speckle_intensity.animated),
+            },
+            window_title: env_parse("CHRONO_WINDOW_TITLE").unwrap_or(defaults.window_title),
+            // Below a minute the search API quota runs out quickly.
+            poll_secs: env_parse::<i64>("CHRONO_POLL_SECS")
+                .map(|secs| secs.max(60))
+                .unwrap_or(defaults.poll_secs),
+            dock_badge: env_parse("CHRONO_DOCK_BADGE").unwrap_or(defaults.dock_badge),
+            dashboards: env_value("CHRONO_DASHBOARDS")
+                .map(|dashboards| parse_dashboards(&dashboards))
+                .unwrap_or(defaults.dashboards),
+            dark_theme: env_value("CHRONO_DARK_THEME")
+                .map(|settings| parse_settings("dark", &settings))
+                .or(defaults.dark_theme),
+            light_theme: env_value("CHRONO_LIGHT_THEME")
+                .map(|settings| parse_settings("light", &settings))
+                .or(defaults.light_theme),
+            // The search API returns at most 100 items per page.
+            pr_count: env_parse::<usize>("CHRONO_PR_COUNT")
+                .map(|count| count.clamp(1, 100))
+                .unwrap_or(defaults.pr_count),
+            watch_file: env_value("CHRONO_WATCH_FILE")
+                .map(PathBuf::from)
+                .or(defaults.watch_file),
+            avatars: env_parse("CHRONO_AVATARS").unwrap_or(defaults.avatars),
+            command: env_value("CHRONO_COMMAND").or(defaults.command),
+            command_secs: env_parse::<f64>("CHRONO_COMMAND_SECS")
+                .map(|secs| secs.max(5.0))
+                .unwrap_or(defaults.command_secs),
+            jira: env_list("CHRONO_JIRA_URLS")
+                .map(|entries| parse_jira(&entries))
+                .unwrap_or(defaults.jira),
+            pomodoro_minutes: env_parse::<f64>("CHRONO_POMODORO_MINUTES")
+                .map(|minutes| minutes.max(1.0))
+                .unwrap_or(defaults.pomodoro_minutes),
+            share_url: env_value("CHRONO_SHARE_URL").or(defaults.share_url),
+            team_sync: env_parse("CHRONO_TEAM_SYNC").unwrap_or(defaults.team_sync),
+            team_sync_port: env_parse("CHRONO_TEAM_SYNC_PORT").unwrap_or(defaults.team_sync_port),
+            #[cfg(feature = "network")]
+            export: env_parse("CHRONO_EXPORT").or(defaults.export),
+            #[cfg(feature = "network")]
+            metrics_addr: env_value("CHRONO_METRICS_ADDR").or(defaults.metrics_addr),
+            eink: env_parse("CHRONO_EINK").unwrap_or(defaults.eink),
+            high_contrast: env_parse("CHRONO_HIGH_CONTRAST").unwrap_or(defaults.high_contrast),
+            // Each frame is a full screenshot held in memory.
+            frame_history: env_parse::<usize>("CHRONO_FRAME_HISTORY")
+                .map(|frames| frames.min(120))
+                .unwrap_or(defaults.frame_history),
+            record_secs: env_parse::<f64>("CHRONO_RECORD_SECS")
+                .map(|secs| secs.clamp(1.0, 60.0))
+                .unwrap_or(defaults.record_secs),
+            record_fps: env_parse::<f64>("CHRONO_RECORD_FPS")
+                .map(|fps| fps.clamp(1.0, 50.0))
+                .unwrap_or(defaults.record_fps),
+            // An inline key turns Linear on; `CHRONO_LINEAR=true` uses the
+            // keychain instead.
+            linear: env_parse("CHRONO_LINEAR")
+                .unwrap_or(env_value("CHRONO_LINEAR_API_KEY").is_some() || defaults.linear),
+            linear_api_key: env_value("CHRONO_LINEAR_API_KEY").or(defaults.linear_api_key),
+            wake_time: env_value("CHRONO_WAKE_TIME")
+                .and_then(|time| match NaiveTime::parse_from_str(&time, "%H:%M") {
+                    Ok(time) => Some(time),
+                    Err(_) => {
+                        log!("Ignoring invalid CHRONO_WAKE_TIME: {time}");
+                        None
+                    }
+                })
+                .or(defaults.wake_time),
+            wake_ramp_minutes: env_parse::<f64>("CHRONO_WAKE_RAMP_MINUTES")
+                .map(|minutes| minutes.max(1.0))
+                .unwrap_or(defaults.wake_ramp_minutes),
+            show_timezone: env_parse("CHRONO_SHOW_TIMEZONE").unwrap_or(defaults.show_timezone),
+            demo_time: env_value("CHRONO_DEMO_TIME")
+                .and_then(|time| match DateTime::parse_from_rfc3339(&time) {
+                    Ok(time) => Some(time),
+                    Err(_) => {
+                        log!("Ignoring invalid CHRONO_DEMO_TIME: {time}");
+                        None
+                    }
+                })
+                .or(defaults.demo_time),
+            restore_session: env_parse("CHRONO_RESTORE_SESSION")
+                .unwrap_or(defaults.restore_session),
+            texture_budget_kb: env_parse::<usize>("CHRONO_TEXTURE_BUDGET_KB")
+                .map(|kb| kb.max(64))
+                .unwrap_or(defaults.texture_budget_kb),
+            weather: env_parse("CHRONO_WEATHER").unwrap_or(defaults.weather),
+            // `CHRONO_WEATHER_LOCATION` is the name from before the sun
+            // times used it too.
+            location: ["CHRONO_LOCATION", "CHRONO_WEATHER_LOCATION"]
+                .into_iter()
+                .find_map(|key| Some((key, env_value(key)?)))
+                .and_then(|(key, location)| match parse_location(&location) {
+                    Some(location) => Some(location),
+                    None => {
+                        log!("Ignoring invalid {key}: {location}");
+                        None
+                    }
+                })
+                .or(defaults.location),
+            weather_unit: env_parse("CHRONO_WEATHER_UNIT").unwrap_or(defaults.weather_unit),
+            weather_api_key: env_value("CHRONO_WEATHER_API_KEY").or(defaults.weather_api_key),
+            // Both services update their current conditions every 10-15
+            // minutes.
+            weather_secs: env_parse::<f64>("CHRONO_WEATHER_SECS")
+                .map(|secs| secs.max(300.0))
+                .unwrap_or(defaults.weather_secs),
+            sun_times: env_parse("CHRONO_SUN_TIMES").unwrap_or(defaults.sun_times),
+            sunset_tint: env_parse("CHRONO_SUNSET_TINT").unwrap_or(defaults.sunset_tint),
+            night_hours: env_parse("CHRONO_NIGHT_HOURS").or(defaults.night_hours),
+            night_brightness: env_parse::<f32>("CHRONO_NIGHT_BRIGHTNESS")
+                .map(|brightness| brightness.clamp(0.0, 1.0))
+                .unwrap_or(defaults.night_brightness),
+            updated_label: env_parse("CHRONO_UPDATED_LABEL").unwrap_or(defaults.updated_label),
+            time_format: env_parse("CHRONO_TIME_FORMAT").or(defaults.time_format),
+            date_format: env_parse("CHRONO_DATE_FORMAT").or(defaults.date_format),
+            hour_format: env_value("CHRONO_HOUR_FORMAT")
+                .filter(|value| !value.eq_ignore_ascii_case("auto"))
+                .and_then(|_| env_parse("CHRONO_HOUR_FORMAT"))
+                .or(defaults.hour_format),
+            blink_colon: env_parse("CHRONO_BLINK_COLON").unwrap_or(defaults.blink_colon),
+            seconds: env_parse("CHRONO_SECONDS").unwrap_or(defaults.seconds),
+            calendars: env_list("CHRONO_CALENDARS").unwrap_or(defaults.calendars),
+            calendar_secs: env_parse::<f64>("CHRONO_CALENDAR_SECS")
+                .map(|secs| secs.max(60.0))
+                .unwrap_or(defaults.calendar_secs),
+            desktop_notifications: env_parse("CHRONO_DESKTOP_NOTIFICATIONS")
+                .unwrap_or(defaults.desktop_notifications),
+            chime: env_parse("CHRONO_CHIME").unwrap_or(defaults.chime),
+            activity_sound: env_parse("CHRONO_ACTIVITY_SOUND").unwrap_or(defaults.activity_sound),
+            sound_volume: env_parse::<f32>("CHRONO_SOUND_VOLUME")
+                .map(|volume| volume.clamp(0.0, 1.0))
+                .unwrap_or(defaults.sound_volume),
+            quiet_hours: env_parse("CHRONO_QUIET_HOURS").or(defaults.quiet_hours),
+            heatmap: env_parse("CHRONO_HEATMAP").unwrap_or(defaults.heatmap),
+            last_commit: env_parse("CHRONO_LAST_COMMIT").unwrap_or(defaults.last_commit),
+            workflows: env_list("CHRONO_WORKFLOWS").unwrap_or(defaults.workflows),
+            stale_days: env_parse::<u32>("CHRONO_STALE_DAYS")
+                .filter(|days| *days > 0)
+                .or(defaults.stale_days),
+            stale_last: env_parse("CHRONO_STALE_LAST").unwrap_or(defaults.stale_last),
+            repos: env_list("CHRONO_REPOS").unwrap_or(defaults.repos),
+            pr_query: env_value("CHRONO_PR_QUERY").or(defaults.pr_query),
+            drag_to_move: env_parse("CHRONO_DRAG_TO_MOVE").unwrap_or(defaults.drag_to_move),
+            colors: {
+                let active = env_color("CHRONO_ACTIVE_COLOR");
+                ThemeOverride {
+                    background_color: env_color("CHRONO_BACKGROUND_COLOR"),
+                    inactive_color: env_color("CHRONO_INACTIVE_COLOR"),
+                    active_color: active,
+                    // The noise pixels follow the accent unless set apart.
+                    noise_color: env_color("CHRONO_NOISE_COLOR").or(active),
+                    am_pm_color: env_color("CHRONO_AM_PM_COLOR"),
+                    hour_wave: env_parse("CHRONO_HOUR_WAVE"),
+                    hour_chirp: env_parse("CHRONO_HOUR_CHIRP"),
+                }
+            },
+            status_palette: {
+                let preset = env_parse("CHRONO_STATUS_PALETTE").unwrap_or(defaults.status_palette);
+                StatusPalette {
+                    success: env_color("CHRONO_SUCCESS_COLOR").unwrap_or(preset.success),
+                    pending: env_color("CHRONO_PENDING_COLOR").unwrap_or(preset.pending),
+                    failure: env_color("CHRONO_FAILURE_COLOR").unwrap_or(preset.failure),
+                    ..preset
+                }
+            },
+        }
+    }
+}
+
+impl Config {
+    // Debug output with tokens, webhook and calendar URLs blanked, for crash
+    // reports.
+    pub fn redacted_summary(&self) -> String {
+        let mut config = self.clone();
+        let hidden = "<redacted>".to_string();
+        for account in config.github_accounts.iter_mut() {
+            account.token = account.token.as_ref().map(|_| hidden.clone());
+        }
+        for instance in config.forges.iter_mut() {
+            instance.token = instance.token.as_ref().map(|_| hidden.clone());
+        }
+        config.linear_api_key = config.linear_api_key.as_ref().map(|_| hidden.clone());
+        config.weather_api_key = config.weather_api_key.as_ref().map(|_| hidden.clone());
+        // Private calendar links carry a token; file paths are kept.
+        for source in config.calendars.iter_mut() {
+            if source.contains("://") {
+                *source = hidden.clone();
+            }
+        }
+        config.webhook_urls = vec![hidden; config.webhook_urls.len()];
+        format!("{:#?}", config)
+    }
+}
+
+// `#RRGGBB` or `#RRGGBBAA`.
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+    let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(Color::from_rgba(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+        alpha,
+    ))
+}
+
+// `https://acme.atlassian.net/browse/,OPS=https://ops.atlassian.net/browse/`,
+// or `off` to leave keys unlinked.
+fn parse_jira(entries: &[String]) -> JiraLinks {
+    let mut links = JiraLinks::default();
+    for entry in entries {
+        if entry.eq_ignore_ascii_case("off") {
+            return JiraLinks::default();
+        }
+        let (project, url) = match entry.split_once('=') {
+            Some((project, url)) => (Some(project.trim()), url.trim()),
+            None => (None, entry.as_str()),
+        };
+        let url = if url.ends_with('/') {
+            url.to_string()
+        } else {
+            format!("{}/", url)
+        };
+        match project {
+            Some(project) => links.projects.push((project.to_string(), url)),
+            None => links.default_url = Some(url),
+        }
+    }
+    links
+}
+
+// `50.45,30.52`
+fn parse_location(value: &str) -> Option<(f64, f64)> {
+    let (latitude, longitude) = value.split_once(',')?;
+    let latitude = latitude.trim().parse::<f64>().ok()?;
+    let longitude = longitude.trim().parse::<f64>().ok()?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+// `work=TOKEN,personal`
+fn parse_accounts(entries: &[String]) -> Vec<GithubAccount> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (label, token) = match entry.split_once('=') {
+                Some((label, token)) => (label.trim(), Some(token.trim().to_string())),
+                None => (entry.as_str(), None),
+            };
+            if label.is_empty() {
+                return None;
+            }
+            Some(GithubAccount {
+                label: label.to_string(),
+                token: token.filter(|token| !token.is_empty()),
+            })
+        })
+        .collect()
+}
+
+fn env_value(key: &str) -> Option<String> {
+    let value = std::env::var(key).ok()?;
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn env_forges() -> Option<Vec<Instance>> {
+    let gitlab = env_value("CHRONO_GITLAB_INSTANCES");
+    let gitea = env_value("CHRONO_GITEA_INSTANCES");
+    // Forgejo speaks the Gitea API; the separate variable is for clarity.
+    let forgejo = env_value("CHRONO_FORGEJO_INSTANCES");
+    if gitlab.is_none() && gitea.is_none() && forgejo.is_none() {
+        return None;
+    }
+    let mut instances = Vec::new();
+    if let Some(gitlab) = gitlab {
+        instances.extend(parse_instances(ForgeKind::GitLab, &gitlab));
+    }
+    for gitea in [gitea, forgejo].into_iter().flatten() {
+        instances.extend(parse_instances(ForgeKind::Gitea, &gitea));
+    }
+    Some(instances)
+}
+
+fn env_parse<T: FromStr>(key: &str) -> Option<T> {
+    let value = env_value(key)?;
+    match value.parse::<T>() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            log!("Ignoring invalid {key}: {value}");
+            None
+        }
+    }
+}
+
+fn env_color(key: &str) -> Option<Color> {
+    let value = env_value(key)?;
+    let color = parse_hex_color(&value);
+    if color.is_none() {
+        log!("Ignoring invalid {key}: {value}");
+    }
+    color
+}
+
+fn env_list(key: &str) -> Option<Vec<String>> {
+    let value = env_value(key)?;
+    Some(
+        value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn jira_keys_stay_plain_text_by_default() {
+        assert_eq!(Config::default().jira.url("OPS-12"), None);
+    }
+
+    #[test]
+    fn reads_jira_defaults_and_projects() {
+        let links = parse_jira(&entries(&[
+            "https://acme.atlassian.net/browse",
+            "OPS = https://ops.atlassian.net/browse/",
+        ]));
+        assert_eq!(
+            links.url("WEB-7").as_deref(),
+            Some("https://acme.atlassian.net/browse/WEB-7")
+        );
+        assert_eq!(
+            links.url("OPS-12").as_deref(),
+            Some("https://ops.atlassian.net/browse/OPS-12")
+        );
+        let off = parse_jira(&entries(&["https://acme.atlassian.net/browse/", "off"]));
+        assert_eq!(off.url("WEB-7"), None);
+        assert!(off.projects.is_empty());
+    }
+
+    #[test]
+    fn reads_locations_in_range() {
+        assert_eq!(parse_location(" 50.45, 30.52 "), Some((50.45, 30.52)));
+        assert_eq!(parse_location("-33.9,151.2"), Some((-33.9, 151.2)));
+        assert_eq!(parse_location("91,0"), None);
+        assert_eq!(parse_location("0,181"), None);
+        assert_eq!(parse_location("Kyiv"), None);
+    }
+
+    #[test]
+    fn reads_accounts_with_and_without_tokens() {
+        let accounts = parse_accounts(&entries(&["work=ghp_1", "personal", "=ghp_2", "side="]));
+        let labels: Vec<(&str, Option<&str>)> = accounts
+            .iter()
+            .map(|account| (account.label.as_str(), account.token.as_deref()))
+            .collect();
+        assert_eq!(
+            labels,
+            [("work", Some("ghp_1")), ("personal", None), ("side", None)]
+        );
+    }
+
+    #[test]
+    fn reads_hex_colors() {
+        assert_eq!(
+            parse_hex_color("#ff8000"),
+            Some(Color::from_rgba(255, 128, 0, 255))
+        );
+        assert_eq!(
+            parse_hex_color("#ff800080"),
+            Some(Color::from_rgba(255, 128, 0, 128))
+        );
+        assert_eq!(parse_hex_color("ff8000"), None);
+        assert_eq!(parse_hex_color("#ff80"), None);
+        assert_eq!(parse_hex_color("#gg8000"), None);
+    }
+
+    // Names of its own, since tests share the process environment.
+    #[test]
+    fn blank_and_invalid_variables_fall_back() {
+        std::env::set_var("CHRONO_TEST_LIST", " a, ,b ,");
+        std::env::set_var("CHRONO_TEST_BLANK", "   ");
+        std::env::set_var("CHRONO_TEST_NUMBER", " 42 ");
+        std::env::set_var("CHRONO_TEST_NOT_A_NUMBER", "forty-two");
+        assert_eq!(env_list("CHRONO_TEST_LIST"), Some(entries(&["a", "b"])));
+        assert_eq!(env_list("CHRONO_TEST_BLANK"), None);
+        assert_eq!(env_list("CHRONO_TEST_UNSET"), None);
+        assert_eq!(env_parse::<u32>("CHRONO_TEST_NUMBER"), Some(42));
+        assert_eq!(env_parse::<u32>("CHRONO_TEST_NOT_A_NUMBER"), None);
+        assert_eq!(env_parse::<u32>("CHRONO_TEST_BLANK"), None);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::cells::CellShape;
+use crate::config::parse_hex_color;
+use crate::layout::PrListPosition;
+use crate::render::ThemeOverride;
+
+// A named set of overrides switched with the number keys. Unset fields
+// fall back to the base configuration.
+#[derive(Clone, Debug, Default)]
+pub struct Dashboard {
+    pub name: String,
+    pub theme: ThemeOverride,
+    pub cell_shape: Option<CellShape>,
+    pub pr_list_position: Option<PrListPosition>,
+    pub show_prs: Option<bool>,
+    pub show_team: Option<bool>,
+    pub effects: Option<bool>,
+    // 0 turns periodic polling off; R still refreshes.
+    pub poll_secs: Option<i64>,
+}
+
+fn parse_switch(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "on" | "true" | "1" => Ok(true),
+        "off" | "false" | "0" => Ok(false),
+        _ => Err(format!("expected on or off, got {}", value)),
+    }
+}
+
+fn apply_setting(dashboard: &mut Dashboard, key: &str, value: &str) -> Result<(), String> {
+    let color = || parse_hex_color(value).ok_or_else(|| format!("invalid color {}", value));
+    match key {
+        "accent" => {
+            let color = color()?;
+            dashboard.theme.active_color = Some(color);
+            dashboard.theme.noise_color = Some(color);
+        }
+        "background" => dashboard.theme.background_color = Some(color()?),
+        "inactive" => dashboard.theme.inactive_color = Some(color()?),
+        "am_pm" => dashboard.theme.am_pm_color = Some(color()?),
+        "shape" => dashboard.cell_shape = Some(value.parse()?),
+        "position" => dashboard.pr_list_position = Some(value.parse()?),
+        "prs" => dashboard.show_prs = Some(parse_switch(value)?),
+        "team" => dashboard.show_team = Some(parse_switch(value)?),
+        "effects" => dashboard.effects = Some(parse_switch(value)?),
+        "wave" => dashboard.theme.hour_wave = Some(parse_switch(value)?),
+        "chirp" => dashboard.theme.hour_chirp = Some(parse_switch(value)?),
+        "poll" => {
+            dashboard.poll_secs = Some(match value {
+                "off" => 0,
+                secs => secs
+                    .parse::<i64>()
+                    .map_err(|_| format!("invalid poll interval {}", secs))?
+                    .max(60),
+            })
+        }
+        _ => return Err(format!("unknown setting {}", key)),
+    }
+    Ok(())
+}
+
+impl Dashboard {
+    // This dashboard with anything it leaves unset taken from `base`.
+    pub fn over(self, base: &Dashboard) -> Dashboard {
+        Dashboard {
+            name: self.name,
+            theme: ThemeOverride {
+                background_color: self.theme.background_color.or(base.theme.background_color),
+                inactive_color: self.theme.inactive_color.or(base.theme.inactive_color),
+                active_color: self.theme.active_color.or(base.theme.active_color),
+                noise_color: self.theme.noise_color.or(base.theme.noise_color),
+                am_pm_color: self.theme.am_pm_color.or(base.theme.am_pm_color),
+                hour_wave: self.theme.hour_wave.or(base.theme.hour_wave),
+                hour_chirp: self.theme.hour_chirp.or(base.theme.hour_chirp),
+            },
+            cell_shape: self.cell_shape.or(base.cell_shape),
+            pr_list_position: self.pr_list_position.or(base.pr_list_position),
+            show_prs: self.show_prs.or(base.show_prs),
+            show_team: self.show_team.or(base.show_team),
+            effects: self.effects.or(base.effects),
+            poll_secs: self.poll_secs.or(base.poll_secs),
+        }
+    }
+}
+
+// A theme file: the dashboard keys as TOML, one `key = "value"` per line.
+// Comments and `[table]` headers are skipped.
+pub fn parse_theme(name: &str, source: &str) -> Dashboard {
+    let mut theme = Dashboard {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    for line in source.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let result = match line.split_once('=') {
+            Some((key, value)) => {
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                apply_setting(&mut theme, key.trim(), value.trim())
+            }
+            None => Err("expected key = value".to_string()),
+        };
+        if let Err(e) = result {
+            log!("Ignoring `{}` in theme {}: {}", line, name, e);
+        }
+    }
+    theme
+}
+
+// `clock: prs=off; team board: position=side, poll=120; focus: effects=off`
+pub fn parse_dashboards(source: &str) -> Vec<Dashboard> {
+    source
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .take(9)
+        .map(|entry| {
+            let (name, settings) = entry.split_once(':').unwrap_or((entry, ""));
+            parse_settings(name.trim(), settings)
+        })
+        .collect()
+}
+
+// `accent=#f0a030, effects=off`, the settings of one dashboard.
+pub fn parse_settings(name: &str, settings: &str) -> Dashboard {
+    let mut dashboard = Dashboard {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    for setting in settings.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let result = match setting.split_once('=') {
+            Some((key, value)) => apply_setting(&mut dashboard, key.trim(), value.trim()),
+            None => Err("expected key=value".to_string()),
+        };
+        if let Err(e) = result {
+            log!(
+                "Ignoring `{}` in dashboard {}: {}",
+                setting,
+                dashboard.name,
+                e
+            );
+        }
+    }
+    dashboard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_files_use_the_dashboard_keys() {
+        let theme = parse_theme(
+            "dusk",
+            "# Warm on dark\n[colors]\naccent = \"#f0a030\"\nbackground = '#101010'\nshape = \"round\"\nglow = \"on\"\n",
+        );
+        assert_eq!(theme.name, "dusk");
+        assert_eq!(theme.theme.active_color, parse_hex_color("#f0a030"));
+        assert_eq!(theme.theme.background_color, parse_hex_color("#101010"));
+        assert!(theme.cell_shape.is_some());
+        assert_eq!(theme.show_prs, None);
+    }
+
+    #[test]
+    fn dashboards_fall_back_to_the_theme() {
+        let theme = parse_theme("dusk", "accent = \"#f0a030\"\nprs = \"off\"\nwave = \"on\"");
+        let dashboard = parse_dashboards("focus: prs=on, chirp=on")
+            .remove(0)
+            .over(&theme);
+        assert_eq!(dashboard.name, "focus");
+        assert_eq!(dashboard.show_prs, Some(true));
+        assert_eq!(dashboard.theme.active_color, parse_hex_color("#f0a030"));
+        assert_eq!(dashboard.theme.hour_wave, Some(true));
+        assert_eq!(dashboard.theme.hour_chirp, Some(true));
+    }
+
+    #[test]
+    fn system_themes_are_dashboard_settings() {
+        let light = parse_settings("light", "background=#ffffff, inactive=#ebedf0, glow=on");
+        assert_eq!(light.name, "light");
+        assert_eq!(light.theme.background_color, parse_hex_color("#ffffff"));
+        assert_eq!(light.theme.inactive_color, parse_hex_color("#ebedf0"));
+        assert_eq!(light.theme.active_color, None);
+    }
+}
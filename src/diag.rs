@@ -0,0 +1,196 @@
+// Recent log lines and rendered frames, kept in memory and written out on
+// F12 or a panic so a glitch that lasted a second can still be reported.
+use macroquad::texture::Image;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+const LOG_LINES: usize = 200;
+
+static LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static FRAMES: Mutex<VecDeque<Image>> = Mutex::new(VecDeque::new());
+static CONFIG_SUMMARY: Mutex<String> = Mutex::new(String::new());
+static CRASHED: AtomicBool = AtomicBool::new(false);
+
+// Daily log files kept in `CHRONO_LOG_DIR`.
+#[cfg(not(target_arch = "wasm32"))]
+const LOG_FILES: usize = 7;
+
+// An info event that also lands in the in-memory log.
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)*) => {
+        $crate::diag::record(format!($($arg)*))
+    };
+}
+
+pub fn record(line: String) {
+    tracing::info!("{}", line);
+    let stamped = format!("{} {}", crate::platform::now().format("%H:%M:%S"), line);
+    if let Ok(mut log) = LOG.lock() {
+        if log.len() == LOG_LINES {
+            log.pop_front();
+        }
+        log.push_back(stamped);
+    }
+}
+
+// Info and above on stderr; `--verbose` adds debug events (queries, retries)
+// and `RUST_LOG=chrono=trace` full response bodies. Set up before the
+// config loads so its warnings are not lost, hence read from the env here.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_logging(verbose: bool) {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(if verbose {
+            "chrono=debug"
+        } else {
+            "chrono=info"
+        })
+    });
+    let file = std::env::var("CHRONO_LOG_DIR")
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .and_then(|dir| {
+            tracing_appender::rolling::Builder::new()
+                .rotation(tracing_appender::rolling::Rotation::DAILY)
+                .filename_prefix("chrono")
+                .filename_suffix("log")
+                .max_log_files(LOG_FILES)
+                .build(dir.trim())
+                .map_err(|e| eprintln!("Not writing a log file: {}", e))
+                .ok()
+        });
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_target(false).with_writer(std::io::stderr))
+        .with(file.map(|file| fmt::layer().with_ansi(false).with_writer(file)))
+        .try_init();
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn init_logging(_verbose: bool) {}
+
+// Included in crash reports; secrets must already be redacted.
+pub fn set_config_summary(summary: String) {
+    if let Ok(mut current) = CONFIG_SUMMARY.lock() {
+        *current = summary;
+    }
+}
+
+// Whether a worker thread has panicked; the board says so instead of
+// silently going stale.
+pub fn crashed() -> bool {
+    CRASHED.load(Ordering::Relaxed)
+}
+
+pub fn capture(frame: Image, keep: usize) {
+    if let Ok(mut frames) = FRAMES.lock() {
+        while frames.len() >= keep.max(1) {
+            frames.pop_front();
+        }
+        frames.push_back(frame);
+    }
+}
+
+// Frames come back from the GPU bottom row first and `export_png` flips the
+// whole strip, so the newest frame goes first to end up at the bottom.
+#[cfg(not(target_arch = "wasm32"))]
+fn strip(frames: &VecDeque<Image>) -> Option<Image> {
+    let width = frames.iter().map(|frame| frame.width).max()?;
+    let height = frames.iter().map(|frame| frame.height as u32).sum::<u32>();
+    let height = u16::try_from(height).ok()?;
+    let mut strip = Image::gen_image_color(width, height, macroquad::color::BLACK);
+    let stride = width as usize * 4;
+    let mut row = 0usize;
+    for frame in frames.iter().rev() {
+        let frame_stride = frame.width as usize * 4;
+        for line in frame.bytes.chunks_exact(frame_stride) {
+            strip.bytes[row * stride..row * stride + frame_stride].copy_from_slice(line);
+            row += 1;
+        }
+    }
+    Some(strip)
+}
+
+// Writes `<stamp>-frames.png` and `<stamp>-log.txt` under
+// `~/.cache/chrono/reports` and returns the directory. `try_lock` because a
+// panic may happen while a lock is held.
+#[cfg(not(target_arch = "wasm32"))]
+fn reports_dir() -> Option<std::path::PathBuf> {
+    let dir = crate::platform::cache_dir()?.join("reports");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn stamp() -> String {
+    crate::platform::now().format("%Y%m%d-%H%M%S").to_string()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn dump() -> Option<std::path::PathBuf> {
+    let dir = reports_dir()?;
+    let stamp = stamp();
+    if let Some(strip) = FRAMES.try_lock().ok().and_then(|frames| strip(&frames)) {
+        let path = dir.join(format!("{}-frames.png", stamp));
+        strip.export_png(&path.to_string_lossy());
+    }
+    let log = LOG
+        .try_lock()
+        .map(|log| log.iter().cloned().collect::<Vec<String>>().join("\n"))
+        .unwrap_or_default();
+    std::fs::write(dir.join(format!("{}-log.txt", stamp)), log).ok()?;
+    Some(dir)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn dump() -> Option<std::path::PathBuf> {
+    None
+}
+
+// `<stamp>-crash.txt`: the panic, a backtrace and the redacted config.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_crash_report(info: &std::panic::PanicHookInfo) -> Option<std::path::PathBuf> {
+    let thread = std::thread::current();
+    let config = CONFIG_SUMMARY
+        .try_lock()
+        .map(|summary| summary.clone())
+        .unwrap_or_default();
+    let report = format!(
+        "chrono {} crashed on thread {}\n{}\n\nBacktrace:\n{}\n\nConfig:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        thread.name().unwrap_or("unnamed"),
+        info,
+        std::backtrace::Backtrace::force_capture(),
+        config,
+    );
+    let path = reports_dir()?.join(format!("{}-crash.txt", stamp()));
+    std::fs::write(&path, report).ok()?;
+    Some(path)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_crash_report(_info: &std::panic::PanicHookInfo) -> Option<std::path::PathBuf> {
+    None
+}
+
+// Saves a crash report, frames and log before the default hook prints the
+// panic. A panic on the render thread ends the app, so only worker panics
+// get the on-board notice.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(path) = write_crash_report(info) {
+            eprintln!("Saved a crash report to {}", path.display());
+            CRASHED.store(true, Ordering::Relaxed);
+        }
+        if let Some(dir) = dump() {
+            eprintln!("Saved the last frames and log to {}", dir.display());
+        }
+        default_hook(info);
+    }));
+}
@@ -0,0 +1,89 @@
+// Why a fetch came back empty; only the network fetches produce these.
+#![cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChronoError {
+    // The token was missing, rejected (401) or lacks access (403).
+    Auth(String),
+    RateLimited,
+    // No answer, or not a usable one, even after retries.
+    Network(String),
+    // An answer that was not what the API documents.
+    Parse(String),
+}
+
+impl ChronoError {
+    // A few words for the board, next to the GitHub button.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChronoError::Auth(_) => "BAD TOKEN",
+            ChronoError::RateLimited => "RATE LIMITED",
+            ChronoError::Network(_) => "OFFLINE",
+            ChronoError::Parse(_) => "BAD RESPONSE",
+        }
+    }
+
+    // A short name for metrics labels.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChronoError::Auth(_) => "auth",
+            ChronoError::RateLimited => "rate_limited",
+            ChronoError::Network(_) => "network",
+            ChronoError::Parse(_) => "parse",
+        }
+    }
+
+    // Worth retrying soon rather than at the next regular poll.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ChronoError::Network(_))
+    }
+}
+
+impl fmt::Display for ChronoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChronoError::Auth(reason) => write!(f, "authentication failed: {}", reason),
+            ChronoError::RateLimited => write!(f, "rate limit reached"),
+            ChronoError::Network(reason) => write!(f, "network error: {}", reason),
+            ChronoError::Parse(reason) => write!(f, "unexpected response: {}", reason),
+        }
+    }
+}
+
+// Queries other than authentication still report plain strings.
+impl From<ChronoError> for String {
+    fn from(error: ChronoError) -> String {
+        error.to_string()
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+impl From<Box<ureq::Error>> for ChronoError {
+    fn from(error: Box<ureq::Error>) -> ChronoError {
+        match *error {
+            ureq::Error::Status(401 | 403, resp)
+                if resp.header("x-ratelimit-remaining") == Some("0") =>
+            {
+                ChronoError::RateLimited
+            }
+            ureq::Error::Status(429, _) => ChronoError::RateLimited,
+            ureq::Error::Status(code @ (401 | 403), _) => {
+                ChronoError::Auth(format!("status {}", code))
+            }
+            ureq::Error::Status(code, _) => ChronoError::Network(format!("status {}", code)),
+            e => ChronoError::Network(e.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl From<serde_json::Error> for ChronoError {
+    fn from(error: serde_json::Error) -> ChronoError {
+        ChronoError::Parse(error.to_string())
+    }
+}
@@ -0,0 +1,133 @@
+use serde_json::Value;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::str::FromStr;
+
+const INTERVAL_SECS: f64 = 1.0;
+
+// Where the board snapshot goes: newline-delimited JSON on stdout, or to
+// every client connected to a local TCP port.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportTarget {
+    Stdout,
+    Tcp(String),
+}
+
+impl FromStr for ExportTarget {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "stdout" | "-" => Ok(ExportTarget::Stdout),
+            other => match other.strip_prefix("tcp://") {
+                Some(addr) => Ok(ExportTarget::Tcp(addr.to_string())),
+                None if other.contains(':') => Ok(ExportTarget::Tcp(other.to_string())),
+                None => Err(format!("unknown export target {}", value)),
+            },
+        }
+    }
+}
+
+// Writes a JSON snapshot of the board once per second so external
+// renderers (a web page, an e-ink driver) can show the same content.
+pub struct Exporter {
+    listener: Option<TcpListener>,
+    clients: Vec<TcpStream>,
+    last: Option<f64>,
+    // Skip snapshots identical to the previous one (the e-ink profile).
+    only_changes: bool,
+    last_line: String,
+}
+
+impl Exporter {
+    pub fn new(target: &ExportTarget, only_changes: bool) -> Option<Self> {
+        let listener = match target {
+            ExportTarget::Stdout => None,
+            ExportTarget::Tcp(addr) => Some(
+                TcpListener::bind(addr)
+                    .and_then(|listener| {
+                        listener.set_nonblocking(true)?;
+                        Ok(listener)
+                    })
+                    .map_err(|e| log!("Board export unavailable on {}: {}", addr, e))
+                    .ok()?,
+            ),
+        };
+        Some(Exporter {
+            listener,
+            clients: Vec::new(),
+            last: None,
+            only_changes,
+            last_line: String::new(),
+        })
+    }
+
+    // `snapshot` only runs when a line is due.
+    pub fn tick(&mut self, now: f64, snapshot: impl FnOnce() -> Value) {
+        if self.last.is_some_and(|last| now - last < INTERVAL_SECS) {
+            return;
+        }
+        self.last = Some(now);
+        let line = format!("{}\n", snapshot());
+        if self.only_changes && line == self.last_line {
+            return;
+        }
+        self.last_line.clone_from(&line);
+        let Some(listener) = &self.listener else {
+            let mut stdout = std::io::stdout().lock();
+            let _ = stdout.write_all(line.as_bytes());
+            let _ = stdout.flush();
+            return;
+        };
+        while let Ok((client, _)) = listener.accept() {
+            if client.set_nonblocking(true).is_ok() {
+                self.clients.push(client);
+            }
+        }
+        // A client too slow to take a line is dropped rather than stalling
+        // the render loop.
+        self.clients
+            .retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Read;
+
+    #[test]
+    fn parses_export_targets() {
+        assert_eq!("stdout".parse(), Ok(ExportTarget::Stdout));
+        assert_eq!("-".parse(), Ok(ExportTarget::Stdout));
+        assert_eq!(
+            "tcp://127.0.0.1:7878".parse(),
+            Ok(ExportTarget::Tcp("127.0.0.1:7878".to_string()))
+        );
+        assert_eq!(
+            "localhost:7878".parse(),
+            Ok(ExportTarget::Tcp("localhost:7878".to_string()))
+        );
+        assert!("eink".parse::<ExportTarget>().is_err());
+    }
+
+    #[test]
+    fn skips_unchanged_snapshots_when_only_changes() {
+        let target = ExportTarget::Tcp("127.0.0.1:0".to_string());
+        let mut exporter = Exporter::new(&target, true).unwrap();
+        let addr = exporter.listener.as_ref().unwrap().local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).unwrap();
+
+        exporter.tick(0.0, || json!({ "time": "09:00" }));
+        // Not due yet, so the snapshot isn't even taken.
+        exporter.tick(0.5, || unreachable!());
+        exporter.tick(1.0, || json!({ "time": "09:00" }));
+        exporter.tick(2.0, || json!({ "time": "09:01" }));
+        drop(exporter);
+
+        let mut received = String::new();
+        client.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "{\"time\":\"09:00\"}\n{\"time\":\"09:01\"}\n");
+    }
+}
@@ -0,0 +1,467 @@
+// Polling providers off the render thread. Requests run on a small shared
+// pool of blocking workers and the render loop polls the combined future,
+// so providers and avatars load concurrently and a superseded fetch is
+// cancelled by dropping it. The web build has no threads and asks GitHub
+// through the page instead.
+use crate::actions::WorkflowRuns;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use crate::avatars::{self, AvatarStyle};
+use crate::config::Config;
+use crate::error::ChronoError;
+use crate::heatmap::ContributionDay;
+use crate::provider::{
+    Capabilities, CiState, CiStatus, ProviderFetch, RateLimits, ReviewLoad, ReviewStatus, WorkItem,
+};
+use crate::release::Release;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use crate::{cache, github, platform, provider, secrets};
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+use crate::{platform, provider};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    Unknown,
+    Connected,
+    Disconnected,
+    RateLimited,
+    // The last fetch hung or died and was abandoned.
+    TimedOut,
+    // GitHub did not answer even after retries; unlike Disconnected the
+    // token may be fine.
+    Unreachable,
+}
+
+// Everything one poll learned, merged across providers. `connected` is the
+// GitHub connection shown on the button.
+#[derive(Clone, Debug, Default)]
+pub struct FetchResult {
+    pub connected: bool,
+    // Why GitHub came back empty, when it did.
+    pub error: Option<ChronoError>,
+    pub capabilities: Capabilities,
+    pub prs: Vec<WorkItem>,
+    pub reviews: Vec<WorkItem>,
+    pub issues: Vec<WorkItem>,
+    pub ci: Vec<CiStatus>,
+    pub decisions: Vec<ReviewStatus>,
+    pub conflicts: Vec<String>,
+    pub review_load: Vec<ReviewLoad>,
+    pub releases: Vec<Release>,
+    pub unread: usize,
+    pub notifications: Vec<WorkItem>,
+    pub rate_limits: RateLimits,
+    // Unix time of the poll; None when nothing was fetched.
+    pub fetched_at: Option<i64>,
+    pub issue_links: Vec<(String, String)>,
+    // The primary account's, when the heatmap is on.
+    pub contributions: Vec<ContributionDay>,
+    // Unix time of the primary account's latest push.
+    pub last_push: Option<i64>,
+    pub workflows: Vec<WorkflowRuns>,
+    // CI on the `CHRONO_MAIN_CI` branch.
+    pub main_ci: Option<CiState>,
+    // The primary account's login.
+    pub login: Option<String>,
+}
+
+impl FetchResult {
+    pub fn merge(&mut self, fetched: ProviderFetch) {
+        self.capabilities = self.capabilities.union(fetched.capabilities);
+        self.prs.extend(fetched.prs);
+        self.reviews.extend(fetched.reviews);
+        self.issues.extend(fetched.issues);
+        self.ci.extend(fetched.ci);
+        self.decisions.extend(fetched.decisions);
+        self.conflicts.extend(fetched.conflicts);
+        self.issue_links.extend(fetched.issue_links);
+    }
+
+    // Folds in another account's poll; the extras only come from one account.
+    pub fn absorb(&mut self, other: FetchResult) {
+        self.connected |= other.connected;
+        self.error = self.error.take().or(other.error);
+        self.capabilities = self.capabilities.union(other.capabilities);
+        self.prs.extend(other.prs);
+        self.reviews.extend(other.reviews);
+        self.issues.extend(other.issues);
+        self.ci.extend(other.ci);
+        self.decisions.extend(other.decisions);
+        self.conflicts.extend(other.conflicts);
+        self.review_load.extend(other.review_load);
+        self.releases.extend(other.releases);
+        self.unread += other.unread;
+        self.notifications.extend(other.notifications);
+        self.contributions.extend(other.contributions);
+        self.last_push = self.last_push.or(other.last_push);
+        self.workflows.extend(other.workflows);
+        self.main_ci = self.main_ci.or(other.main_ci);
+        self.login = self.login.take().or(other.login);
+        self.rate_limits.merge(&other.rate_limits);
+    }
+}
+
+pub type Pending = Pin<Box<dyn Future<Output = FetchResult>>>;
+
+// The contribution calendar only moves a day at a time, so it is asked for
+// hourly rather than on every poll.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+const CONTRIBUTIONS_SECS: i64 = 60 * 60;
+
+// The last calendar, with the login it belongs to and when it was fetched.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+static CONTRIBUTIONS: Mutex<Option<(String, i64, Vec<ContributionDay>)>> = Mutex::new(None);
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn contributions(github: &github::GithubProvider, login: Option<&str>) -> Vec<ContributionDay> {
+    let now = platform::now().timestamp();
+    if let Ok(cached) = CONTRIBUTIONS.lock() {
+        if let Some((_, _, days)) = cached
+            .as_ref()
+            .filter(|(known, at, _)| Some(known.as_str()) == login && now - at < CONTRIBUTIONS_SECS)
+        {
+            return days.clone();
+        }
+    }
+    let days = github.contributions();
+    if let (Some(login), Ok(mut cached)) = (login, CONTRIBUTIONS.lock()) {
+        if !days.is_empty() {
+            *cached = Some((login.to_string(), now, days.clone()));
+        }
+    }
+    days
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn fetch_github(token: &str, label: Option<&str>, primary: bool, config: &Config) -> FetchResult {
+    let mut github = github::GithubProvider::new(
+        token,
+        config.include_drafts,
+        &config.repos,
+        config.pr_query.as_deref(),
+    );
+    let mut fetched = match provider::fetch(&mut github, config.pr_count) {
+        Ok(fetched) => fetched,
+        Err(error) => {
+            return FetchResult {
+                rate_limits: github.rate_limits(),
+                error: Some(error),
+                ..Default::default()
+            }
+        }
+    };
+    if let Some(label) = label {
+        fetched.label(label);
+    }
+    let mut result = FetchResult {
+        connected: true,
+        ..Default::default()
+    };
+    result.merge(fetched);
+    if primary {
+        result.login = github.viewer().map(str::to_string);
+        result.review_load = github.review_load(&config.team);
+        if let Some(repo) = config.release_repo.as_ref() {
+            result.releases = github.milestones(repo);
+        }
+        if let Some((unread, notifications)) = github.notifications(5) {
+            result.unread = unread;
+            result.notifications = notifications;
+        }
+        if config.heatmap {
+            result.contributions = contributions(&github, result.login.as_deref());
+        }
+        if config.last_commit {
+            result.last_push = github.last_push();
+        }
+        if !config.workflows.is_empty() {
+            result.workflows = github.workflow_runs(&config.workflows);
+        }
+        if let Some(entry) = config.main_ci.as_ref() {
+            result.main_ci = github.branch_ci(entry);
+        }
+    }
+    result.rate_limits = github.rate_limits();
+    result
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn spawn(github_token: Option<String>, config: Config) -> Pending {
+    Box::pin(async move {
+        let mut result = FetchResult::default();
+        // Configured accounts replace the single token. They are polled side
+        // by side; the first one also supplies review load, milestones and
+        // notifications.
+        let accounts: Vec<(Option<String>, Option<String>)> = if config.github_accounts.is_empty() {
+            github_token
+                .into_iter()
+                .map(|token| (None, Some(token)))
+                .collect()
+        } else {
+            config
+                .github_accounts
+                .iter()
+                .map(|account| (Some(account.label.clone()), account.token.clone()))
+                .collect()
+        };
+        let config = Arc::new(config);
+        // Every request starts on the pool right away; awaiting them in
+        // order below only orders the merge.
+        let github = accounts
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (label, token))| {
+                let config = config.clone();
+                blocking::unblock(move || {
+                    // The keychain may block too, so it is read on the pool.
+                    let token = token.or_else(|| secrets::account_token(label.as_deref()?));
+                    let Some(token) = token else {
+                        log!("GitHub account {}: no token", label.unwrap_or_default());
+                        return None;
+                    };
+                    Some(fetch_github(&token, label.as_deref(), idx == 0, &config))
+                })
+            })
+            .collect::<Vec<_>>();
+        let others = provider::registry(&config)
+            .into_iter()
+            .map(|mut provider| {
+                let limit = config.pr_count;
+                blocking::unblock(move || provider::fetch(provider.as_mut(), limit))
+            })
+            .collect::<Vec<_>>();
+        for account in github {
+            if let Some(account) = account.await {
+                result.absorb(account);
+            }
+        }
+        for fetched in others {
+            if let Ok(fetched) = fetched.await {
+                result.merge(fetched);
+            }
+        }
+        if config.avatars != AvatarStyle::Off {
+            let agent = avatars::agent();
+            let downloads =
+                avatars::missing(result.prs.iter().filter_map(|pr| pr.avatar_url.as_deref()))
+                    .into_iter()
+                    .map(|url| {
+                        let agent = agent.clone();
+                        blocking::unblock(move || avatars::download(&agent, &url))
+                    })
+                    .collect::<Vec<_>>();
+            for download in downloads {
+                download.await;
+            }
+        }
+        if result.connected || !result.prs.is_empty() {
+            result.fetched_at = Some(platform::now().timestamp());
+            result = blocking::unblock(move || {
+                cache::save(&result);
+                result
+            })
+            .await;
+        }
+        result
+    })
+}
+
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+async fn web_get(url: &str, token: &str) -> Result<serde_json::Value, ChronoError> {
+    let (status, body) = platform::http_get(url, token)
+        .await
+        .map_err(ChronoError::Network)?;
+    match status {
+        200..=299 => Ok(serde_json::from_str(&body)?),
+        401 | 403 => Err(ChronoError::Auth(format!("status {}", status))),
+        429 => Err(ChronoError::RateLimited),
+        _ => Err(ChronoError::Network(format!("status {}", status))),
+    }
+}
+
+// The signed-in account's open PRs and review requests; the rest of the
+// board's GitHub data needs the native build.
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+async fn fetch_github_web(token: &str, config: &Config) -> Result<FetchResult, ChronoError> {
+    let user = web_get("https://api.github.com/user", token).await?;
+    let login = user["login"]
+        .as_str()
+        .ok_or_else(|| ChronoError::Parse("no login in response".to_string()))?
+        .to_string();
+    let search = |terms: String| {
+        format!(
+            "https://api.github.com/search/issues?q={}&per_page={}&sort=updated&order=desc",
+            terms, config.pr_count
+        )
+    };
+    let drafts = if config.include_drafts {
+        ""
+    } else {
+        "+draft:false"
+    };
+    let prs = web_get(
+        &search(format!("is:pr+is:open+author:{}{}", login, drafts)),
+        token,
+    )
+    .await?;
+    let reviews = match web_get(
+        &search(format!("is:pr+is:open+review-requested:{}", login)),
+        token,
+    )
+    .await
+    {
+        Ok(json) => provider::search_items(&json, config.pr_count),
+        Err(e) => {
+            log!("GitHub: failed to fetch reviews: {}", e);
+            Vec::new()
+        }
+    };
+    Ok(FetchResult {
+        connected: true,
+        capabilities: Capabilities {
+            prs: true,
+            reviews: true,
+            ..Default::default()
+        },
+        prs: provider::search_items(&prs, config.pr_count),
+        reviews,
+        fetched_at: Some(platform::now().timestamp()),
+        login: Some(login),
+        ..Default::default()
+    })
+}
+
+// The `?token=` the page keeps in localStorage, used through the JS bridge
+// in web/chrono.js.
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+pub fn spawn(github_token: Option<String>, config: Config) -> Pending {
+    Box::pin(async move {
+        let Some(token) = github_token else {
+            return FetchResult::default();
+        };
+        fetch_github_web(&token, &config)
+            .await
+            .unwrap_or_else(|error| {
+                log!("GitHub: {}", error);
+                FetchResult {
+                    error: Some(error),
+                    ..Default::default()
+                }
+            })
+    })
+}
+
+// Without the network feature chrono is a plain clock and GitHub reads as
+// disconnected.
+#[cfg(not(feature = "network"))]
+pub fn spawn(_github_token: Option<String>, _config: Config) -> Pending {
+    Box::pin(std::future::ready(FetchResult::default()))
+}
+
+// Advances `pending` without blocking; the render loop calls this once a
+// frame. `Ready(None)` means a provider panicked and the fetch is lost.
+pub fn poll(pending: &mut Pending) -> Poll<Option<FetchResult>> {
+    let mut cx = Context::from_waker(Waker::noop());
+    match panic::catch_unwind(AssertUnwindSafe(|| pending.as_mut().poll(&mut cx))) {
+        Ok(Poll::Ready(result)) => Poll::Ready(Some(result)),
+        Ok(Poll::Pending) => Poll::Pending,
+        Err(_) => Poll::Ready(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::provider::mock::MockProvider;
+    use crate::provider::{self, RateLimit, RateResource};
+
+    fn limits(entries: &[(RateResource, u32, i64)]) -> RateLimits {
+        let mut limits = RateLimits::default();
+        for &(resource, remaining, reset) in entries {
+            limits.record(RateLimit {
+                resource,
+                remaining,
+                reset,
+            });
+        }
+        limits
+    }
+
+    #[test]
+    fn merges_each_provider_into_the_poll() {
+        let mut result = FetchResult::default();
+        let mut github = MockProvider::with_prs(&["fix parser"]);
+        github.capabilities.ci = true;
+        github.ci = vec![CiStatus {
+            url: "https://example.com/0".to_string(),
+            state: CiState::Success,
+        }];
+        let mut fetched = provider::fetch(&mut github, 5).unwrap();
+        fetched.label("work");
+        result.merge(fetched);
+        let mut gitea = MockProvider::with_prs(&["bump deps"]);
+        gitea.capabilities.reviews = true;
+        result.merge(provider::fetch(&mut gitea, 5).unwrap());
+
+        assert!(result.capabilities.prs && result.capabilities.ci);
+        assert!(result.capabilities.reviews && !result.capabilities.conflicts);
+        assert_eq!(result.prs.len(), 2);
+        assert_eq!(result.prs[0].account.as_deref(), Some("work"));
+        assert_eq!(result.prs[1].account, None);
+        assert_eq!(result.ci.len(), 1);
+    }
+
+    #[test]
+    fn a_failed_sign_in_is_absorbed_as_an_error() {
+        let mut provider = MockProvider::with_prs(&["fix parser"]);
+        provider.auth_error = Some(ChronoError::Auth("status 401".to_string()));
+        let error = provider::fetch(&mut provider, 5).unwrap_err();
+        let mut result = FetchResult {
+            connected: true,
+            prs: MockProvider::with_prs(&["other account"]).prs,
+            ..Default::default()
+        };
+        result.absorb(FetchResult {
+            error: Some(error.clone()),
+            ..Default::default()
+        });
+        assert!(result.connected);
+        assert_eq!(result.error, Some(error));
+        assert_eq!(result.prs.len(), 1);
+    }
+
+    #[test]
+    fn absorb_keeps_the_first_error_and_the_tighter_quota() {
+        let mut first = FetchResult {
+            error: Some(ChronoError::RateLimited),
+            rate_limits: limits(&[(RateResource::Core, 40, 100)]),
+            prs: vec![WorkItem::default()],
+            unread: 1,
+            ..Default::default()
+        };
+        first.absorb(FetchResult {
+            connected: true,
+            error: Some(ChronoError::Network("timeout".to_string())),
+            rate_limits: limits(&[
+                (RateResource::Core, 4000, 200),
+                (RateResource::Search, 5, 60),
+            ]),
+            prs: vec![WorkItem::default()],
+            unread: 2,
+            ..Default::default()
+        });
+        assert!(first.connected);
+        assert_eq!(first.error, Some(ChronoError::RateLimited));
+        let remaining = |resource| first.rate_limits.get(resource).map(|limit| limit.remaining);
+        assert_eq!(remaining(RateResource::Core), Some(40));
+        assert_eq!(remaining(RateResource::Search), Some(5));
+        assert_eq!(first.prs.len(), 2);
+        assert_eq!(first.unread, 3);
+    }
+}
@@ -0,0 +1,256 @@
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use crate::error::ChronoError;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use crate::provider::WorkItem;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use crate::provider::{self, parse_timestamp, Capabilities, Provider};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitLab,
+    // Forgejo speaks the same API.
+    Gitea,
+}
+
+// A self-hosted (or gitlab.com) instance. Without an inline token the
+// keychain entry stored with `--store-token <url>` is used.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+pub struct Instance {
+    pub kind: ForgeKind,
+    pub base_url: String,
+    pub token: Option<String>,
+}
+
+// `https://git.example.com=TOKEN,https://gitlab.com`
+pub fn parse_instances(kind: ForgeKind, source: &str) -> Vec<Instance> {
+    source
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let (url, token) = match entry.split_once('=') {
+                Some((url, token)) => (url.trim(), Some(token.trim().to_string())),
+                None => (entry, None),
+            };
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                log!("Ignoring forge instance `{}`: expected an http(s) URL", url);
+                return None;
+            }
+            Some(Instance {
+                kind,
+                base_url: url.trim_end_matches('/').to_string(),
+                token: token.filter(|token| !token.is_empty()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub struct ForgeProvider {
+    instance: Instance,
+    agent: ureq::Agent,
+    token: String,
+    // GitLab filters review requests by numeric user id.
+    user_id: Option<u64>,
+    include_drafts: bool,
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+impl ForgeProvider {
+    pub fn new(instance: Instance, include_drafts: bool) -> Self {
+        ForgeProvider {
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(4))
+                .build(),
+            token: String::new(),
+            user_id: None,
+            include_drafts,
+            instance,
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<serde_json::Value, ChronoError> {
+        let url = format!("{}{}", self.instance.base_url, path);
+        let request = self
+            .agent
+            .get(&url)
+            .set("User-Agent", "commit-clock")
+            .set("Accept", "application/json");
+        let request = match self.instance.kind {
+            ForgeKind::GitLab => request.set("PRIVATE-TOKEN", &self.token),
+            ForgeKind::Gitea => request.set("Authorization", &format!("token {}", self.token)),
+        };
+        let body = provider::send(&request, None)?
+            .into_string()
+            .map_err(|e| ChronoError::Network(e.to_string()))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    fn items(&self, path: &str, limit: usize, drafts: bool) -> Result<Vec<WorkItem>, String> {
+        let (url_key, author_key) = match self.instance.kind {
+            ForgeKind::GitLab => ("web_url", "author"),
+            ForgeKind::Gitea => ("html_url", "user"),
+        };
+        Ok(self
+            .get(path)?
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| {
+                        let title = item.get("title").and_then(|v| v.as_str())?;
+                        let url = item.get(url_key).and_then(|v| v.as_str())?;
+                        // Gitea marks drafts with a title prefix only.
+                        if !drafts && is_draft_title(title) {
+                            return None;
+                        }
+                        // GitLab numbers per project as `iid`; Gitea names
+                        // the repository alongside the item.
+                        let (repo, number) = match self.instance.kind {
+                            ForgeKind::GitLab => (
+                                url.split_once("/-/")
+                                    .and_then(|(project, _)| project.rsplit('/').next()),
+                                item.get("iid").and_then(|v| v.as_u64()),
+                            ),
+                            ForgeKind::Gitea => (
+                                item.get("repository")
+                                    .and_then(|repo| repo.get("full_name"))
+                                    .and_then(|v| v.as_str()),
+                                item.get("number").and_then(|v| v.as_u64()),
+                            ),
+                        };
+                        Some(WorkItem {
+                            title: title.to_string(),
+                            url: url.to_string(),
+                            repo: repo.map(str::to_string),
+                            number,
+                            updated_at: item
+                                .get("updated_at")
+                                .and_then(|v| v.as_str())
+                                .and_then(parse_timestamp),
+                            avatar_url: item
+                                .get(author_key)
+                                .and_then(|author| author.get("avatar_url"))
+                                .and_then(|v| v.as_str())
+                                .map(str::to_string),
+                            ..Default::default()
+                        })
+                    })
+                    .take(limit)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+impl Provider for ForgeProvider {
+    fn name(&self) -> &str {
+        &self.instance.base_url
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            prs: true,
+            reviews: true,
+            ci: false,
+            issues: true,
+            decisions: false,
+            conflicts: false,
+        }
+    }
+
+    fn auth(&mut self) -> Result<(), ChronoError> {
+        self.token = self
+            .instance
+            .token
+            .clone()
+            .or_else(|| crate::secrets::instance_token(&self.instance.base_url))
+            .ok_or_else(|| ChronoError::Auth("no token".to_string()))?;
+        let user = match self.instance.kind {
+            ForgeKind::GitLab => self.get("/api/v4/user")?,
+            ForgeKind::Gitea => self.get("/api/v1/user")?,
+        };
+        self.user_id = user.get("id").and_then(|v| v.as_u64());
+        Ok(())
+    }
+
+    fn fetch_prs(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        match self.instance.kind {
+            ForgeKind::GitLab => self.items(
+                &format!(
+                    "/api/v4/merge_requests?state=opened&scope=created_by_me&order_by=updated_at&per_page={}{}",
+                    limit,
+                    if self.include_drafts { "" } else { "&wip=no" }
+                ),
+                limit,
+                self.include_drafts,
+            ),
+            ForgeKind::Gitea => self.items(
+                &format!(
+                    "/api/v1/repos/issues/search?type=pulls&state=open&created=true&limit={}",
+                    limit
+                ),
+                limit,
+                self.include_drafts,
+            ),
+        }
+    }
+
+    fn fetch_issues(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        match self.instance.kind {
+            ForgeKind::GitLab => self.items(
+                &format!(
+                    "/api/v4/issues?state=opened&scope=assigned_to_me&order_by=updated_at&per_page={}",
+                    limit
+                ),
+                limit,
+                true,
+            ),
+            ForgeKind::Gitea => self.items(
+                &format!(
+                    "/api/v1/repos/issues/search?type=issues&state=open&assigned=true&limit={}",
+                    limit
+                ),
+                limit,
+                true,
+            ),
+        }
+    }
+
+    fn fetch_reviews(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        match self.instance.kind {
+            ForgeKind::GitLab => {
+                let user_id = self.user_id.ok_or("no user id")?;
+                self.items(
+                    &format!(
+                        "/api/v4/merge_requests?state=opened&scope=all&reviewer_id={}&per_page={}",
+                        user_id, limit
+                    ),
+                    limit,
+                    self.include_drafts,
+                )
+            }
+            ForgeKind::Gitea => self.items(
+                &format!(
+                    "/api/v1/repos/issues/search?type=pulls&state=open&review_requested=true&limit={}",
+                    limit
+                ),
+                limit,
+                self.include_drafts,
+            ),
+        }
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn is_draft_title(title: &str) -> bool {
+    let title = title.trim_start().to_ascii_lowercase();
+    ["wip:", "[wip]", "draft:", "[draft]"]
+        .iter()
+        .any(|prefix| title.starts_with(prefix))
+}
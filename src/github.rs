@@ -0,0 +1,849 @@
+use crate::actions::{self, WorkflowRuns};
+use crate::error::ChronoError;
+use crate::heatmap::{self, ContributionDay};
+use crate::provider::{
+    self, parse_timestamp, search_items, Capabilities, CiState, CiStatus, Provider, RateLimit,
+    RateLimits, RateResource, ReviewDecision, ReviewStatus,
+};
+use crate::provider::{ReviewLoad, WorkItem};
+use crate::release::Release;
+use std::cell::RefCell;
+use std::time::Duration;
+
+pub struct GithubProvider {
+    agent: ureq::Agent,
+    auth_header: String,
+    login: Option<String>,
+    include_drafts: bool,
+    // `CHRONO_REPOS`: orgs and `owner/repo`s to search, `-` in front to leave
+    // one out.
+    repos: Vec<String>,
+    // `CHRONO_PR_QUERY`, in place of the authored-PRs search.
+    query: Option<String>,
+    // Lowest quota seen per resource in any response so far.
+    rate_limits: RefCell<RateLimits>,
+    // Each PR's `/pulls/N` for this poll, by URL: CI reads the head commit
+    // and conflicts read `mergeable` from the same response.
+    pulls: RefCell<Vec<(String, serde_json::Value)>>,
+}
+
+impl GithubProvider {
+    pub fn new(token: &str, include_drafts: bool, repos: &[String], query: Option<&str>) -> Self {
+        GithubProvider {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(4))
+                .build(),
+            auth_header: format!("Bearer {}", token),
+            login: None,
+            include_drafts,
+            repos: repos.to_vec(),
+            query: query.map(str::to_string),
+            rate_limits: RefCell::default(),
+            pulls: RefCell::default(),
+        }
+    }
+
+    // The signed-in account, once `auth` has run.
+    pub fn viewer(&self) -> Option<&str> {
+        self.login.as_deref()
+    }
+
+    pub fn rate_limits(&self) -> RateLimits {
+        self.rate_limits.borrow().clone()
+    }
+
+    fn record_rate_limit(&self, resp: &ureq::Response) {
+        let header = |name: &str| {
+            resp.header(name)
+                .and_then(|value| value.trim().parse().ok())
+        };
+        let (Some(remaining), Some(reset)) = (
+            header("x-ratelimit-remaining").map(|value: i64| value as u32),
+            header("x-ratelimit-reset"),
+        ) else {
+            return;
+        };
+        self.rate_limits.borrow_mut().record(RateLimit {
+            resource: RateResource::from_header(resp.header("x-ratelimit-resource")),
+            remaining,
+            reset,
+        });
+    }
+
+    fn get(&self, url: &str) -> Result<serde_json::Value, ChronoError> {
+        let request = self
+            .agent
+            .get(url)
+            .set("User-Agent", "commit-clock")
+            .set("Authorization", &self.auth_header)
+            .set("Accept", "application/vnd.github+json");
+        let resp = provider::send(&request, None).map_err(|e| {
+            // Errors carry the rate-limit headers too, which is exactly
+            // when they matter.
+            if let ureq::Error::Status(_, resp) = e.as_ref() {
+                self.record_rate_limit(resp);
+            }
+            ChronoError::from(e)
+        })?;
+        self.record_rate_limit(&resp);
+        let status = resp.status();
+        if !(200..300).contains(&status) {
+            return Err(ChronoError::Network(format!("status {}", status)));
+        }
+        let body = resp
+            .into_string()
+            .map_err(|e| ChronoError::Network(e.to_string()))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    fn graphql(&self, query: &str) -> Result<serde_json::Value, ChronoError> {
+        let request = self
+            .agent
+            .post("https://api.github.com/graphql")
+            .set("User-Agent", "commit-clock")
+            .set("Authorization", &self.auth_header)
+            .set("Content-Type", "application/json");
+        let body = serde_json::json!({ "query": query }).to_string();
+        let resp = provider::send(&request, Some(&body)).map_err(|e| {
+            if let ureq::Error::Status(_, resp) = e.as_ref() {
+                self.record_rate_limit(resp);
+            }
+            ChronoError::from(e)
+        })?;
+        self.record_rate_limit(&resp);
+        let body = resp
+            .into_string()
+            .map_err(|e| ChronoError::Network(e.to_string()))?;
+        let json: serde_json::Value = serde_json::from_str(&body)?;
+        if let Some(message) = json["errors"][0]["message"].as_str() {
+            return Err(ChronoError::Parse(message.to_string()));
+        }
+        Ok(json["data"].clone())
+    }
+
+    fn login(&self) -> Result<&str, String> {
+        self.login.as_deref().ok_or("not authenticated".to_string())
+    }
+
+    // Falls back to scanning recently updated repos when the search index
+    // has not caught up yet.
+    fn scan_repos_for_prs(&self, login: &str, limit: usize) -> Result<Vec<WorkItem>, String> {
+        let repos_url = "https://api.github.com/user/repos?affiliation=owner,collaborator,organization_member&per_page=50&sort=updated";
+        tracing::debug!(url = repos_url, "GitHub repos query");
+        let repos_json = self.get(repos_url)?;
+        tracing::trace!(body = %repos_json, "GitHub repos response");
+
+        let repos = repos_json
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.get("full_name").and_then(|v| v.as_str()))
+                    .take(20)
+                    .map(|name| name.to_string())
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+
+        let mut matches: Vec<(String, WorkItem)> = Vec::new();
+        for repo in repos {
+            let pulls_url = format!(
+                "https://api.github.com/repos/{}/pulls?state=open&per_page=10&sort=updated&direction=desc",
+                repo
+            );
+            let Ok(pulls_json) = self.get(&pulls_url) else {
+                continue;
+            };
+            let Some(pulls) = pulls_json.as_array() else {
+                continue;
+            };
+
+            for pr in pulls {
+                let author = pr
+                    .get("user")
+                    .and_then(|u| u.get("login"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                let title = pr.get("title").and_then(|v| v.as_str()).unwrap_or("");
+                let url = pr.get("html_url").and_then(|v| v.as_str()).unwrap_or("");
+                let updated = pr.get("updated_at").and_then(|v| v.as_str()).unwrap_or("");
+                let draft = pr.get("draft").and_then(|v| v.as_bool()).unwrap_or(false);
+                let avatar_url = pr
+                    .get("user")
+                    .and_then(|u| u.get("avatar_url"))
+                    .and_then(|v| v.as_str());
+
+                if author == login
+                    && (self.include_drafts || !draft)
+                    && in_scope(&repo, &self.repos)
+                {
+                    matches.push((
+                        updated.to_string(),
+                        WorkItem {
+                            title: title.to_string(),
+                            url: url.to_string(),
+                            repo: Some(repo.clone()),
+                            number: pr.get("number").and_then(|v| v.as_u64()),
+                            updated_at: parse_timestamp(updated),
+                            avatar_url: avatar_url.map(str::to_string),
+                            ..Default::default()
+                        },
+                    ));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
+        Ok(matches.into_iter().map(|(_, pr)| pr).take(limit).collect())
+    }
+
+    // One GraphQL query for the whole team, which leaves the search quota
+    // to the PR, review and issue lists.
+    pub fn review_load(&self, team: &[String]) -> Vec<ReviewLoad> {
+        if team.is_empty() {
+            return Vec::new();
+        }
+        match self.graphql(&review_load_query(team)) {
+            Ok(data) => parse_review_load(team, &data),
+            Err(e) => {
+                log!("Review load for {} failed: {}", team.join(", "), e);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn milestones(&self, repo: &str) -> Vec<Release> {
+        let milestones_url = format!(
+            "https://api.github.com/repos/{}/milestones?state=open&sort=due_on&direction=asc&per_page=20",
+            repo
+        );
+        tracing::debug!(url = %milestones_url, "GitHub milestones query");
+        let milestones_json = self.get(&milestones_url).unwrap_or(serde_json::Value::Null);
+        let mut releases = Vec::new();
+        if let Some(items) = milestones_json.as_array() {
+            for item in items {
+                let title = item.get("title").and_then(|v| v.as_str());
+                let due = item
+                    .get("due_on")
+                    .and_then(|v| v.as_str())
+                    .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok());
+                let url = item.get("html_url").and_then(|v| v.as_str());
+                if let (Some(title), Some(due)) = (title, due) {
+                    releases.push(Release {
+                        title: title.to_string(),
+                        due,
+                        url: url.map(|url| url.to_string()),
+                    });
+                }
+            }
+        }
+        releases
+    }
+
+    // Unread notification count and the latest subjects, linked to their
+    // web pages where GitHub provides one.
+    pub fn notifications(&self, limit: usize) -> Option<(usize, Vec<WorkItem>)> {
+        let json = self
+            .get("https://api.github.com/notifications?per_page=50")
+            .ok()?;
+        let items = json.as_array()?;
+        let latest = items
+            .iter()
+            .filter_map(|item| {
+                let subject = item.get("subject")?;
+                let title = subject.get("title").and_then(|v| v.as_str())?;
+                let url = subject
+                    .get("url")
+                    .and_then(|v| v.as_str())
+                    .map(api_to_web_url)
+                    .or_else(|| {
+                        item.get("repository")
+                            .and_then(|repo| repo.get("html_url"))
+                            .and_then(|v| v.as_str())
+                            .map(|url| url.to_string())
+                    })?;
+                Some(WorkItem {
+                    title: title.to_string(),
+                    url,
+                    ..Default::default()
+                })
+            })
+            .take(limit)
+            .collect();
+        Some((items.len(), latest))
+    }
+
+    // The viewer's contribution calendar for the heatmap strip.
+    pub fn contributions(&self) -> Vec<ContributionDay> {
+        let query = "{ viewer { contributionsCollection { contributionCalendar { weeks { contributionDays { date contributionCount } } } } } }";
+        match self.graphql(query) {
+            Ok(data) => heatmap::parse_calendar(
+                &data["viewer"]["contributionsCollection"]["contributionCalendar"],
+            ),
+            Err(e) => {
+                log!("Contribution calendar failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    // Unix time of the viewer's latest push, from their recent events.
+    pub fn last_push(&self) -> Option<i64> {
+        let url = format!(
+            "https://api.github.com/users/{}/events?per_page=30",
+            self.login().ok()?
+        );
+        let json = self.get(&url).ok()?;
+        json.as_array()?
+            .iter()
+            .find(|event| event["type"] == "PushEvent")
+            .and_then(|event| event["created_at"].as_str())
+            .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| at.timestamp())
+    }
+
+    // The latest runs of each `owner/repo/workflow.yml`; a workflow that
+    // cannot be read is left out.
+    pub fn workflow_runs(&self, workflows: &[String]) -> Vec<WorkflowRuns> {
+        workflows
+            .iter()
+            .filter_map(|entry| {
+                let (repo, workflow) = actions::parse_workflow(entry)?;
+                let url = format!(
+                    "https://api.github.com/repos/{}/actions/workflows/{}/runs?per_page={}",
+                    repo,
+                    workflow,
+                    actions::RUNS
+                );
+                match self.get(&url) {
+                    Ok(json) => Some(WorkflowRuns {
+                        label: actions::workflow_label(repo, workflow),
+                        runs: actions::parse_runs(&json),
+                    }),
+                    Err(e) => {
+                        log!("Workflow runs for {} failed: {}", entry, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn pull(&self, owner: &str, repo: &str, number: &str) -> Result<serde_json::Value, String> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}",
+            owner, repo, number
+        );
+        if let Some((_, pr)) = self.pulls.borrow().iter().find(|(known, _)| *known == url) {
+            return Ok(pr.clone());
+        }
+        let pr = self.get(&url)?;
+        self.pulls.borrow_mut().push((url, pr.clone()));
+        Ok(pr)
+    }
+
+    // The CI state of a PR's head commit.
+    fn ci_state(&self, pr_url: &str) -> Result<Option<CiState>, String> {
+        let Some((owner, repo, number)) = parse_pr_url(pr_url) else {
+            return Ok(None);
+        };
+        let pr = self.pull(owner, repo, number)?;
+        let Some(sha) = pr
+            .get("head")
+            .and_then(|head| head.get("sha"))
+            .and_then(|v| v.as_str())
+        else {
+            return Ok(None);
+        };
+        self.commit_ci_state(&format!("{}/{}", owner, repo), sha)
+    }
+
+    // The CI state of the tip of `CHRONO_MAIN_CI`'s branch.
+    pub fn branch_ci(&self, entry: &str) -> Option<CiState> {
+        let (repo, branch) = main_branch(entry);
+        self.commit_ci_state(repo, branch).unwrap_or_else(|e| {
+            log!("CI on {} failed: {}", entry, e);
+            None
+        })
+    }
+
+    // `git_ref` is a commit SHA or a branch name.
+    fn commit_ci_state(&self, repo: &str, git_ref: &str) -> Result<Option<CiState>, String> {
+        let runs = self.get(&format!(
+            "https://api.github.com/repos/{}/commits/{}/check-runs?per_page=100",
+            repo, git_ref
+        ))?;
+        let combined = self.get(&format!(
+            "https://api.github.com/repos/{}/commits/{}/status",
+            repo, git_ref
+        ))?;
+        Ok(combine_ci(&runs, &combined))
+    }
+
+    // GitHub works `mergeable` out in the background and answers null until
+    // it has, so a fresh PR shows its conflicts from the next poll on.
+    fn has_conflicts(&self, pr_url: &str) -> Result<bool, String> {
+        let Some((owner, repo, number)) = parse_pr_url(pr_url) else {
+            return Ok(false);
+        };
+        let pr = self.pull(owner, repo, number)?;
+        Ok(pr.get("mergeable").and_then(|v| v.as_bool()) == Some(false))
+    }
+
+    // The latest decisive review of each reviewer counts; one request for
+    // changes outweighs any approvals.
+    fn review_decision(&self, pr_url: &str) -> Result<Option<ReviewDecision>, String> {
+        let Some((owner, repo, number)) = parse_pr_url(pr_url) else {
+            return Ok(None);
+        };
+        let reviews = self.get(&format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}/reviews?per_page=100",
+            owner, repo, number
+        ))?;
+        let mut latest: Vec<(String, String)> = Vec::new();
+        for review in reviews.as_array().into_iter().flatten() {
+            let user = review
+                .get("user")
+                .and_then(|u| u.get("login"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let state = review.get("state").and_then(|v| v.as_str()).unwrap_or("");
+            if !matches!(state, "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED") {
+                continue;
+            }
+            latest.retain(|(login, _)| login != user);
+            latest.push((user.to_string(), state.to_string()));
+        }
+        Ok(Some(
+            if latest.iter().any(|(_, state)| state == "CHANGES_REQUESTED") {
+                ReviewDecision::ChangesRequested
+            } else if latest.iter().any(|(_, state)| state == "APPROVED") {
+                ReviewDecision::Approved
+            } else {
+                ReviewDecision::Pending
+            },
+        ))
+    }
+}
+
+// `acme` searches the org, `acme/api` the repo, and a leading `-` excludes
+// either: `+org:acme+-repo:acme/legacy`.
+fn scope_qualifiers(repos: &[String]) -> String {
+    repos
+        .iter()
+        .map(|entry| {
+            let (not, name) = match entry.strip_prefix('-') {
+                Some(name) => ("-", name),
+                None => ("", entry.as_str()),
+            };
+            let kind = if name.contains('/') { "repo" } else { "org" };
+            format!("+{}{}:{}", not, kind, name)
+        })
+        .collect()
+}
+
+// An aliased `search` per teammate, `m0` for the first. Logins are letters,
+// digits and hyphens, so anything else is left out rather than quoted.
+fn review_load_query(team: &[String]) -> String {
+    let searches = team
+        .iter()
+        .enumerate()
+        .filter(|(_, member)| {
+            member
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        })
+        .map(|(idx, member)| {
+            format!(
+                "m{}: search(query: \"is:pr is:open review-requested:{}\", type: ISSUE) {{ issueCount }}",
+                idx, member
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("{{ {} }}", searches.join(" "))
+}
+
+fn parse_review_load(team: &[String], data: &serde_json::Value) -> Vec<ReviewLoad> {
+    team.iter()
+        .enumerate()
+        .filter_map(|(idx, member)| {
+            let Some(count) = data[format!("m{}", idx)]["issueCount"].as_u64() else {
+                log!("Review load for {} failed", member);
+                return None;
+            };
+            Some(ReviewLoad {
+                login: member.clone(),
+                count: count as usize,
+            })
+        })
+        .collect()
+}
+
+// A `{login}` template as the `q` parameter: terms joined with `+` and
+// anything else URL-encoded, so `label:"needs review"` survives.
+fn search_terms(template: &str, login: &str) -> String {
+    template
+        .replace("{login}", login)
+        .split_whitespace()
+        .map(|term| {
+            term.bytes()
+                .map(|byte| match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b':' | b'/' => {
+                        (byte as char).to_string()
+                    }
+                    _ => format!("%{:02X}", byte),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+// The same rules for the repo scan the search falls back on.
+fn in_scope(repo: &str, repos: &[String]) -> bool {
+    let matches = |name: &str| {
+        repo.eq_ignore_ascii_case(name)
+            || repo
+                .split_once('/')
+                .is_some_and(|(owner, _)| owner.eq_ignore_ascii_case(name))
+    };
+    let (excluded, included): (Vec<&String>, Vec<&String>) =
+        repos.iter().partition(|entry| entry.starts_with('-'));
+    !excluded.iter().any(|entry| matches(&entry[1..]))
+        && (included.is_empty() || included.iter().any(|entry| matches(entry)))
+}
+
+// Combines check runs (GitHub Actions and apps) with the legacy commit
+// statuses that other CI services still report through; None when the
+// commit has neither.
+fn combine_ci(runs: &serde_json::Value, combined: &serde_json::Value) -> Option<CiState> {
+    let runs = runs
+        .get("check_runs")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let has_statuses = combined
+        .get("total_count")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+        > 0;
+    let combined_state = combined
+        .get("state")
+        .and_then(|v| v.as_str())
+        .filter(|_| has_statuses);
+    if runs.is_empty() && combined_state.is_none() {
+        return None;
+    }
+    let failed = matches!(combined_state, Some("failure" | "error"))
+        || runs.iter().any(|run| {
+            matches!(
+                run.get("conclusion").and_then(|v| v.as_str()),
+                Some("failure" | "timed_out" | "cancelled" | "action_required")
+            )
+        });
+    let pending = combined_state == Some("pending")
+        || runs
+            .iter()
+            .any(|run| run.get("status").and_then(|v| v.as_str()) != Some("completed"));
+    Some(if failed {
+        CiState::Failure
+    } else if pending {
+        CiState::Pending
+    } else {
+        CiState::Success
+    })
+}
+
+// `owner/repo@branch`, the branch defaulting to `main`.
+fn main_branch(entry: &str) -> (&str, &str) {
+    entry
+        .trim()
+        .split_once('@')
+        .unwrap_or((entry.trim(), "main"))
+}
+
+// https://api.github.com/repos/{owner}/{repo}/pulls/{number} points at
+// https://github.com/{owner}/{repo}/pull/{number}, likewise for issues.
+fn api_to_web_url(url: &str) -> String {
+    url.replacen("https://api.github.com/repos/", "https://github.com/", 1)
+        .replacen("/pulls/", "/pull/", 1)
+}
+
+// https://github.com/{owner}/{repo}/pull/{number}
+fn parse_pr_url(url: &str) -> Option<(&str, &str, &str)> {
+    let path = url.strip_prefix("https://github.com/")?;
+    let mut parts = path.split('/');
+    let owner = parts.next()?;
+    let repo = parts.next()?;
+    if parts.next()? != "pull" {
+        return None;
+    }
+    let number = parts.next()?;
+    Some((owner, repo, number))
+}
+
+// `query` for each PR. One that fails is logged and left out so the rest
+// still show; the part only fails when every PR does.
+fn each_pr<T>(
+    prs: &[WorkItem],
+    what: &str,
+    query: impl Fn(&WorkItem) -> Result<Option<T>, String>,
+) -> Result<Vec<T>, String> {
+    let mut found = Vec::new();
+    let mut failure = None;
+    let mut answered = prs.is_empty();
+    for pr in prs {
+        match query(pr) {
+            Ok(item) => {
+                answered = true;
+                found.extend(item);
+            }
+            Err(e) => {
+                log!("GitHub: {} of {} failed: {}", what, pr.url, e);
+                failure = Some(e);
+            }
+        }
+    }
+    match failure {
+        Some(e) if !answered => Err(e),
+        _ => Ok(found),
+    }
+}
+
+impl Provider for GithubProvider {
+    fn name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            prs: true,
+            reviews: true,
+            ci: true,
+            issues: true,
+            decisions: true,
+            conflicts: true,
+        }
+    }
+
+    fn auth(&mut self) -> Result<(), ChronoError> {
+        let user_json = self.get("https://api.github.com/user")?;
+        tracing::trace!(body = %user_json, "GitHub user response");
+        let login = user_json
+            .get("login")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| ChronoError::Parse("no login in response".to_string()))?;
+        self.login = Some(login.to_string());
+        Ok(())
+    }
+
+    fn fetch_prs(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        let login = self.login()?;
+        if let Some(query) = self.query.as_ref() {
+            // Exactly what was asked for, without the draft and scope
+            // qualifiers; there is no scan to fall back on.
+            let query = format!(
+                "https://api.github.com/search/issues?q={}&per_page={}&sort=updated&order=desc",
+                search_terms(query, login),
+                limit
+            );
+            tracing::debug!(url = %query, "GitHub custom PR query");
+            return Ok(search_items(&self.get(&query)?, limit));
+        }
+        let drafts = if self.include_drafts {
+            ""
+        } else {
+            "+draft:false"
+        };
+        let query = format!(
+            "https://api.github.com/search/issues?q=is:pr+is:open+author:{}{}{}&per_page={}&sort=updated&order=desc",
+            login,
+            drafts,
+            scope_qualifiers(&self.repos),
+            limit
+        );
+        tracing::debug!(url = %query, "GitHub PR query");
+        let prs_json = self.get(&query)?;
+        tracing::trace!(body = %prs_json, "GitHub PR response");
+        let prs = search_items(&prs_json, limit);
+        if prs.is_empty() {
+            return self.scan_repos_for_prs(login, limit);
+        }
+        Ok(prs)
+    }
+
+    fn fetch_reviews(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        let reviews_query = format!(
+            "https://api.github.com/search/issues?q=is:pr+is:open+review-requested:{}{}&per_page={}&sort=updated&order=desc",
+            self.login()?,
+            scope_qualifiers(&self.repos),
+            limit
+        );
+        tracing::debug!(url = %reviews_query, "GitHub review query");
+        Ok(search_items(&self.get(&reviews_query)?, limit))
+    }
+
+    fn fetch_issues(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        let issues_query = format!(
+            "https://api.github.com/search/issues?q=is:issue+is:open+assignee:{}&per_page={}&sort=updated&order=desc",
+            self.login()?,
+            limit
+        );
+        tracing::debug!(url = %issues_query, "GitHub issues query");
+        Ok(search_items(&self.get(&issues_query)?, limit))
+    }
+
+    fn fetch_ci(&self, prs: &[WorkItem]) -> Result<Vec<CiStatus>, String> {
+        each_pr(prs, "CI status", |pr| {
+            Ok(self.ci_state(&pr.url)?.map(|state| CiStatus {
+                url: pr.url.clone(),
+                state,
+            }))
+        })
+    }
+
+    fn fetch_decisions(&self, prs: &[WorkItem]) -> Result<Vec<ReviewStatus>, String> {
+        each_pr(prs, "review decision", |pr| {
+            Ok(self.review_decision(&pr.url)?.map(|decision| ReviewStatus {
+                url: pr.url.clone(),
+                decision,
+            }))
+        })
+    }
+
+    fn fetch_conflicts(&self, prs: &[WorkItem]) -> Result<Vec<String>, String> {
+        each_pr(prs, "mergeability", |pr| {
+            Ok(self.has_conflicts(&pr.url)?.then(|| pr.url.clone()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repos(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn scopes_the_search_to_orgs_and_repos() {
+        assert_eq!(scope_qualifiers(&[]), "");
+        assert_eq!(
+            scope_qualifiers(&repos(&["acme", "o/chrono", "-acme/legacy"])),
+            "+org:acme+repo:o/chrono+-repo:acme/legacy"
+        );
+        let scope = repos(&["acme", "-acme/legacy"]);
+        assert!(in_scope("acme/api", &scope));
+        assert!(in_scope("Acme/api", &scope));
+        assert!(!in_scope("acme/legacy", &scope));
+        assert!(!in_scope("o/chrono", &scope));
+        assert!(in_scope("o/chrono", &repos(&["-acme"])));
+        assert!(in_scope("o/chrono", &[]));
+    }
+
+    #[test]
+    fn combines_check_runs_and_statuses() {
+        use serde_json::json;
+        let no_statuses = json!({ "state": "pending", "total_count": 0 });
+        assert_eq!(combine_ci(&json!({ "check_runs": [] }), &no_statuses), None);
+        let runs = json!({ "check_runs": [
+            { "status": "completed", "conclusion": "success" },
+            { "status": "in_progress", "conclusion": null },
+        ] });
+        assert_eq!(combine_ci(&runs, &no_statuses), Some(CiState::Pending));
+        let failing = json!({ "state": "failure", "total_count": 1 });
+        assert_eq!(combine_ci(&runs, &failing), Some(CiState::Failure));
+        let green = json!({ "check_runs": [{ "status": "completed", "conclusion": "success" }] });
+        assert_eq!(combine_ci(&green, &no_statuses), Some(CiState::Success));
+    }
+
+    #[test]
+    fn reads_the_main_branch_entry() {
+        assert_eq!(main_branch("o/chrono"), ("o/chrono", "main"));
+        assert_eq!(main_branch(" o/chrono@trunk "), ("o/chrono", "trunk"));
+    }
+
+    #[test]
+    fn fills_in_the_custom_query() {
+        assert_eq!(
+            search_terms("is:pr  is:open review-requested:{login}", "octo"),
+            "is:pr+is:open+review-requested:octo"
+        );
+        assert_eq!(
+            search_terms("is:pr label:\"needs review\" team:acme/web", "octo"),
+            "is:pr+label:%22needs+review%22+team:acme/web"
+        );
+    }
+
+    #[test]
+    fn counts_the_team_in_one_query() {
+        let team = repos(&["octo", "bad\" }", "hub-bot"]);
+        assert_eq!(
+            review_load_query(&team),
+            "{ m0: search(query: \"is:pr is:open review-requested:octo\", type: ISSUE) { issueCount } \
+             m2: search(query: \"is:pr is:open review-requested:hub-bot\", type: ISSUE) { issueCount } }"
+        );
+        let data = serde_json::json!({ "m0": { "issueCount": 4 }, "m2": { "issueCount": 0 } });
+        let load = parse_review_load(&team, &data);
+        let counts: Vec<(&str, usize)> = load
+            .iter()
+            .map(|member| (member.login.as_str(), member.count))
+            .collect();
+        assert_eq!(counts, [("octo", 4), ("hub-bot", 0)]);
+    }
+
+    #[test]
+    fn a_failing_pr_leaves_the_others() {
+        let prs: Vec<WorkItem> = ["one", "two", "three"]
+            .iter()
+            .map(|title| WorkItem {
+                title: title.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let titles = |failing: &[&str]| {
+            each_pr(&prs, "title", |pr| {
+                if failing.contains(&pr.title.as_str()) {
+                    return Err(format!("{} failed", pr.title));
+                }
+                Ok((pr.title != "three").then(|| pr.title.clone()))
+            })
+        };
+        assert_eq!(titles(&["one"]), Ok(vec!["two".to_string()]));
+        assert_eq!(titles(&["one", "two"]), Ok(Vec::new()));
+        assert_eq!(
+            titles(&["one", "two", "three"]),
+            Err("three failed".to_string())
+        );
+        assert_eq!(each_pr(&[], "title", |_| Ok(Some(()))), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn conflicts_come_from_the_pull_already_fetched() {
+        use serde_json::json;
+        let github = GithubProvider::new("token", false, &[], None);
+        // As `ci_state` leaves them; no request is made for these.
+        for (number, mergeable) in [("1", json!(false)), ("2", json!(true)), ("3", json!(null))] {
+            github.pulls.borrow_mut().push((
+                format!("https://api.github.com/repos/o/chrono/pulls/{}", number),
+                json!({ "head": { "sha": "abc" }, "mergeable": mergeable }),
+            ));
+        }
+        let prs: Vec<WorkItem> = ["1", "2", "3"]
+            .iter()
+            .map(|number| WorkItem {
+                url: format!("https://github.com/o/chrono/pull/{}", number),
+                ..Default::default()
+            })
+            .chain([WorkItem {
+                url: "https://gitlab.com/o/chrono/-/merge_requests/4".to_string(),
+                ..Default::default()
+            }])
+            .collect();
+        assert_eq!(
+            github.fetch_conflicts(&prs),
+            Ok(vec!["https://github.com/o/chrono/pull/1".to_string()])
+        );
+    }
+}
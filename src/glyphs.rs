@@ -0,0 +1,344 @@
+// The 5x7 pixel font the board is drawn in. Each glyph is seven rows of
+// `#` (lit) and `.`; columns left blank on both sides are trimmed when
+// text is laid out.
+
+pub fn glyph_pattern(ch: char) -> [&'static str; 7] {
+    match ch {
+        // Pixel Operator-inspired 5x7 glyphs.
+        '0' => [
+            ".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###.",
+        ],
+        '1' => [
+            "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+        '2' => [
+            ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+        ],
+        '3' => [
+            ".###.", "#...#", "....#", "..##.", "....#", "#...#", ".###.",
+        ],
+        '4' => [
+            "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+        ],
+        '5' => [
+            "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+        ],
+        '6' => [
+            ".###.", "#...#", "#....", "####.", "#...#", "#...#", ".###.",
+        ],
+        '7' => [
+            "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+        ],
+        '8' => [
+            ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+        ],
+        '9' => [
+            ".###.", "#...#", "#...#", ".####", "....#", "#...#", ".###.",
+        ],
+        ':' => ["...", ".#.", ".#.", "...", ".#.", ".#.", "..."],
+        '-' => ["...", "...", "...", "###", "...", "...", "..."],
+        '+' => ["...", "...", ".#.", "###", ".#.", "...", "..."],
+        '…' => [
+            ".....", ".....", ".....", ".....", ".....", ".....", "#.#.#",
+        ],
+        '.' => ["...", "...", "...", "...", "...", "...", ".#."],
+        '°' => [".#.", "#.#", ".#.", "...", "...", "...", "..."],
+        '↑' => [
+            "..#..", ".###.", "#.#.#", "..#..", "..#..", "..#..", "..#..",
+        ],
+        '↓' => [
+            "..#..", "..#..", "..#..", "..#..", "#.#.#", ".###.", "..#..",
+        ],
+        // Weather conditions, drawn next to the date.
+        '☀' => [
+            ".....", "#.#.#", ".###.", "#####", ".###.", "#.#.#", ".....",
+        ],
+        '☁' => [
+            ".....", "..##.", ".#..#", "#...#", "#####", ".....", ".....",
+        ],
+        '☂' => [
+            "..##.", ".####", "#####", ".....", ".#.#.", "#.#..", ".....",
+        ],
+        '❄' => [
+            "..#..", "#.#.#", ".###.", "..#..", ".###.", "#.#.#", "..#..",
+        ],
+        '⚡' => [
+            "...#.", "..#..", ".#...", "#####", "...#.", "..#..", ".#...",
+        ],
+        '≡' => [
+            ".....", "#####", ".....", ".####", ".....", "####.", ".....",
+        ],
+        'A' => [
+            ".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+        'B' => [
+            "####.", "#...#", "#...#", "####.", "#...#", "#...#", "####.",
+        ],
+        'C' => [
+            ".###.", "#...#", "#....", "#....", "#....", "#...#", ".###.",
+        ],
+        'D' => [
+            "####.", "#...#", "#...#", "#...#", "#...#", "#...#", "####.",
+        ],
+        'E' => [
+            "#####", "#....", "#....", "####.", "#....", "#....", "#####",
+        ],
+        'F' => [
+            "#####", "#....", "#....", "####.", "#....", "#....", "#....",
+        ],
+        'G' => [
+            ".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".###.",
+        ],
+        'H' => [
+            "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+        'I' => [
+            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "#####",
+        ],
+        'J' => [
+            "..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##..",
+        ],
+        'K' => [
+            "#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#",
+        ],
+        'L' => [
+            "#....", "#....", "#....", "#....", "#....", "#....", "#####",
+        ],
+        'M' => [
+            "#...#", "##.##", "#.#.#", "#...#", "#...#", "#...#", "#...#",
+        ],
+        'N' => [
+            "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#", "#...#",
+        ],
+        'O' => [
+            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+        'P' => [
+            "####.", "#...#", "#...#", "####.", "#....", "#....", "#....",
+        ],
+        'Q' => [
+            ".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#",
+        ],
+        'R' => [
+            "####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#",
+        ],
+        'S' => [
+            ".###.", "#....", "#....", ".###.", "....#", "....#", "###..",
+        ],
+        'T' => [
+            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#..",
+        ],
+        'U' => [
+            "#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+        'V' => [
+            "#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#..",
+        ],
+        'W' => [
+            "#...#", "#...#", "#...#", "#.#.#", "#.#.#", "##.##", "#...#",
+        ],
+        'X' => [
+            "#...#", ".#.#.", "..#..", "..#..", "..#..", ".#.#.", "#...#",
+        ],
+        'Y' => [
+            "#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#..",
+        ],
+        'Z' => [
+            "#####", "....#", "...#.", "..#..", ".#...", "#....", "#####",
+        ],
+        ' ' => [
+            ".....", ".....", ".....", ".....", ".....", ".....", ".....",
+        ],
+        _ => [
+            ".....", ".....", ".....", ".....", ".....", ".....", ".....",
+        ],
+    }
+}
+
+pub fn glyph_bounds(glyph: [&'static str; 7]) -> Option<(usize, usize)> {
+    let mut min_x = usize::MAX;
+    let mut max_x = 0usize;
+    let mut found = false;
+    for line in glyph.iter() {
+        for (idx, cell) in line.chars().enumerate() {
+            if cell == '#' {
+                min_x = min_x.min(idx);
+                max_x = max_x.max(idx);
+                found = true;
+            }
+        }
+    }
+    if found {
+        Some((min_x, max_x))
+    } else {
+        None
+    }
+}
+
+// Anything without a glyph is drawn as a blank space.
+pub fn has_glyph(ch: char) -> bool {
+    ch == ' ' || glyph_bounds(glyph_pattern(ch)).is_some()
+}
+
+pub fn space_width_cols() -> f32 {
+    3.0
+}
+
+// `text` with every digit as `0`, the widest, so a time measured this way
+// keeps its size while its digits tick over.
+pub fn widest_digits(text: &str) -> String {
+    text.chars()
+        .map(|ch| if ch.is_ascii_digit() { '0' } else { ch })
+        .collect()
+}
+
+// The lit cells of `text` as 7 rows of `#` and `.`, laid out the way
+// `draw_pixel_text` places them.
+pub fn glyph_rows(text: &str) -> Vec<String> {
+    let mut rows = vec![String::new(); 7];
+    for (idx, ch) in text.chars().enumerate() {
+        if idx > 0 {
+            rows.iter_mut().for_each(|row| row.push('.'));
+        }
+        let glyph = glyph_pattern(ch);
+        match glyph_bounds(glyph) {
+            Some((min_x, max_x)) => {
+                for (row, line) in rows.iter_mut().zip(glyph.iter()) {
+                    row.push_str(&line[min_x..=max_x]);
+                }
+            }
+            None => rows
+                .iter_mut()
+                .for_each(|row| row.push_str(&".".repeat(space_width_cols() as usize))),
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // Every glyph with its lit cell count and trimmed columns, so an edit to
+    // the table shows up here.
+    pub(crate) const SNAPSHOT: [(char, usize, (usize, usize)); 50] = [
+        ('0', 19, (0, 4)),
+        ('1', 10, (1, 3)),
+        ('2', 14, (0, 4)),
+        ('3', 14, (0, 4)),
+        ('4', 14, (0, 4)),
+        ('5', 17, (0, 4)),
+        ('6', 17, (0, 4)),
+        ('7', 11, (0, 4)),
+        ('8', 17, (0, 4)),
+        ('9', 17, (0, 4)),
+        (':', 4, (1, 1)),
+        ('-', 3, (0, 2)),
+        ('+', 5, (0, 2)),
+        ('…', 3, (0, 4)),
+        ('.', 1, (1, 1)),
+        ('°', 4, (0, 2)),
+        ('↑', 11, (0, 4)),
+        ('↓', 11, (0, 4)),
+        ('☀', 17, (0, 4)),
+        ('☁', 11, (0, 4)),
+        ('☂', 15, (0, 4)),
+        ('❄', 15, (0, 4)),
+        ('⚡', 11, (0, 4)),
+        ('≡', 13, (0, 4)),
+        ('A', 18, (0, 4)),
+        ('B', 20, (0, 4)),
+        ('C', 13, (0, 4)),
+        ('D', 18, (0, 4)),
+        ('E', 18, (0, 4)),
+        ('F', 14, (0, 4)),
+        ('G', 17, (0, 4)),
+        ('H', 17, (0, 4)),
+        ('I', 15, (0, 4)),
+        ('J', 11, (0, 4)),
+        ('K', 14, (0, 4)),
+        ('L', 11, (0, 4)),
+        ('M', 17, (0, 4)),
+        ('N', 17, (0, 4)),
+        ('O', 16, (0, 4)),
+        ('P', 15, (0, 4)),
+        ('Q', 17, (0, 4)),
+        ('R', 18, (0, 4)),
+        ('S', 13, (0, 4)),
+        ('T', 11, (0, 4)),
+        ('U', 15, (0, 4)),
+        ('V', 13, (0, 4)),
+        ('W', 18, (0, 4)),
+        ('X', 11, (0, 4)),
+        ('Y', 10, (0, 4)),
+        ('Z', 15, (0, 4)),
+    ];
+
+    #[test]
+    fn glyph_table_matches_the_snapshot() {
+        for (ch, lit, bounds) in SNAPSHOT {
+            let glyph = glyph_pattern(ch);
+            let cells = glyph
+                .iter()
+                .map(|line| line.matches('#').count())
+                .sum::<usize>();
+            assert_eq!(
+                (cells, glyph_bounds(glyph)),
+                (lit, Some(bounds)),
+                "{:?}",
+                ch
+            );
+            assert!(has_glyph(ch));
+        }
+        assert!(has_glyph(' '));
+        assert!(!has_glyph('?'));
+    }
+
+    #[test]
+    fn glyph_rows_share_one_width() {
+        for (ch, ..) in SNAPSHOT {
+            let glyph = glyph_pattern(ch);
+            let width = glyph[0].len();
+            assert!(
+                glyph
+                    .iter()
+                    .all(|line| line.len() == width && line.chars().all(|c| c == '#' || c == '.')),
+                "{:?}",
+                ch
+            );
+        }
+    }
+
+    #[test]
+    fn bounds_skip_blank_columns() {
+        assert_eq!(glyph_bounds(glyph_pattern('0')), Some((0, 4)));
+        assert_eq!(glyph_bounds(glyph_pattern('1')), Some((1, 3)));
+        assert_eq!(glyph_bounds(glyph_pattern(':')), Some((1, 1)));
+        assert_eq!(glyph_bounds(glyph_pattern(' ')), None);
+    }
+
+    #[test]
+    fn rows_put_one_blank_column_between_glyphs() {
+        let rows = glyph_rows("1:");
+        assert_eq!(rows.len(), 7);
+        assert_eq!(rows[0], ".#...");
+        assert_eq!(rows[1], "##..#");
+        assert_eq!(rows[6], "###..");
+    }
+
+    #[test]
+    fn spaces_are_blank_columns() {
+        let rows = glyph_rows("1 1");
+        assert!(rows.iter().all(|row| row.len() == 3 + 1 + 3 + 1 + 3));
+        assert!(rows.iter().all(|row| &row[3..8] == "....."));
+    }
+
+    #[test]
+    fn no_digit_is_wider_than_zero() {
+        assert_eq!(widest_digits("11:47:09.1 PM"), "00:00:00.0 PM");
+        let width = glyph_rows("0")[0].len();
+        for digit in '0'..='9' {
+            assert!(glyph_rows(&digit.to_string())[0].len() <= width);
+        }
+    }
+}
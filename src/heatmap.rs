@@ -0,0 +1,111 @@
+// The last seven weeks of the viewer's GitHub contribution calendar, drawn
+// as a strip of cells along the bottom edge (`CHRONO_HEATMAP`).
+use chrono::{Datelike, NaiveDate};
+
+pub const WEEKS: usize = 7;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContributionDay {
+    pub date: NaiveDate,
+    pub count: u32,
+}
+
+// The days of the last `WEEKS` weeks from the GraphQL `contributionCalendar`,
+// oldest first. GitHub's weeks start on Sunday and the current one is cut
+// short at today.
+#[cfg(feature = "network")]
+pub fn parse_calendar(calendar: &serde_json::Value) -> Vec<ContributionDay> {
+    let Some(weeks) = calendar["weeks"].as_array() else {
+        return Vec::new();
+    };
+    weeks[weeks.len().saturating_sub(WEEKS)..]
+        .iter()
+        .filter_map(|week| week["contributionDays"].as_array())
+        .flatten()
+        .filter_map(|day| {
+            Some(ContributionDay {
+                date: NaiveDate::parse_from_str(day["date"].as_str()?, "%Y-%m-%d").ok()?,
+                count: day["contributionCount"].as_u64()? as u32,
+            })
+        })
+        .collect()
+}
+
+// 0 for no contributions, then 1 to 4 in quarters of the busiest day, as
+// GitHub shades its own calendar.
+pub fn level(count: u32, busiest: u32) -> u8 {
+    if count == 0 || busiest == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(busiest).clamp(1, 4) as u8
+}
+
+// (column, row, level) for each day: a column per week, a row per weekday
+// from Sunday.
+pub fn cells(days: &[ContributionDay]) -> Vec<(usize, usize, u8)> {
+    let Some(first) = days.first() else {
+        return Vec::new();
+    };
+    let busiest = days.iter().map(|day| day.count).max().unwrap_or(0);
+    let week_start =
+        first.date - chrono::Duration::days(first.date.weekday().num_days_from_sunday() as i64);
+    days.iter()
+        .map(|day| {
+            let offset = (day.date - week_start).num_days() as usize;
+            (offset / 7, offset % 7, level(day.count, busiest))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shades_in_quarters_of_the_busiest_day() {
+        assert_eq!(level(0, 12), 0);
+        assert_eq!(level(1, 12), 1);
+        assert_eq!(level(3, 12), 1);
+        assert_eq!(level(4, 12), 2);
+        assert_eq!(level(12, 12), 4);
+        assert_eq!(level(5, 0), 0);
+    }
+
+    #[test]
+    fn lays_weeks_out_in_columns() {
+        // A Thursday, so the first column starts four rows down.
+        let start = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let days = (0..10)
+            .map(|offset| ContributionDay {
+                date: start + chrono::Duration::days(offset),
+                count: offset as u32,
+            })
+            .collect::<Vec<_>>();
+        let cells = cells(&days);
+        assert_eq!(cells[0], (0, 4, 0));
+        assert_eq!(cells[3], (1, 0, 2));
+        assert_eq!(cells[9], (1, 6, 4));
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn keeps_the_last_seven_weeks() {
+        let week = |first: u32| {
+            serde_json::json!({
+                "contributionDays": (0..7).map(|day| serde_json::json!({
+                    "date": NaiveDate::from_ymd_opt(2026, 1, 4).unwrap()
+                        .checked_add_days(chrono::Days::new((first * 7 + day) as u64))
+                        .unwrap()
+                        .to_string(),
+                    "contributionCount": day,
+                })).collect::<Vec<_>>()
+            })
+        };
+        let calendar = serde_json::json!({ "weeks": (0..10).map(week).collect::<Vec<_>>() });
+        let days = parse_calendar(&calendar);
+        assert_eq!(days.len(), WEEKS * 7);
+        assert_eq!(days[0].date.to_string(), "2026-01-25");
+        assert_eq!(days[0].date.weekday(), chrono::Weekday::Sun);
+        assert_eq!(days[48].count, 6);
+    }
+}
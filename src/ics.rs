@@ -0,0 +1,548 @@
+// Events from iCalendar (`.ics`) files, shown in the countdown next to the
+// release calendar and as the next meeting. Only what those need is read:
+// the summary, the start and its zone, a link, and daily or weekly repeats
+// with their skipped and changed instances.
+use crate::release::Release;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+
+// A repeat older than this is not walked from its first day.
+const MAX_REPEAT_DAYS: i64 = 20 * 366;
+
+// Long lines are folded onto continuation lines starting with a space or
+// tab (RFC 5545, 3.1).
+fn unfold(source: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in source.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push(' '),
+            Some(escaped) => out.push(escaped),
+            None => {}
+        }
+    }
+    out
+}
+
+// The `TZID` parameter of `DTSTART;TZID=Europe/Berlin`, unquoted.
+fn tzid(params: &str) -> Option<&str> {
+    params.split(';').find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.eq_ignore_ascii_case("TZID")
+            .then(|| value.trim_matches('"'))
+    })
+}
+
+// Whose wall clock a start is read on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Zone {
+    Utc,
+    Named(Tz),
+    // Floating times and dates, on the board's own clock.
+    Local,
+}
+
+impl Zone {
+    fn place(self, time: NaiveDateTime, offset: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Zone::Utc => Some(Utc.from_utc_datetime(&time).with_timezone(&offset)),
+            // A time the clocks go back over is the first of the two; one
+            // they skip does not happen.
+            Zone::Named(zone) => Some(
+                zone.from_local_datetime(&time)
+                    .earliest()?
+                    .with_timezone(&offset),
+            ),
+            Zone::Local => time.and_local_timezone(offset).single(),
+        }
+    }
+}
+
+// `20261101T090000Z` is UTC and a time with a `TZID` from the time zone
+// database is in that zone. Anything else, floating times, bare dates and
+// Windows zone names such as `W. Europe Standard Time`, is local time.
+fn parse_time(value: &str, tzid: Option<&str>) -> Option<(NaiveDateTime, Zone)> {
+    let (value, zone) = match value.strip_suffix('Z') {
+        Some(utc) => (utc, Zone::Utc),
+        None => (
+            value,
+            tzid.and_then(|name| name.parse().ok())
+                .map_or(Zone::Local, Zone::Named),
+        ),
+    };
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })?;
+    Some((time, zone))
+}
+
+fn parse_start(
+    value: &str,
+    tzid: Option<&str>,
+    offset: FixedOffset,
+) -> Option<DateTime<FixedOffset>> {
+    let (time, zone) = parse_time(value, tzid)?;
+    zone.place(time, offset)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+}
+
+// The subset of `RRULE` (RFC 5545, 3.3.10) that standups and weekly syncs
+// use: `FREQ=DAILY` or `WEEKLY` with `INTERVAL`, `BYDAY`, `COUNT` and
+// `UNTIL`. Other rules leave the event at its first start.
+#[derive(Clone, Debug, PartialEq)]
+struct Repeat {
+    frequency: Frequency,
+    interval: i64,
+    days: Vec<Weekday>,
+    count: Option<usize>,
+    until: Option<DateTime<FixedOffset>>,
+}
+
+fn weekday(code: &str) -> Option<Weekday> {
+    // `BYDAY` may carry an ordinal, as in `1MO`, which only monthly rules use.
+    let code = code.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '+' || ch == '-');
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_repeat(value: &str, offset: FixedOffset) -> Option<Repeat> {
+    let mut repeat = Repeat {
+        frequency: Frequency::Daily,
+        interval: 1,
+        days: Vec::new(),
+        count: None,
+        until: None,
+    };
+    let mut frequency = None;
+    for part in value.split(';') {
+        let (name, value) = part.split_once('=')?;
+        match name.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => repeat.interval = value.parse::<i64>().ok()?.max(1),
+            "BYDAY" => repeat.days = value.split(',').filter_map(weekday).collect(),
+            "COUNT" => repeat.count = value.parse().ok(),
+            "UNTIL" => repeat.until = parse_start(value, None, offset),
+            _ => {}
+        }
+    }
+    repeat.frequency = frequency?;
+    Some(repeat)
+}
+
+// Starts of `repeat` from `first` that fall in `from..until`, other than
+// those in `except`. The days are walked on the wall clock of `zone`, so a
+// meeting stays at its hour there when the clocks change.
+fn occurrences(
+    first: NaiveDateTime,
+    zone: Zone,
+    repeat: &Repeat,
+    except: &[DateTime<FixedOffset>],
+    from: DateTime<FixedOffset>,
+    until: DateTime<FixedOffset>,
+) -> Vec<DateTime<FixedOffset>> {
+    let start_day = first.date();
+    let week_start = start_day - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let mut starts = Vec::new();
+    let mut seen = 0;
+    for day in 0..MAX_REPEAT_DAYS {
+        let date = start_day + Duration::days(day);
+        let hit = match repeat.frequency {
+            Frequency::Daily => {
+                day % repeat.interval == 0
+                    && (repeat.days.is_empty() || repeat.days.contains(&date.weekday()))
+            }
+            Frequency::Weekly => {
+                let week = (date - week_start).num_days() / 7;
+                week % repeat.interval == 0
+                    && if repeat.days.is_empty() {
+                        date.weekday() == first.weekday()
+                    } else {
+                        repeat.days.contains(&date.weekday())
+                    }
+            }
+        };
+        if !hit {
+            continue;
+        }
+        let Some(start) = zone.place(date.and_time(first.time()), *from.offset()) else {
+            continue;
+        };
+        seen += 1;
+        if start >= until
+            || repeat.until.is_some_and(|last| start > last)
+            || repeat.count.is_some_and(|count| seen > count)
+        {
+            break;
+        }
+        if start >= from && !except.contains(&start) {
+            starts.push(start);
+        }
+    }
+    starts
+}
+
+struct Event {
+    release: Release,
+    // The start on its own wall clock, which repeats are walked from.
+    start: NaiveDateTime,
+    zone: Zone,
+    all_day: bool,
+    repeat: Option<Repeat>,
+    // Starts left out of the repeat by `EXDATE`.
+    except: Vec<DateTime<FixedOffset>>,
+    uid: Option<String>,
+    // A changed instance of a repeat has the repeat's `UID` and, as its
+    // `RECURRENCE-ID`, the start it stands in for.
+    replaces: Option<DateTime<FixedOffset>>,
+    cancelled: bool,
+}
+
+// A `VEVENT` as its lines are read.
+#[derive(Default)]
+struct Draft {
+    title: Option<String>,
+    start: Option<(NaiveDateTime, Zone)>,
+    url: Option<String>,
+    all_day: bool,
+    repeat: Option<Repeat>,
+    except: Vec<DateTime<FixedOffset>>,
+    uid: Option<String>,
+    replaces: Option<DateTime<FixedOffset>>,
+    cancelled: bool,
+}
+
+fn parse(source: &str, offset: FixedOffset) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut draft: Option<Draft> = None;
+    for line in unfold(source) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name.split_once(';').unwrap_or((name, ""));
+        let value = value.trim();
+        match (name.to_ascii_uppercase().as_str(), value, draft.as_mut()) {
+            ("BEGIN", "VEVENT", _) => draft = Some(Draft::default()),
+            ("END", "VEVENT", _) => {
+                let Some(draft) = draft.take() else {
+                    continue;
+                };
+                let due = draft
+                    .start
+                    .and_then(|(start, zone)| Some((start, zone, zone.place(start, offset)?)));
+                match (draft.title, due) {
+                    (Some(title), Some((start, zone, due))) => events.push(Event {
+                        release: Release {
+                            title,
+                            due,
+                            url: draft.url,
+                        },
+                        start,
+                        zone,
+                        all_day: draft.all_day,
+                        repeat: draft.repeat,
+                        except: draft.except,
+                        uid: draft.uid,
+                        replaces: draft.replaces,
+                        cancelled: draft.cancelled,
+                    }),
+                    _ => log!("Ignoring a calendar event without a summary or start"),
+                }
+            }
+            ("SUMMARY", value, Some(draft)) => draft.title = Some(unescape(value)),
+            ("DTSTART", value, Some(draft)) => {
+                draft.start = parse_time(value, tzid(params));
+                draft.all_day = !value.contains('T');
+            }
+            ("URL", value, Some(draft)) => draft.url = Some(value.to_string()),
+            ("RRULE", value, Some(draft)) => draft.repeat = parse_repeat(value, offset),
+            ("EXDATE", value, Some(draft)) => draft.except.extend(
+                value
+                    .split(',')
+                    .filter_map(|value| parse_start(value, tzid(params), offset)),
+            ),
+            ("UID", value, Some(draft)) => draft.uid = Some(value.to_string()),
+            ("RECURRENCE-ID", value, Some(draft)) => {
+                draft.replaces = parse_start(value, tzid(params), offset)
+            }
+            ("STATUS", value, Some(draft)) => {
+                draft.cancelled = value.eq_ignore_ascii_case("CANCELLED")
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+pub fn parse_events(source: &str, offset: FixedOffset) -> Vec<Release> {
+    parse(source, offset)
+        .into_iter()
+        .map(|event| event.release)
+        .collect()
+}
+
+// Timed events starting in the `days` from `from`, with repeats expanded
+// to each start; all-day and cancelled events are not meetings.
+pub fn parse_meetings(source: &str, from: DateTime<FixedOffset>, days: i64) -> Vec<Release> {
+    let until = from + Duration::days(days);
+    let events = parse(source, *from.offset());
+    let mut meetings = Vec::new();
+    for event in &events {
+        if event.all_day || event.cancelled {
+            continue;
+        }
+        let starts = match &event.repeat {
+            Some(repeat) if event.replaces.is_none() => {
+                // Changed instances are shown on their own, in place of the
+                // start they replace.
+                let mut except = event.except.clone();
+                except.extend(
+                    events
+                        .iter()
+                        .filter(|other| other.uid.is_some() && other.uid == event.uid)
+                        .filter_map(|other| other.replaces),
+                );
+                occurrences(event.start, event.zone, repeat, &except, from, until)
+            }
+            _ if (from..until).contains(&event.release.due) => vec![event.release.due],
+            _ => Vec::new(),
+        };
+        meetings.extend(starts.into_iter().map(|due| Release {
+            due,
+            ..event.release.clone()
+        }));
+    }
+    meetings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+SUMMARY:Release v3.0\\, final\r
+DTSTART:20261101T090000Z\r
+URL:https://example.com/v3\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Team off\r
+ site\r
+DTSTART;VALUE=DATE:20261224\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;TZID=Europe/Berlin:20261105T143000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn reads_summaries_starts_and_links() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let events = parse_events(CALENDAR, offset);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title, "Release v3.0, final");
+        assert_eq!(events[0].due.to_rfc3339(), "2026-11-01T10:00:00+01:00");
+        assert_eq!(events[0].url.as_deref(), Some("https://example.com/v3"));
+        // Folded onto a second line.
+        assert_eq!(events[1].title, "Team offsite");
+        assert_eq!(events[1].due.to_rfc3339(), "2026-12-24T00:00:00+01:00");
+        assert_eq!(events[1].url, None);
+    }
+
+    #[test]
+    fn local_times_keep_the_wall_clock() {
+        let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(
+            parse_start("20261105T143000", None, offset).map(|due| due.to_rfc3339()),
+            Some("2026-11-05T14:30:00-05:00".to_string())
+        );
+        assert_eq!(parse_start("soon", None, offset), None);
+    }
+
+    const MEETINGS: &str = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+SUMMARY:Standup
+DTSTART:20260105T103000
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Retro
+DTSTART:20260902T150000
+RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Offsite
+DTSTART;VALUE=DATE:20261017
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Demo
+DTSTART:20261019T080000Z
+END:VEVENT
+END:VCALENDAR
+";
+
+    #[test]
+    fn expands_repeats_into_meetings() {
+        // Friday morning, after the standup.
+        let from = DateTime::parse_from_rfc3339("2026-10-16T11:00:00+02:00").unwrap();
+        let meetings = parse_meetings(MEETINGS, from, 7);
+        let starts = |title: &str| {
+            meetings
+                .iter()
+                .filter(|meeting| meeting.title == title)
+                .map(|meeting| meeting.due.format("%a %d %H:%M").to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            starts("Standup"),
+            [
+                "Mon 19 10:30",
+                "Tue 20 10:30",
+                "Wed 21 10:30",
+                "Thu 22 10:30",
+                "Fri 23 10:30"
+            ]
+        );
+        // The fourth and last retro was on 14 October.
+        assert!(starts("Retro").is_empty());
+        assert!(starts("Offsite").is_empty());
+        assert_eq!(starts("Demo"), ["Mon 19 10:00"]);
+    }
+
+    #[test]
+    fn reads_the_repeat_subset() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let repeat = parse_repeat("FREQ=DAILY;INTERVAL=2;UNTIL=20261231T000000Z", offset).unwrap();
+        assert_eq!(repeat.frequency, Frequency::Daily);
+        assert_eq!(repeat.interval, 2);
+        assert!(repeat.until.is_some());
+        assert_eq!(parse_repeat("FREQ=MONTHLY;BYDAY=1MO", offset), None);
+        assert_eq!(weekday("-1FR"), Some(Weekday::Fri));
+    }
+
+    fn meeting_starts(source: &str, from: &str, days: i64) -> Vec<String> {
+        let from = DateTime::parse_from_rfc3339(from).unwrap();
+        let mut meetings = parse_meetings(source, from, days);
+        meetings.sort_by_key(|meeting| meeting.due);
+        meetings
+            .iter()
+            .map(|meeting| format!("{} {}", meeting.title, meeting.due.format("%a %d %H:%M")))
+            .collect()
+    }
+
+    #[test]
+    fn converts_zoned_times_across_their_clock_changes() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let start =
+            |tzid| parse_start("20261105T090000", Some(tzid), offset).map(|due| due.to_rfc3339());
+        assert_eq!(
+            start("America/New_York").as_deref(),
+            Some("2026-11-05T15:00:00+01:00")
+        );
+        // Not in the time zone database, so kept on the wall clock.
+        assert_eq!(
+            start("W. Europe Standard Time").as_deref(),
+            Some("2026-11-05T09:00:00+01:00")
+        );
+
+        // New York leaves summer time a week after Europe.
+        let source = "BEGIN:VEVENT
+SUMMARY:Sync
+DTSTART;TZID=\"America/New_York\":20261001T090000
+RRULE:FREQ=WEEKLY
+END:VEVENT
+";
+        assert_eq!(
+            meeting_starts(source, "2026-10-28T00:00:00+01:00", 14),
+            ["Sync Thu 29 14:00", "Sync Thu 05 15:00"]
+        );
+    }
+
+    #[test]
+    fn leaves_out_excluded_starts() {
+        let source = "BEGIN:VEVENT
+SUMMARY:Standup
+DTSTART;TZID=Europe/Berlin:20261001T103000
+RRULE:FREQ=DAILY
+EXDATE;TZID=Europe/Berlin:20261020T103000,20261022T103000
+END:VEVENT
+";
+        assert_eq!(
+            meeting_starts(source, "2026-10-19T00:00:00+02:00", 5),
+            [
+                "Standup Mon 19 10:30",
+                "Standup Wed 21 10:30",
+                "Standup Fri 23 10:30"
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_instances_replace_their_repeat() {
+        let source = "BEGIN:VEVENT
+UID:sync-1
+SUMMARY:Sync
+DTSTART:20261005T100000Z
+RRULE:FREQ=WEEKLY
+END:VEVENT
+BEGIN:VEVENT
+UID:sync-1
+RECURRENCE-ID:20261019T100000Z
+SUMMARY:Sync (moved)
+DTSTART:20261020T140000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:sync-1
+RECURRENCE-ID:20261026T100000Z
+SUMMARY:Sync
+DTSTART:20261026T100000Z
+STATUS:CANCELLED
+END:VEVENT
+";
+        assert_eq!(
+            meeting_starts(source, "2026-10-16T00:00:00+00:00", 18),
+            ["Sync (moved) Tue 20 14:00", "Sync Mon 02 10:00"]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// Files dropped onto the window: an `.ics` calendar adds its events to the
+// countdown, a `.toml` theme is applied and installed for later launches.
+use crate::dashboards::{parse_theme, Dashboard};
+use crate::ics;
+use crate::release::Release;
+use chrono::FixedOffset;
+use std::path::Path;
+
+pub enum Import {
+    Events(Vec<Release>),
+    Theme(Dashboard),
+}
+
+// `name` is the dropped file's path, or just its name in the browser.
+pub fn import(name: &str, bytes: &[u8], offset: FixedOffset) -> Option<Import> {
+    let path = Path::new(name);
+    let source = String::from_utf8_lossy(bytes);
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("ics") => {
+            let events = ics::parse_events(&source, offset);
+            log!("Added {} events from {}", events.len(), name);
+            Some(Import::Events(events))
+        }
+        Some("toml") => {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy())
+                .unwrap_or_default();
+            install_theme(&source);
+            Some(Import::Theme(parse_theme(&stem, &source)))
+        }
+        _ => {
+            log!("Ignoring dropped file {}: expected .ics or .toml", name);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn theme_path() -> Option<std::path::PathBuf> {
+    Some(crate::platform::config_dir()?.join("theme.toml"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn install_theme(source: &str) {
+    let Some(path) = theme_path() else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    match std::fs::write(&path, source) {
+        Ok(()) => log!("Installed the theme to {}", path.display()),
+        Err(e) => log!("Failed to write {}: {e}", path.display()),
+    }
+}
+
+// The theme dropped last, applied under the configured dashboards.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn installed_theme() -> Option<Dashboard> {
+    let source = std::fs::read_to_string(theme_path()?).ok()?;
+    Some(parse_theme("installed", &source))
+}
+
+// The browser build applies a dropped theme until the page is reloaded.
+#[cfg(target_arch = "wasm32")]
+fn install_theme(_source: &str) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn installed_theme() -> Option<Dashboard> {
+    None
+}
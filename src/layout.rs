@@ -0,0 +1,216 @@
+use macroquad::prelude::Rect;
+use std::str::FromStr;
+
+// Height of one PR row (title line plus gap) used to size the top band.
+const PR_ROW_HEIGHT: f32 = 24.0;
+// Keeps the top band clear of the GitHub button in the corner.
+const BUTTON_RESERVE: f32 = 48.0;
+const PADDING: f32 = 12.0;
+// Below this width a side column would squeeze the clock too much.
+const MIN_SIDE_WIDTH: f32 = 560.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrListPosition {
+    Top,
+    Bottom,
+    Side,
+}
+
+impl FromStr for PrListPosition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "top" | "above" => Ok(PrListPosition::Top),
+            "bottom" | "below" => Ok(PrListPosition::Bottom),
+            "side" | "right" => Ok(PrListPosition::Side),
+            _ => Err(format!("unknown PR list position {}", value)),
+        }
+    }
+}
+
+// `portrait` stacks the hours, minutes and seconds and puts the PR list
+// below them, for narrow tall windows; `auto` picks it by the window shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Landscape,
+    Portrait,
+    Auto,
+}
+
+impl Orientation {
+    pub fn is_portrait(self, container: Rect) -> bool {
+        match self {
+            Orientation::Landscape => false,
+            Orientation::Portrait => true,
+            Orientation::Auto => container.h > container.w * 1.5,
+        }
+    }
+}
+
+impl FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "landscape" | "horizontal" => Ok(Orientation::Landscape),
+            "portrait" | "vertical" => Ok(Orientation::Portrait),
+            "auto" => Ok(Orientation::Auto),
+            _ => Err(format!("unknown orientation {}", value)),
+        }
+    }
+}
+
+// How PR titles that do not fit on one line are handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextOverflow {
+    Wrap,
+    Ellipsis,
+}
+
+impl FromStr for TextOverflow {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "wrap" => Ok(TextOverflow::Wrap),
+            "ellipsis" | "truncate" => Ok(TextOverflow::Ellipsis),
+            _ => Err(format!("unknown text overflow mode {}", value)),
+        }
+    }
+}
+
+// `dot` replaces the AM/PM text with a single lit cell for PM, like LED
+// alarm clocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmPmStyle {
+    Text,
+    Dot,
+}
+
+impl FromStr for AmPmStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Ok(AmPmStyle::Text),
+            "dot" => Ok(AmPmStyle::Dot),
+            _ => Err(format!("unknown AM/PM style {}", value)),
+        }
+    }
+}
+
+// How the seconds show: as digits, or (`pulse`) left out of the time with a
+// single cell next to it that lights up every second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecondsStyle {
+    Digits,
+    Pulse,
+}
+
+impl FromStr for SecondsStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "digits" => Ok(SecondsStyle::Digits),
+            "pulse" => Ok(SecondsStyle::Pulse),
+            _ => Err(format!("unknown seconds style {}", value)),
+        }
+    }
+}
+
+// Where the clock and the PR list go. `prs` is None when the list simply
+// follows the clock, since its top then depends on the drawn time.
+#[derive(Clone, Copy, Debug)]
+pub struct Areas {
+    pub clock: Rect,
+    pub prs: Option<Rect>,
+    pub portrait: bool,
+    // Just the time, without the year and date rows.
+    pub compact: bool,
+}
+
+pub fn compact(container: Rect) -> Areas {
+    Areas {
+        clock: container,
+        prs: None,
+        portrait: false,
+        compact: true,
+    }
+}
+
+pub fn split(
+    container: Rect,
+    position: PrListPosition,
+    orientation: Orientation,
+    pr_count: usize,
+) -> Areas {
+    let portrait = orientation.is_portrait(container);
+    let below = Areas {
+        clock: container,
+        prs: None,
+        portrait,
+        compact: false,
+    };
+    if pr_count == 0 {
+        return below;
+    }
+    match position {
+        PrListPosition::Side if portrait => below,
+        PrListPosition::Bottom => below,
+        PrListPosition::Top => {
+            let band = (pr_count as f32 * PR_ROW_HEIGHT + PADDING).min(container.h * 0.45);
+            Areas {
+                clock: Rect::new(
+                    container.x,
+                    container.y + band,
+                    container.w,
+                    container.h - band,
+                ),
+                prs: Some(Rect::new(
+                    container.x + PADDING,
+                    container.y + PADDING,
+                    container.w - PADDING - BUTTON_RESERVE,
+                    band - PADDING,
+                )),
+                portrait,
+                compact: false,
+            }
+        }
+        PrListPosition::Side if container.w < MIN_SIDE_WIDTH => below,
+        PrListPosition::Side => {
+            let column = (container.w * 0.4).round();
+            let clock_w = container.w - column;
+            Areas {
+                clock: Rect::new(container.x, container.y, clock_w, container.h),
+                prs: Some(Rect::new(
+                    container.x + clock_w,
+                    container.y + BUTTON_RESERVE,
+                    column - PADDING,
+                    container.h - BUTTON_RESERVE - PADDING,
+                )),
+                portrait,
+                compact: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portrait_keeps_the_list_below() {
+        let tall = Rect::new(0.0, 0.0, 260.0, 640.0);
+        let wide = Rect::new(0.0, 0.0, 900.0, 400.0);
+        assert!(Orientation::Auto.is_portrait(tall));
+        assert!(!Orientation::Auto.is_portrait(wide));
+        let areas = split(tall, PrListPosition::Side, Orientation::Auto, 3);
+        assert!(areas.portrait);
+        assert!(areas.prs.is_none());
+        let areas = split(wide, PrListPosition::Side, Orientation::Landscape, 3);
+        assert!(!areas.portrait && areas.prs.is_some());
+    }
+}
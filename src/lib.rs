@@ -0,0 +1,65 @@
+// Everything but the window and the frame loop, which live in main.rs.
+#[macro_use]
+pub mod diag;
+pub mod a11y;
+pub mod actions;
+pub mod alerts;
+pub mod animation;
+pub mod appearance;
+pub mod avatars;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub mod cache;
+pub mod calendar;
+pub mod cells;
+pub mod clock;
+pub mod command;
+pub mod config;
+pub mod dashboards;
+pub mod error;
+#[cfg(feature = "network")]
+pub mod export;
+pub mod fetch;
+pub mod forges;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub mod github;
+pub mod glyphs;
+pub mod heatmap;
+pub mod ics;
+pub mod import;
+pub mod layout;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub mod linear;
+pub mod meetings;
+pub mod menubar;
+#[cfg(feature = "network")]
+pub mod metrics;
+pub mod night;
+pub mod oauth;
+pub mod pages;
+pub mod platform;
+pub mod pomodoro;
+pub mod power;
+pub mod prefs;
+pub mod presence;
+pub mod provider;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recording;
+pub mod release;
+pub mod render;
+pub mod rules;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub mod secrets;
+pub mod session;
+pub mod share;
+pub mod sound;
+pub mod speckles;
+pub mod sun;
+pub mod teamsync;
+pub mod textures;
+#[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+pub mod tui;
+pub mod wake;
+pub mod watch;
+pub mod weather;
+pub mod webhooks;
+pub mod widgets;
@@ -0,0 +1,138 @@
+use crate::error::ChronoError;
+use crate::provider::WorkItem;
+use crate::provider::{self, parse_timestamp, Capabilities, Provider};
+use serde_json::{json, Value};
+
+const API_URL: &str = "https://api.linear.app/graphql";
+// The keychain entry `--store-token https://linear.app` writes.
+pub const KEYCHAIN_URL: &str = "https://linear.app";
+
+// Linear issues assigned to the viewer that are planned or in progress.
+pub struct LinearProvider {
+    agent: ureq::Agent,
+    api_key: Option<String>,
+    // `https://linear.app/<workspace>/issue/`, once authenticated.
+    issue_url: Option<String>,
+    team_keys: Vec<String>,
+}
+
+impl LinearProvider {
+    pub fn new(api_key: Option<String>) -> Self {
+        LinearProvider {
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(4))
+                .build(),
+            api_key,
+            issue_url: None,
+            team_keys: Vec::new(),
+        }
+    }
+
+    fn query(&self, query: &str, variables: Value) -> Result<Value, ChronoError> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| ChronoError::Auth("no API key".to_string()))?;
+        let body = json!({ "query": query, "variables": variables });
+        let request = self
+            .agent
+            .post(API_URL)
+            .set("User-Agent", "commit-clock")
+            .set("Authorization", api_key)
+            .set("Content-Type", "application/json");
+        let body = body.to_string();
+        let response = match provider::send(&request, Some(&body)) {
+            Ok(resp) => resp,
+            // A rejected key comes back as a 400 with a GraphQL error.
+            Err(e) => match *e {
+                ureq::Error::Status(400, resp) => resp,
+                e => return Err(ChronoError::from(Box::new(e))),
+            },
+        }
+        .into_string()
+        .map_err(|e| ChronoError::Network(e.to_string()))?;
+        let json: Value = serde_json::from_str(&response)?;
+        let error = &json["errors"][0];
+        if let Some(message) = error["message"].as_str() {
+            return Err(if error["extensions"]["type"] == "authentication error" {
+                ChronoError::Auth(message.to_string())
+            } else {
+                ChronoError::Parse(message.to_string())
+            });
+        }
+        Ok(json["data"].clone())
+    }
+}
+
+impl Provider for LinearProvider {
+    fn name(&self) -> &str {
+        "Linear"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            issues: true,
+            ..Default::default()
+        }
+    }
+
+    fn auth(&mut self) -> Result<(), ChronoError> {
+        if self.api_key.is_none() {
+            self.api_key = crate::secrets::instance_token(KEYCHAIN_URL);
+        }
+        let data = self.query(
+            "{ viewer { organization { urlKey } } teams { nodes { key } } }",
+            Value::Null,
+        )?;
+        let workspace = data["viewer"]["organization"]["urlKey"]
+            .as_str()
+            .ok_or_else(|| ChronoError::Parse("no workspace".to_string()))?;
+        self.issue_url = Some(format!("https://linear.app/{}/issue/", workspace));
+        self.team_keys = data["teams"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|team| team["key"].as_str().map(str::to_string))
+            .collect();
+        Ok(())
+    }
+
+    fn fetch_prs(&self, _limit: usize) -> Result<Vec<WorkItem>, String> {
+        Ok(Vec::new())
+    }
+
+    fn fetch_issues(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+        let data = self.query(
+            "query($first: Int!) { viewer { assignedIssues(first: $first, orderBy: updatedAt, \
+             filter: { state: { type: { in: [\"unstarted\", \"started\"] } } }) \
+             { nodes { identifier title url updatedAt } } } }",
+            json!({ "first": limit }),
+        )?;
+        Ok(data["viewer"]["assignedIssues"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|issue| {
+                let identifier = issue["identifier"].as_str()?;
+                Some(WorkItem {
+                    // The identifier leads so it is linked like a Jira key.
+                    title: format!("{} {}", identifier, issue["title"].as_str()?),
+                    url: issue["url"].as_str()?.to_string(),
+                    updated_at: issue["updatedAt"].as_str().and_then(parse_timestamp),
+                    ..Default::default()
+                })
+            })
+            .take(limit)
+            .collect())
+    }
+
+    fn issue_links(&self) -> Vec<(String, String)> {
+        let Some(issue_url) = &self.issue_url else {
+            return Vec::new();
+        };
+        self.team_keys
+            .iter()
+            .map(|key| (key.clone(), issue_url.clone()))
+            .collect()
+    }
+}
@@ -1,16 +1,65 @@
-use chrono::{Datelike, Local, Timelike};
+use chrono::{Datelike, Timelike};
+use chrono_board::actions::WorkflowRuns;
+use chrono_board::animation::{Animations, Celebration, Effect};
+use chrono_board::appearance::{Appearance, AppearanceMonitor};
+use chrono_board::avatars::AvatarStyle;
+use chrono_board::clock::{
+    age_label, format_time, format_timezone, format_window_title, last_commit_label, short_time,
+    tick_on, time_source, until_next_second, updated_label, AmPmMark, ClockText, FixedClock,
+    HourFormat, TimeFormat,
+};
+use chrono_board::config::Config;
+use chrono_board::error::ChronoError;
+#[cfg(feature = "network")]
+use chrono_board::export;
+use chrono_board::fetch::{self, ConnectionStatus};
+#[cfg(feature = "network")]
+use chrono_board::glyphs::glyph_rows;
+use chrono_board::layout::{self, AmPmStyle, SecondsStyle};
+#[cfg(feature = "network")]
+use chrono_board::metrics::MetricsServer;
+use chrono_board::oauth::{self, DeviceFlowEvent};
+use chrono_board::pages::{self, Page, Stats};
+use chrono_board::platform::{self, load_github_token, open_url};
+use chrono_board::power::PowerMonitor;
+use chrono_board::provider::{CiState, CiStatus, RateLimits, ReviewLoad, ReviewStatus, WorkItem};
+#[cfg(not(target_arch = "wasm32"))]
+use chrono_board::recording;
+use chrono_board::release::{self, Release};
+use chrono_board::render::*;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use chrono_board::secrets;
+use chrono_board::sound::Cue;
+use chrono_board::textures::TextureCache;
+use chrono_board::webhooks::{WebhookEvent, Webhooks};
+use chrono_board::widgets::*;
+use chrono_board::{
+    a11y, alerts, command, diag, heatmap, import, log, meetings, menubar, night, pomodoro, prefs,
+    presence, rules, session, share, sound, sun, teamsync, wake, watch, weather,
+};
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+use chrono_board::{avatars, cache, textures};
 use macroquad::prelude::*;
+#[cfg(feature = "svg")]
 use resvg::tiny_skia::{Pixmap, Transform};
+#[cfg(feature = "svg")]
 use resvg::usvg::{Options, Tree};
-use std::cell::RefCell;
+use std::collections::HashSet;
 use std::sync::mpsc;
-use std::thread;
+use std::task::Poll;
 use std::time::Duration;
 
 use miniquad::conf::Conf;
 use miniquad::conf::Icon;
 use std::error::Error;
 use std::fs;
+use std::path::Path;
+
+// Embedded so the app works from a bundle or any working directory; a file
+// with the same name next to the binary's cwd still overrides it.
+const ICON_16: [u8; 16 * 16 * 4] = *include_bytes!("../icon_16.rgba");
+const ICON_32: [u8; 32 * 32 * 4] = *include_bytes!("../icon_32.rgba");
+const ICON_64: [u8; 64 * 64 * 4] = *include_bytes!("../icon_64.rgba");
 
 fn icon<const SIZE: usize>(path: &str) -> Result<[u8; SIZE], Box<dyn Error>> {
     let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
@@ -24,179 +73,184 @@ fn icon<const SIZE: usize>(path: &str) -> Result<[u8; SIZE], Box<dyn Error>> {
     })
 }
 
-fn files() -> Result<Icon, Box<dyn Error>> {
-    let small = icon::<{ 16 * 16 * 4 }>("icon_16.rgba")?;
-    let medium = icon::<{ 32 * 32 * 4 }>("icon_32.rgba")?;
-    let big = icon::<{ 64 * 64 * 4 }>("icon_64.rgba")?;
-    Ok(Icon { small, medium, big })
-}
-
-pub fn conf() -> Conf {
-    let icon = match files() {
-        Ok(icon) => Some(icon),
+fn icon_or_embedded<const SIZE: usize>(path: &str, embedded: [u8; SIZE]) -> [u8; SIZE] {
+    if !Path::new(path).exists() {
+        return embedded;
+    }
+    match icon::<SIZE>(path) {
+        Ok(data) => data,
         Err(e) => {
-            eprintln!("Failed to load icons: {e}");
-            None
+            log!("Failed to load icon override: {e}");
+            embedded
         }
-    };
+    }
+}
+
+fn files() -> Icon {
+    Icon {
+        small: icon_or_embedded("icon_16.rgba", ICON_16),
+        medium: icon_or_embedded("icon_32.rgba", ICON_32),
+        big: icon_or_embedded("icon_64.rgba", ICON_64),
+    }
+}
 
+pub fn conf() -> Conf {
     Conf {
         window_title: "".to_string(),
         window_width: 640,
         window_height: 260,
         window_resizable: false,
-        icon,
+        icon: Some(files()),
         ..Default::default()
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum HourFormat {
-    H24,
-    H12,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum TimeFormat {
-    HhMmSs,
-    HhMm,
-    MmSs,
-    IsoTime,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum ConnectionStatus {
-    Unknown,
-    Connected,
-    Disconnected,
-}
-
-#[derive(Clone, Debug)]
-struct GithubPr {
-    title: String,
-    url: String,
-}
-
-#[derive(Clone, Debug)]
-struct GithubFetchResult {
-    connected: bool,
-    prs: Vec<GithubPr>,
-}
-
-#[derive(Clone, Copy, Debug)]
-struct ClockLayout {
-    time_bottom: f32,
-    left_x: f32,
-    board_grid: PixelGrid,
-    pr_grid: PixelGrid,
-}
-
-#[derive(Clone, Debug)]
-struct PrHit {
-    rect: Rect,
-    url: String,
-}
-
-#[derive(Clone, Copy, Debug)]
-struct Theme {
-    background_color: Color,
-    inactive_color: Color,
-    active_color: Color,
-    noise_color: Color,
-    active_alpha: f32,
-    active_alpha_jitter: f32,
+// Everything that changes what ends up on screen; the scene is only
+// re-rendered when this differs from the previous frame.
+#[derive(Clone, Debug, PartialEq)]
+struct SceneKey {
+    container: Rect,
+    theme: Theme,
+    year: String,
+    date: String,
+    weather: Option<String>,
+    time: String,
+    am_pm: Option<String>,
+    colon_lit: bool,
+    pulse: Option<bool>,
+    github_status: ConnectionStatus,
+    github_prs: Vec<WorkItem>,
+    issues: Vec<WorkItem>,
+    work_panel: WorkPanel,
+    account_filter: Option<usize>,
+    pr_scroll: usize,
+    dashboard: usize,
+    ci_statuses: Vec<CiStatus>,
+    review_decisions: Vec<ReviewStatus>,
+    conflicts: Vec<String>,
+    review_load: Vec<ReviewLoad>,
+    release_label: Option<String>,
+    // The rate-limit wait or a fetch timeout, shown next to the button.
+    status_label: Option<String>,
+    updated_label: Option<String>,
+    timezone: Option<String>,
+    widget_lines: Vec<WidgetLine>,
+    unread: usize,
+    notifications: Option<Vec<WorkItem>>,
+    sign_in_code: Option<String>,
+    share_link: Option<String>,
+    hovered_hit: Option<usize>,
+    power_save: bool,
+    compact: bool,
+    page: Page,
+    // The stats page's lines, only kept while it shows.
+    stats: Vec<String>,
+    heatmap: Vec<(usize, usize, u8)>,
+    workflows: Vec<WorkflowRuns>,
 }
 
-#[derive(Clone, Copy, Debug)]
-struct PixelGrid {
-    cell: f32,
-    gap: f32,
+// A press on the background moving the window (`CHRONO_DRAG_TO_MOVE`).
+// The cursor is window-relative, so each new position moves the window by
+// the cursor's offset from where it was grabbed.
+struct WindowDrag {
+    grab: Vec2,
+    last: Vec2,
+    origin: (i32, i32),
 }
 
-impl PixelGrid {
-    fn step(self) -> f32 {
-        self.cell + self.gap
-    }
-}
+// The browser has no config folder to open and no window to close.
+#[cfg(not(target_arch = "wasm32"))]
+const MENU_ACTIONS: &[MenuAction] = &[
+    MenuAction::Refresh,
+    MenuAction::CopyTime,
+    MenuAction::ToggleHours,
+    MenuAction::Settings,
+    MenuAction::Quit,
+];
+#[cfg(target_arch = "wasm32")]
+const MENU_ACTIONS: &[MenuAction] = &[
+    MenuAction::Refresh,
+    MenuAction::CopyTime,
+    MenuAction::ToggleHours,
+];
+
+const TOOLTIP_DELAY_SECS: f64 = 0.5;
+// How far a touch has to travel sideways to turn the page.
+const SWIPE_MIN_PX: f32 = 60.0;
+
+const POWER_SAVE_POLL_SECS: i64 = 900;
+// A fetch running longer than this is abandoned.
+const FETCH_DEADLINE_SECS: f64 = 120.0;
+const FETCH_RETRY_SECS: i64 = 60;
 
-#[derive(Clone, Copy, Debug)]
-struct FrameContext {
-    theme: Theme,
-    container: Rect,
-}
+const GITHUB_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 640"><path fill="#ffffff" d="M237.9 461.4C237.9 463.4 235.6 465 232.7 465C229.4 465.3 227.1 463.7 227.1 461.4C227.1 459.4 229.4 457.8 232.3 457.8C235.3 457.5 237.9 459.1 237.9 461.4zM206.8 456.9C206.1 458.9 208.1 461.2 211.1 461.8C213.7 462.8 216.7 461.8 217.3 459.8C217.9 457.8 216 455.5 213 454.6C210.4 453.9 207.5 454.9 206.8 456.9zM251 455.2C248.1 455.9 246.1 457.8 246.4 460.1C246.7 462.1 249.3 463.4 252.3 462.7C255.2 462 257.2 460.1 256.9 458.1C256.6 456.2 253.9 454.9 251 455.2zM316.8 72C178.1 72 72 177.3 72 316C72 426.9 141.8 521.8 241.5 555.2C254.3 557.5 258.8 549.6 258.8 543.1C258.8 536.9 258.5 502.7 258.5 481.7C258.5 481.7 188.5 496.7 173.8 451.9C173.8 451.9 162.4 422.8 146 415.3C146 415.3 123.1 399.6 147.6 399.9C147.6 399.9 172.5 401.9 186.2 425.7C208.1 464.3 244.8 453.2 259.1 446.6C261.4 430.6 267.9 419.5 275.1 412.9C219.2 406.7 162.8 398.6 162.8 302.4C162.8 274.9 170.4 261.1 186.4 243.5C183.8 237 175.3 210.2 189 175.6C209.9 169.1 258 202.6 258 202.6C278 197 299.5 194.1 320.8 194.1C342.1 194.1 363.6 197 383.6 202.6C383.6 202.6 431.7 169 452.6 175.6C466.3 210.3 457.8 237 455.2 243.5C471.2 261.2 481 275 481 302.4C481 398.9 422.1 406.6 366.2 412.9C375.4 420.8 383.2 435.8 383.2 459.3C383.2 493 382.9 534.7 382.9 542.9C382.9 549.4 387.5 557.3 400.2 555C500.2 521.8 568 426.9 568 316C568 177.3 455.5 72 316.8 72zM169.2 416.9C167.9 417.9 168.2 420.2 169.9 422.1C171.5 423.7 173.8 424.4 175.1 423.1C176.4 422.1 176.1 419.8 174.4 417.9C172.8 416.3 170.5 415.6 169.2 416.9zM158.4 408.8C157.7 410.1 158.7 411.7 160.7 412.7C162.3 413.7 164.3 413.4 165 412C165.7 410.7 164.7 409.1 162.7 408.1C160.7 407.5 159.1 407.8 158.4 408.8zM190.8 444.4C189.2 445.7 189.8 448.7 192.1 450.6C194.4 452.9 197.3 453.2 198.6 451.6C199.9 450.3 199.3 447.3 197.3 445.4C195.1 443.1 192.1 442.8 190.8 444.4zM179.4 429.7C177.8 430.7 177.8 433.3 179.4 435.6C181 437.9 183.7 438.9 185 437.9C186.6 436.6 186.6 434 185 431.7C183.6 429.4 181 428.4 179.4 429.7z"/></svg>"##;
 
-impl Default for FrameContext {
-    fn default() -> Self {
-        let theme = Theme {
-            background_color: Color {
-                r: 0.06,
-                g: 0.07,
-                b: 0.08,
-                a: 1.0,
-            },
-            inactive_color: Color {
-                r: 0.12,
-                g: 0.13,
-                b: 0.15,
-                a: 1.0,
-            },
-            active_color: Color {
-                r: 0.2,
-                g: 0.85,
-                b: 0.82,
-                a: 1.0,
-            },
-            noise_color: Color {
-                r: 0.2,
-                g: 0.85,
-                b: 0.82,
-                a: 1.0,
-            },
-            active_alpha: 0.82,
-            active_alpha_jitter: 0.4,
-        };
+const PR_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 640"><path fill="#ffffff" d="M176 120C189.3 120 200 130.7 200 144C200 157.3 189.3 168 176 168C162.7 168 152 157.3 152 144C152 130.7 162.7 120 176 120zM208.4 217.2C236.4 204.8 256 176.7 256 144C256 99.8 220.2 64 176 64C131.8 64 96 99.8 96 144C96 176.8 115.7 205 144 217.3L144 422.6C115.7 435 96 463.2 96 496C96 540.2 131.8 576 176 576C220.2 576 256 540.2 256 496C256 463.2 236.3 435 208 422.7L208 336.1C234.7 356.2 268 368.1 304 368.1L390.7 368.1C403 396.4 431.2 416.1 464 416.1C508.2 416.1 544 380.3 544 336.1C544 291.9 508.2 256.1 464 256.1C431.2 256.1 403 275.8 390.7 304.1L304 304C254.1 304 213 265.9 208.4 217.2zM176 472C189.3 472 200 482.7 200 496C200 509.3 189.3 520 176 520C162.7 520 152 509.3 152 496C152 482.7 162.7 472 176 472zM440 336C440 322.7 450.7 312 464 312C477.3 312 488 322.7 488 336C488 349.3 477.3 360 464 360C450.7 360 440 349.3 440 336z"/></svg>"##;
 
-        FrameContext {
-            theme,
-            container: Rect::new(20.0, 20.0, 440.0, 220.0),
+const ISSUE_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 640"><path fill="#ffffff" d="M320 128C426 128 512 214 512 320C512 426 426 512 320 512C214 512 128 426 128 320C128 214 214 128 320 128zM320 576C461.4 576 576 461.4 576 320C576 178.6 461.4 64 320 64C178.6 64 64 178.6 64 320C64 461.4 178.6 576 320 576zM320 368C346.5 368 368 346.5 368 320C368 293.5 346.5 272 320 272C293.5 272 272 293.5 272 320C272 346.5 293.5 368 320 368z"/></svg>"##;
+
+// Stand-ins drawn from pixel primitives for when an SVG icon cannot be
+// rasterized, so the button and the list never lose their icons.
+const GITHUB_FALLBACK_ICON: [&str; 9] = [
+    "#########",
+    "#.......#",
+    "#..###..#",
+    "#.#.....#",
+    "#.#.##..#",
+    "#.#..#..#",
+    "#..###..#",
+    "#.......#",
+    "#########",
+];
+const PR_FALLBACK_ICON: [&str; 9] = [
+    "###......",
+    "#.#..#...",
+    "###.####.",
+    ".#...#.#.",
+    ".#.....#.",
+    ".#.....#.",
+    "###...###",
+    "#.#...#.#",
+    "###...###",
+];
+const ISSUE_FALLBACK_ICON: [&str; 7] = [
+    "..###..", ".#...#.", "#.....#", "#..#..#", "#.....#", ".#...#.", "..###..",
+];
+
+fn pixel_icon_texture(rows: &[&str]) -> Texture2D {
+    let height = rows.len() as u16;
+    let width = rows.first().map_or(0, |row| row.len()) as u16;
+    let mut image = Image::gen_image_color(width, height, Color::new(1.0, 1.0, 1.0, 0.0));
+    for (y, row) in rows.iter().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            if cell == '#' {
+                image.set_pixel(x as u32, y as u32, WHITE);
+            }
         }
     }
+    let texture = Texture2D::from_image(&image);
+    texture.set_filter(FilterMode::Nearest);
+    texture
 }
 
-thread_local! {
-    static FRAME_CONTEXT: RefCell<FrameContext> = RefCell::new(FrameContext::default());
+fn load_github_icon_texture(size: u32) -> Texture2D {
+    load_svg_texture(GITHUB_ICON_SVG, size)
+        .unwrap_or_else(|| pixel_icon_texture(&GITHUB_FALLBACK_ICON))
 }
 
-const GITHUB_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 640"><path fill="#ffffff" d="M237.9 461.4C237.9 463.4 235.6 465 232.7 465C229.4 465.3 227.1 463.7 227.1 461.4C227.1 459.4 229.4 457.8 232.3 457.8C235.3 457.5 237.9 459.1 237.9 461.4zM206.8 456.9C206.1 458.9 208.1 461.2 211.1 461.8C213.7 462.8 216.7 461.8 217.3 459.8C217.9 457.8 216 455.5 213 454.6C210.4 453.9 207.5 454.9 206.8 456.9zM251 455.2C248.1 455.9 246.1 457.8 246.4 460.1C246.7 462.1 249.3 463.4 252.3 462.7C255.2 462 257.2 460.1 256.9 458.1C256.6 456.2 253.9 454.9 251 455.2zM316.8 72C178.1 72 72 177.3 72 316C72 426.9 141.8 521.8 241.5 555.2C254.3 557.5 258.8 549.6 258.8 543.1C258.8 536.9 258.5 502.7 258.5 481.7C258.5 481.7 188.5 496.7 173.8 451.9C173.8 451.9 162.4 422.8 146 415.3C146 415.3 123.1 399.6 147.6 399.9C147.6 399.9 172.5 401.9 186.2 425.7C208.1 464.3 244.8 453.2 259.1 446.6C261.4 430.6 267.9 419.5 275.1 412.9C219.2 406.7 162.8 398.6 162.8 302.4C162.8 274.9 170.4 261.1 186.4 243.5C183.8 237 175.3 210.2 189 175.6C209.9 169.1 258 202.6 258 202.6C278 197 299.5 194.1 320.8 194.1C342.1 194.1 363.6 197 383.6 202.6C383.6 202.6 431.7 169 452.6 175.6C466.3 210.3 457.8 237 455.2 243.5C471.2 261.2 481 275 481 302.4C481 398.9 422.1 406.6 366.2 412.9C375.4 420.8 383.2 435.8 383.2 459.3C383.2 493 382.9 534.7 382.9 542.9C382.9 549.4 387.5 557.3 400.2 555C500.2 521.8 568 426.9 568 316C568 177.3 455.5 72 316.8 72zM169.2 416.9C167.9 417.9 168.2 420.2 169.9 422.1C171.5 423.7 173.8 424.4 175.1 423.1C176.4 422.1 176.1 419.8 174.4 417.9C172.8 416.3 170.5 415.6 169.2 416.9zM158.4 408.8C157.7 410.1 158.7 411.7 160.7 412.7C162.3 413.7 164.3 413.4 165 412C165.7 410.7 164.7 409.1 162.7 408.1C160.7 407.5 159.1 407.8 158.4 408.8zM190.8 444.4C189.2 445.7 189.8 448.7 192.1 450.6C194.4 452.9 197.3 453.2 198.6 451.6C199.9 450.3 199.3 447.3 197.3 445.4C195.1 443.1 192.1 442.8 190.8 444.4zM179.4 429.7C177.8 430.7 177.8 433.3 179.4 435.6C181 437.9 183.7 438.9 185 437.9C186.6 436.6 186.6 434 185 431.7C183.6 429.4 181 428.4 179.4 429.7z"/></svg>"##;
-
-const PR_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 640"><path fill="#ffffff" d="M176 120C189.3 120 200 130.7 200 144C200 157.3 189.3 168 176 168C162.7 168 152 157.3 152 144C152 130.7 162.7 120 176 120zM208.4 217.2C236.4 204.8 256 176.7 256 144C256 99.8 220.2 64 176 64C131.8 64 96 99.8 96 144C96 176.8 115.7 205 144 217.3L144 422.6C115.7 435 96 463.2 96 496C96 540.2 131.8 576 176 576C220.2 576 256 540.2 256 496C256 463.2 236.3 435 208 422.7L208 336.1C234.7 356.2 268 368.1 304 368.1L390.7 368.1C403 396.4 431.2 416.1 464 416.1C508.2 416.1 544 380.3 544 336.1C544 291.9 508.2 256.1 464 256.1C431.2 256.1 403 275.8 390.7 304.1L304 304C254.1 304 213 265.9 208.4 217.2zM176 472C189.3 472 200 482.7 200 496C200 509.3 189.3 520 176 520C162.7 520 152 509.3 152 496C152 482.7 162.7 472 176 472zM440 336C440 322.7 450.7 312 464 312C477.3 312 488 322.7 488 336C488 349.3 477.3 360 464 360C450.7 360 440 349.3 440 336z"/></svg>"##;
-
-fn update_context(theme: Theme, container: Rect) {
-    FRAME_CONTEXT.with(|ctx| {
-        let mut ctx = ctx.borrow_mut();
-        ctx.theme = theme;
-        ctx.container = container;
-    });
+fn load_pr_icon_texture(size: u32) -> Texture2D {
+    load_svg_texture(PR_ICON_SVG, size).unwrap_or_else(|| pixel_icon_texture(&PR_FALLBACK_ICON))
 }
 
-fn load_github_icon_texture(size: u32) -> Option<Texture2D> {
-    let opt = Options::default();
-    let tree = Tree::from_str(GITHUB_ICON_SVG, &opt).ok()?;
-    let mut pixmap = Pixmap::new(size, size)?;
-    let svg_size = tree.size();
-    let scale = (size as f32 / svg_size.width()).min(size as f32 / svg_size.height());
-    let tx = (size as f32 - svg_size.width() * scale) * 0.5;
-    let ty = (size as f32 - svg_size.height() * scale) * 0.5;
-    let transform = Transform::from_scale(scale, scale).post_translate(tx, ty);
-    resvg::render(&tree, transform, &mut pixmap.as_mut());
-    let texture = Texture2D::from_rgba8(size as u16, size as u16, pixmap.data());
-    texture.set_filter(FilterMode::Nearest);
-    Some(texture)
+fn load_issue_icon_texture(size: u32) -> Texture2D {
+    load_svg_texture(ISSUE_ICON_SVG, size)
+        .unwrap_or_else(|| pixel_icon_texture(&ISSUE_FALLBACK_ICON))
 }
 
-fn load_pr_icon_texture(size: u32) -> Option<Texture2D> {
+#[cfg(feature = "svg")]
+fn load_svg_texture(svg: &str, size: u32) -> Option<Texture2D> {
     let opt = Options::default();
-    let tree = Tree::from_str(PR_ICON_SVG, &opt).ok()?;
+    let tree = Tree::from_str(svg, &opt).ok()?;
     let mut pixmap = Pixmap::new(size, size)?;
     let svg_size = tree.size();
     let scale = (size as f32 / svg_size.width()).min(size as f32 / svg_size.height());
@@ -209,1157 +263,1167 @@ fn load_pr_icon_texture(size: u32) -> Option<Texture2D> {
     Some(texture)
 }
 
-fn spawn_github_fetch(token: String) -> mpsc::Receiver<GithubFetchResult> {
-    let (tx, rx) = mpsc::channel();
-    thread::spawn(move || {
-        let agent = ureq::AgentBuilder::new()
-            .timeout(Duration::from_secs(4))
-            .build();
-        let auth_header = format!("Bearer {}", token);
-        let user_resp = agent
-            .get("https://api.github.com/user")
-            .set("User-Agent", "commit-clock")
-            .set("Authorization", &auth_header)
-            .set("Accept", "application/vnd.github+json")
-            .call();
-
-        let user_resp = match user_resp {
-            Ok(resp) if (200..300).contains(&resp.status()) => resp,
-            _ => {
-                let _ = tx.send(GithubFetchResult {
-                    connected: false,
-                    prs: Vec::new(),
-                });
-                return;
-            }
-        };
-
-        let user_status = user_resp.status();
-        let user_json: serde_json::Value = match user_resp.into_string() {
-            Ok(body) => {
-                println!("GitHub user status: {}", user_status);
-                println!("GitHub user response: {}", body);
-                serde_json::from_str(&body).unwrap_or_else(|_| serde_json::Value::Null)
-            }
-            Err(_) => {
-                let _ = tx.send(GithubFetchResult {
-                    connected: false,
-                    prs: Vec::new(),
-                });
-                return;
-            }
-        };
-
-        let login = match user_json.get("login").and_then(|value| value.as_str()) {
-            Some(login) => login.to_string(),
-            None => {
-                let _ = tx.send(GithubFetchResult {
-                    connected: false,
-                    prs: Vec::new(),
-                });
-                return;
-            }
-        };
-
-        let query = format!(
-            "https://api.github.com/search/issues?q=is:pr+is:open+author:{}&per_page=3&sort=updated&order=desc",
-            login
-        );
-        println!("GitHub PR query: {}", query);
-        let prs_resp = agent
-            .get(&query)
-            .set("User-Agent", "commit-clock")
-            .set("Authorization", &auth_header)
-            .set("Accept", "application/vnd.github+json")
-            .call();
-
-        let prs_resp = match prs_resp {
-            Ok(resp) if (200..300).contains(&resp.status()) => resp,
-            _ => {
-                let _ = tx.send(GithubFetchResult {
-                    connected: true,
-                    prs: Vec::new(),
-                });
-                return;
-            }
-        };
-
-        let prs_status = prs_resp.status();
-        let prs_json: serde_json::Value = match prs_resp.into_string() {
-            Ok(body) => {
-                println!("GitHub PR status: {}", prs_status);
-                println!("GitHub PR response: {}", body);
-                serde_json::from_str(&body).unwrap_or_else(|_| serde_json::Value::Null)
-            }
-            Err(_) => {
-                let _ = tx.send(GithubFetchResult {
-                    connected: true,
-                    prs: Vec::new(),
-                });
-                return;
-            }
-        };
-
-        let mut prs = prs_json
-            .get("items")
-            .and_then(|items| items.as_array())
-            .map(|items| {
-                items
-                    .iter()
-                    .filter_map(|item| {
-                        let title = item.get("title").and_then(|t| t.as_str())?;
-                        let url = item.get("html_url").and_then(|u| u.as_str())?;
-                        Some(GithubPr {
-                            title: title.to_string(),
-                            url: url.to_string(),
-                        })
-                    })
-                    .take(3)
-                    .collect::<Vec<GithubPr>>()
-            })
-            .unwrap_or_default();
-
-        if prs.is_empty() {
-            let repos_url = "https://api.github.com/user/repos?affiliation=owner,collaborator,organization_member&per_page=50&sort=updated";
-            println!("GitHub repos query: {}", repos_url);
-            let repos_resp = agent
-                .get(repos_url)
-                .set("User-Agent", "commit-clock")
-                .set("Authorization", &auth_header)
-                .set("Accept", "application/vnd.github+json")
-                .call();
-
-            let repos_resp = match repos_resp {
-                Ok(resp) if (200..300).contains(&resp.status()) => resp,
-                _ => {
-                    let _ = tx.send(GithubFetchResult {
-                        connected: true,
-                        prs: Vec::new(),
-                    });
-                    return;
-                }
-            };
-
-            let repos_status = repos_resp.status();
-            let repos_json: serde_json::Value = match repos_resp.into_string() {
-                Ok(body) => {
-                    println!("GitHub repos status: {}", repos_status);
-                    println!("GitHub repos response: {}", body);
-                    serde_json::from_str(&body).unwrap_or_else(|_| serde_json::Value::Null)
-                }
-                Err(_) => serde_json::Value::Null,
-            };
-
-            let repos = repos_json
-                .as_array()
-                .map(|items| {
-                    items
-                        .iter()
-                        .filter_map(|item| item.get("full_name").and_then(|v| v.as_str()))
-                        .take(20)
-                        .map(|name| name.to_string())
-                        .collect::<Vec<String>>()
-                })
-                .unwrap_or_default();
-
-            let mut matches: Vec<(String, GithubPr)> = Vec::new();
-            for repo in repos {
-                let pulls_url = format!(
-                    "https://api.github.com/repos/{}/pulls?state=open&per_page=10&sort=updated&direction=desc",
-                    repo
-                );
-                let pulls_resp = agent
-                    .get(&pulls_url)
-                    .set("User-Agent", "commit-clock")
-                    .set("Authorization", &auth_header)
-                    .set("Accept", "application/vnd.github+json")
-                    .call();
-
-                let pulls_resp = match pulls_resp {
-                    Ok(resp) if (200..300).contains(&resp.status()) => resp,
-                    _ => continue,
-                };
-
-                let pulls_json: serde_json::Value = match pulls_resp.into_string() {
-                    Ok(body) => {
-                        serde_json::from_str(&body).unwrap_or_else(|_| serde_json::Value::Null)
-                    }
-                    Err(_) => serde_json::Value::Null,
-                };
-
-                let pulls = match pulls_json.as_array() {
-                    Some(items) => items,
-                    None => continue,
-                };
-
-                for pr in pulls {
-                    let author = pr
-                        .get("user")
-                        .and_then(|u| u.get("login"))
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("");
-                    let title = pr.get("title").and_then(|v| v.as_str()).unwrap_or("");
-                    let url = pr.get("html_url").and_then(|v| v.as_str()).unwrap_or("");
-                    let updated = pr.get("updated_at").and_then(|v| v.as_str()).unwrap_or("");
-
-                    if author == login {
-                        matches.push((
-                            updated.to_string(),
-                            GithubPr {
-                                title: title.to_string(),
-                                url: url.to_string(),
-                            },
-                        ));
-                    }
-                }
-            }
-
-            matches.sort_by(|a, b| b.0.cmp(&a.0));
-            prs = matches.into_iter().map(|(_, pr)| pr).take(3).collect();
-        }
-
-        let _ = tx.send(GithubFetchResult {
-            connected: true,
-            prs,
-        });
-    });
-    rx
+#[cfg(not(feature = "svg"))]
+fn load_svg_texture(_svg: &str, _size: u32) -> Option<Texture2D> {
+    None
 }
 
-fn load_github_token() -> Option<String> {
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
-        let token = token.trim().to_string();
-        if !token.is_empty() {
-            return Some(token);
-        }
-    }
-
-    if let Ok(token) = std::env::var("CHRONO_GITHUB_TOKEN") {
-        let token = token.trim().to_string();
-        if !token.is_empty() {
-            return Some(token);
-        }
-    }
-
-    let home = std::env::var("HOME").ok()?;
-    let path = format!("{}/.config/chrono/token", home);
-    let token = std::fs::read_to_string(path).ok()?;
-    let token = token.trim().to_string();
-    if token.is_empty() {
-        None
-    } else {
-        Some(token)
+// Raster images go through resvg too, which already decodes PNG, JPEG,
+// GIF and WebP.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn load_avatar_texture(path: &Path, style: AvatarStyle, size: u32) -> Option<Texture2D> {
+    let href = path
+        .to_str()?
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;");
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="1" height="1"><image xlink:href="{}" width="1" height="1" preserveAspectRatio="xMidYMid slice"/></svg>"#,
+        href
+    );
+    let texture = load_svg_texture(&svg, size)?;
+    if style == AvatarStyle::Smooth {
+        texture.set_filter(FilterMode::Linear);
     }
+    Some(texture)
 }
 
-fn format_time(hour_format: HourFormat, time_format: TimeFormat) -> String {
-    let now = Local::now();
-    let mut hour = now.hour() as i32;
-    let minute = now.minute();
-    let second = now.second();
-
-    if hour_format == HourFormat::H12 {
-        hour %= 12;
-        if hour == 0 {
-            hour = 12;
+// Picks up avatars the fetch thread has saved since the last call, smaller
+// when the listed authors would not fit the texture budget at full size.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn load_avatar_textures(
+    textures: &mut TextureCache<Texture2D>,
+    items: &[WorkItem],
+    style: AvatarStyle,
+) {
+    if style == AvatarStyle::Off {
+        return;
+    }
+    let urls = items
+        .iter()
+        .filter_map(|item| item.avatar_url.as_ref())
+        .collect::<HashSet<&String>>();
+    let size = textures::fitting_size(style.texture_size(), urls.len(), textures.budget_bytes());
+    for url in urls {
+        if textures.contains(url) {
+            continue;
+        }
+        if let Some(texture) =
+            avatars::path(url).and_then(|path| load_avatar_texture(&path, style, size))
+        {
+            textures.insert(url.clone(), texture, textures::texture_bytes(size));
         }
     }
+}
 
-    match time_format {
-        TimeFormat::HhMmSs => format!("{:02}:{:02}:{:02}", hour, minute, second),
-        TimeFormat::HhMm => format!("{:02}:{:02}", hour, minute),
-        TimeFormat::MmSs => format!("{:02}:{:02}", minute, second),
-        TimeFormat::IsoTime => format!("{:02}:{:02}:{:02}", hour, minute, second),
-    }
+#[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+fn load_avatar_textures(
+    _textures: &mut TextureCache<Texture2D>,
+    _items: &[WorkItem],
+    _style: AvatarStyle,
+) {
 }
 
-fn am_pm_suffix(hour_format: HourFormat) -> Option<String> {
-    if hour_format == HourFormat::H24 {
-        return None;
+// What the board shows, for `CHRONO_EXPORT`.
+// `full_refresh` hints e-ink drivers to clear ghosting with a full redraw.
+#[cfg(feature = "network")]
+fn board_json(key: &SceneKey, panel: &[WorkItem], full_refresh: bool) -> serde_json::Value {
+    let row = |name: &str, text: &str| serde_json::json!({ "name": name, "text": text, "cells": glyph_rows(text) });
+    let mut rows = vec![
+        row("year", &key.year),
+        row("date", &key.date),
+        row("time", &key.time),
+    ];
+    if let Some(weather) = &key.weather {
+        rows.push(row("weather", weather));
     }
-    let hour = Local::now().hour();
-    if hour >= 12 {
-        Some("PM".to_string())
-    } else {
-        Some("AM".to_string())
+    if let Some(am_pm) = &key.am_pm {
+        rows.push(row("am_pm", am_pm));
     }
+    for line in &key.widget_lines {
+        rows.push(row("widget", &line.text));
+    }
+    serde_json::json!({
+        "theme": {
+            "background": color_hex(key.theme.background_color),
+            "inactive": color_hex(key.theme.inactive_color),
+            "active": color_hex(key.theme.active_color),
+        },
+        "rows": rows,
+        "github": format!("{:?}", key.github_status).to_lowercase(),
+        "items": panel
+            .iter()
+            .map(|item| serde_json::json!({
+                "title": item.title,
+                "url": item.url,
+                "meta": item.meta_line(platform::now().timestamp()),
+            }))
+            .collect::<Vec<_>>(),
+        "release": key.release_label,
+        "updated": key.updated_label,
+        "timezone": key.timezone,
+        "full_refresh": full_refresh,
+    })
 }
 
-fn format_year() -> String {
-    Local::now().year().to_string()
-}
-
-fn format_day_month() -> String {
-    let now = Local::now();
-    let day = now.day();
-    let month_name = match now.month() {
-        1 => "JAN",
-        2 => "FEB",
-        3 => "MAR",
-        4 => "APR",
-        5 => "MAY",
-        6 => "JUN",
-        7 => "JUL",
-        8 => "AUG",
-        9 => "SEP",
-        10 => "OCT",
-        11 => "NOV",
-        _ => "DEC",
-    };
-    format!("{:02}{}", day, month_name)
-}
-
-fn grid_from_height(target_height: f32, gap_ratio: f32) -> PixelGrid {
-    let cell = (target_height / 7.0).round().max(1.0);
-    let gap = (cell * gap_ratio).round().max(1.0);
-    PixelGrid { cell, gap }
-}
-
-fn draw_background(board_grid: PixelGrid) {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        clear_background(ctx.theme.background_color);
-        draw_grid(ctx.container, board_grid, ctx.theme.inactive_color);
-    });
-}
-
-fn draw_noise_pixels(board_grid: PixelGrid) {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let rect = ctx.container;
-        let step = (board_grid.step() * 1.4).round().max(6.0) as i32;
-        let dot = (board_grid.cell * 0.35).max(2.0);
-        for y in (rect.y as i32..(rect.y + rect.h) as i32).step_by(step as usize) {
-            for x in (rect.x as i32..(rect.x + rect.w) as i32).step_by(step as usize) {
-                let hash = (x * 37 + y * 101) & 255;
-                if hash < 22 {
-                    let alpha = 0.03 + (hash as f32 / 255.0) * 0.04;
-                    draw_rectangle(
-                        x as f32 + 2.0,
-                        y as f32 + 2.0,
-                        dot,
-                        dot,
-                        Color::new(
-                            ctx.theme.noise_color.r,
-                            ctx.theme.noise_color.g,
-                            ctx.theme.noise_color.b,
-                            alpha,
-                        ),
-                    );
-                }
-            }
-        }
-    });
-}
-
-fn draw_active_speckles(board_grid: PixelGrid, minute_seed: i32, blocked: &[Rect]) {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let rect = ctx.container;
-        let step = board_grid.step();
-        let cols = (rect.w / step).ceil() as i32;
-        let rows = (rect.h / step).ceil() as i32;
-        let total = (cols * rows).max(1);
-        let mut picks: Vec<(i32, i32)> = Vec::new();
-        for i in 0..9 {
-            let mut idx = ((minute_seed * 997 + i * 379) % total).abs();
-            for _ in 0..total {
-                let row = idx / cols;
-                let col = idx % cols;
-                let parity = (row + col) & 1;
-                if parity == 0 && !picks.contains(&(row, col)) {
-                    picks.push((row, col));
-                    break;
-                }
-                idx = (idx + 1) % total;
-            }
-        }
+fn main() {
+    let _ = dotenvy::dotenv();
+    diag::init_logging(std::env::args().any(|arg| arg == "--verbose" || arg == "-v"));
+    diag::install_panic_hook();
 
-        for (i, (row, col)) in picks.iter().enumerate() {
-            let alpha = if i < 3 {
-                0.35 + ((row * 13 + col * 7) & 15) as f32 / 120.0
-            } else if i < 6 {
-                0.65 + ((row * 23 + col * 11) & 15) as f32 / 120.0
-            } else {
-                0.95 + ((row * 31 + col * 17) & 7) as f32 / 100.0
+    #[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+    {
+        let args = std::env::args().collect::<Vec<String>>();
+        if let Some(idx) = args.iter().position(|arg| arg == "--store-github-token") {
+            let slot = match args.get(idx + 1).filter(|arg| !arg.starts_with('-')) {
+                Some(label) => secrets::TokenSlot::GithubAccount(label),
+                None => secrets::TokenSlot::Github,
             };
-            let speck_rect = Rect::new(
-                rect.x + *col as f32 * step,
-                rect.y + *row as f32 * step,
-                board_grid.cell,
-                board_grid.cell,
-            );
-            if rect_overlaps_any(speck_rect, blocked) {
-                continue;
-            }
-            draw_rectangle(
-                speck_rect.x,
-                speck_rect.y,
-                speck_rect.w,
-                speck_rect.h,
-                Color::new(
-                    ctx.theme.active_color.r,
-                    ctx.theme.active_color.g,
-                    ctx.theme.active_color.b,
-                    alpha.min(1.0),
-                ),
-            );
+            std::process::exit(secrets::store_token_from_stdin(slot));
         }
-    });
-}
-
-fn draw_grid(rect: Rect, grid: PixelGrid, color: Color) {
-    let step = grid.step();
-    let cols = (rect.w / step).ceil() as i32;
-    let rows = (rect.h / step).ceil() as i32;
-    for row in 0..rows {
-        for col in 0..cols {
-            let x = rect.x + col as f32 * step;
-            let y = rect.y + row as f32 * step;
-            draw_rectangle(x, y, grid.cell, grid.cell, color);
-        }
-    }
-}
-
-fn draw_pixel_text(text: &str, origin: Vec2, grid: PixelGrid, color: Color, cutout: bool) {
-    let step = grid.step();
-    let spacing = glyph_spacing(grid);
-    let mut cursor_x = origin.x;
-    for ch in text.chars() {
-        // 5x7 glyphs with pixel-based inter-character spacing.
-        let glyph = glyph_pattern(ch);
-        if let Some((min_x, max_x)) = glyph_bounds(glyph) {
-            let width_cols = (max_x - min_x + 1) as f32;
-            for (row, line) in glyph.iter().enumerate() {
-                for (col, cell) in line.chars().enumerate() {
-                    if cell == '#' {
-                        let x = cursor_x + (col as f32 - min_x as f32) * step;
-                        let y = origin.y + row as f32 * step;
-                        let draw_color = if cutout {
-                            color
-                        } else {
-                            apply_active_alpha(color, x, y)
-                        };
-                        draw_rectangle(x, y, grid.cell, grid.cell, draw_color);
-                    }
-                }
-            }
-            cursor_x += width_cols * step + spacing;
-        } else {
-            cursor_x += space_width_cols() * step + spacing;
+        if let Some(idx) = args.iter().position(|arg| arg == "--store-token") {
+            let Some(instance) = args.get(idx + 1) else {
+                eprintln!("Usage: chrono --store-token <instance url>");
+                std::process::exit(1);
+            };
+            std::process::exit(secrets::store_token_from_stdin(
+                secrets::TokenSlot::Instance(instance),
+            ));
         }
     }
-}
 
-fn measure_pixel_text(text: &str, grid: PixelGrid) -> Vec2 {
-    let step = grid.step();
-    let spacing = glyph_spacing(grid);
-    let mut width = 0.0;
-    let mut count = 0usize;
-    for ch in text.chars() {
-        let glyph = glyph_pattern(ch);
-        let cols = if let Some((min_x, max_x)) = glyph_bounds(glyph) {
-            (max_x - min_x + 1) as f32
-        } else {
-            space_width_cols()
-        };
-        width += cols * step + spacing;
-        count += 1;
+    #[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+    if std::env::args().any(|arg| arg == "--tui") {
+        std::process::exit(chrono_board::tui::run());
     }
-    if count > 0 {
-        width -= spacing;
-    }
-    let height = step * 7.0 - grid.gap;
-    vec2(width, height)
-}
 
-fn snap_to_grid(origin: f32, value: f32, step: f32) -> f32 {
-    let offset = value - origin;
-    origin + (offset / step).round() * step
+    macroquad::Window::from_config(conf(), run());
 }
 
-fn rect_overlaps_any(target: Rect, blocked: &[Rect]) -> bool {
-    blocked.iter().any(|rect| rects_intersect(target, *rect))
+// The focus link ending at `ends_at` and its QR code.
+fn share_code(base: &str, ends_at: i64) -> Option<(String, qrcodegen::QrCode)> {
+    let url = share::focus_url(base, ends_at);
+    share::qr_code(&url).map(|code| (url, code))
 }
 
-fn rects_intersect(a: Rect, b: Rect) -> bool {
-    a.x < b.x + b.w && a.x + a.w > b.x && a.y < b.y + b.h && a.y + a.h > b.y
-}
-
-fn point_in_rect(point: Vec2, rect: Rect) -> bool {
-    point.x >= rect.x
-        && point.x <= rect.x + rect.w
-        && point.y >= rect.y
-        && point.y <= rect.y + rect.h
-}
-
-fn open_url(url: &str) {
-    #[cfg(target_os = "macos")]
-    {
-        let _ = std::process::Command::new("open").arg(url).spawn();
-    }
-    #[cfg(target_os = "windows")]
-    {
-        let _ = std::process::Command::new("cmd")
-            .args(["/C", "start", "", url])
-            .spawn();
-    }
-    #[cfg(all(unix, not(target_os = "macos")))]
+async fn run() {
+    let mut config = Config::load();
+    diag::set_config_summary(config.redacted_summary());
+    let clock = time_source(config.demo_time);
+    let mut accent_index = 0usize;
+    // E-ink updates once a minute, so seconds are left out, as they are
+    // when the pulse cell stands in for them.
+    let mut time_format = match &config.time_format {
+        Some(format) => TimeFormat::Custom(format.clone()),
+        None if config.eink || config.seconds == SecondsStyle::Pulse => TimeFormat::HhMm,
+        None => TimeFormat::HhMmSs,
+    };
+    let mut github_status = ConnectionStatus::Unknown;
+    // Why the last fetch failed, shown next to the button.
+    let mut github_error: Option<ChronoError> = None;
+    let mut github_fetch: Option<fetch::Pending> = None;
+    let mut github_last_fetch = clock.now().timestamp();
+    // Set by the R and D keys (and at startup) to poll on the next frame.
+    let mut refresh_requested = true;
+    // When the current fetch began, for the watchdog, and when to retry
+    // after one was abandoned.
+    let mut fetch_started = get_time();
+    let mut retry_at: Option<i64> = None;
+    let mut github_token = load_github_token();
+    // Start from the last fetch on disk; the first poll replaces it.
+    #[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+    let cached = cache::load().unwrap_or_default();
+    #[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+    let cached = fetch::FetchResult::default();
+    // Where the last run left off.
+    let restored = config
+        .restore_session
+        .then(session::load)
+        .flatten()
+        .unwrap_or_default();
+    // The configured format, else the one H last switched to, else the
+    // system's.
+    let mut hour_format = config
+        .hour_format
+        .or(prefs::load().hour_format)
+        .unwrap_or_else(HourFormat::detect);
+    let mut github_prs: Vec<WorkItem> = cached.prs;
+    let mut issues: Vec<WorkItem> = cached.issues;
+    let mut work_panel = if restored.issues && cached.capabilities.issues {
+        WorkPanel::Issues
+    } else {
+        WorkPanel::Prs
+    };
+    // Index into the configured accounts; None shows every account.
+    let mut account_filter: Option<usize> = None;
+    // The heading (and the I key) only matter once something reports issues.
+    let mut issues_supported = cached.capabilities.issues;
+    let mut ci_statuses: Vec<CiStatus> = cached.ci;
+    let mut review_decisions: Vec<ReviewStatus> = cached.decisions;
+    let mut conflicts: Vec<String> = cached.conflicts;
+    let mut review_load: Vec<ReviewLoad> = Vec::new();
+    let mut releases: Vec<Release> = Vec::new();
+    // Events from `.ics` files dropped onto the window.
+    let mut dropped_events: Vec<Release> = Vec::new();
+    let mut installed_theme = import::installed_theme().unwrap_or_default();
+    let mut unread = 0usize;
+    // Kept through failed fetches; the calendar changes slowly.
+    let mut heatmap_cells: Vec<(usize, usize, u8)> = Vec::new();
+    let mut last_push: Option<i64> = None;
+    // Review requests in the last fetch, for the stats page.
+    let mut review_count = 0;
+    let mut workflows: Vec<WorkflowRuns> = Vec::new();
+    let mut rate_limits = RateLimits::default();
+    let mut last_updated: Option<i64> = cached.fetched_at;
+    let mut dock_badge = 0usize;
+    let mut notifications: Vec<WorkItem> = Vec::new();
+    let mut notifications_open = restored.notifications_open;
+    let mut sign_in_rx: Option<mpsc::Receiver<DeviceFlowEvent>> = None;
+    // User code and verification URL while a device sign-in is pending.
+    let mut sign_in: Option<(String, String)> = None;
+    let github_icon = load_github_icon_texture(96);
+    let pr_icon = load_pr_icon_texture(96);
+    let issue_icon = load_issue_icon_texture(96);
+    let mut power = PowerMonitor::new(config.power_save);
+    let mut appearance =
+        (config.dark_theme.is_some() || config.light_theme.is_some()).then(AppearanceMonitor::new);
+    let mut scene_target: Option<RenderTarget> = None;
+    let mut scene_key: Option<SceneKey> = None;
+    let mut pr_hits: Vec<ItemHit> = Vec::new();
+    let mut window_drag: Option<WindowDrag> = None;
+    // The window size to go back to when compact mode is toggled off.
+    let mut compact_restore: Option<(f32, f32)> = None;
+    let mut page = Page::Clock;
+    let mut swipe_start: Option<Vec2> = None;
+    let mut context_menu: Option<Vec2> = None;
+    let mut menu_hits: Vec<(Rect, MenuAction)> = Vec::new();
+    let mut pr_tooltips: Vec<Tooltip> = Vec::new();
+    // The hovered tooltip's rect and since when.
+    let mut tooltip_hover: Option<(Rect, f64)> = None;
+    let mut github_login: Option<String> = None;
+    // Where the list went last frame, for wheel scrolling over it.
+    let mut pr_area: Option<Rect> = None;
+    let mut pr_scroll = 0usize;
+    let mut announcer = a11y::Announcer::new();
+    let webhooks = Webhooks::new(config.webhook_urls.clone(), config.webhook_events.clone());
+    let mut last_hour: Option<u32> = None;
+    let mut known_reviews: Option<HashSet<String>> = None;
+    let mut pr_alerts = alerts::PrAlerts::default();
+    let mut theme_override = ThemeOverride::default();
+    let mut menu_bar = menubar::MenuBar::new(config.menu_bar);
+    let sounds = sound::Sounds::load(config.sound_volume).await;
+    let mut animations = Animations::default();
+    // Rows added and removed by the last fetch, slid in and out of the list.
+    let mut list_change = ListChange::default();
+    // Index into the configured dashboards, switched with 1-9.
+    let mut active_dashboard = if restored.dashboard < config.dashboards.len() {
+        restored.dashboard
+    } else {
+        0
+    };
+    let mut window_title = String::new();
+    let mut avatar_textures = TextureCache::new(config.texture_budget_kb * 1024);
+    load_avatar_textures(&mut avatar_textures, &github_prs, config.avatars);
+    let no_avatars = TextureCache::new(0);
+    let mut presence =
+        presence::Presence::new(config.idle_dim_minutes, config.idle_brightness, get_time());
+    let mut watched_file = config.watch_file.clone().map(watch::WatchedFile::new);
+    let mut command_widget = config
+        .command
+        .clone()
+        .map(|command| command::CommandWidget::new(command, config.command_secs));
+    let mut weather_widget = weather::Query::from_config(&config)
+        .map(|query| weather::WeatherWidget::new(query, config.weather_secs));
+    let mut meetings = (!config.calendars.is_empty())
+        .then(|| meetings::Meetings::new(config.calendars.clone(), config.calendar_secs));
+    // The configured Jira URLs plus issue keys providers such as Linear
+    // report.
+    let mut key_links = config.jira.clone();
+    let mut pomodoro = pomodoro::Pomodoro::new(config.pomodoro_minutes);
+    // A shared session takes over from the one the last run left behind.
+    if let Some(until) = platform::launch_link()
+        .and_then(|link| share::focus_until(&link))
+        .or(restored.focus_until)
     {
-        let _ = std::process::Command::new("xdg-open").arg(url).spawn();
+        pomodoro.join(get_time(), (until - clock.now().timestamp()) as f64);
     }
-}
-
-fn glyph_bounds(glyph: [&'static str; 7]) -> Option<(usize, usize)> {
-    let mut min_x = usize::MAX;
-    let mut max_x = 0usize;
-    let mut found = false;
-    for line in glyph.iter() {
-        for (idx, cell) in line.chars().enumerate() {
-            if cell == '#' {
-                min_x = min_x.min(idx);
-                max_x = max_x.max(idx);
-                found = true;
-            }
+    // The link and QR code of the running session while Q shows them.
+    let mut share = match (&config.share_url, pomodoro.remaining(get_time())) {
+        (Some(base), Some(remaining)) if restored.share_open => {
+            share_code(base, clock.now().timestamp() + remaining.ceil() as i64)
         }
-    }
-    if found {
-        Some((min_x, max_x))
-    } else {
-        None
-    }
-}
-
-fn glyph_spacing(grid: PixelGrid) -> f32 {
-    grid.step()
-}
-
-fn space_width_cols() -> f32 {
-    3.0
-}
-
-fn apply_active_alpha(color: Color, x: f32, y: f32) -> Color {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let hash = ((x as i32 * 29 + y as i32 * 91) & 255) as f32 / 255.0;
-        let jitter = (hash - 0.5) * 2.0 * ctx.theme.active_alpha_jitter;
-        let alpha = (ctx.theme.active_alpha + jitter).clamp(0.2, 1.0);
-        Color::new(color.r, color.g, color.b, alpha)
-    })
-}
-
-fn draw_clock(
-    year_str: &str,
-    date_str: &str,
-    time_str: &str,
-    am_pm: Option<&str>,
-    minute_seed: i32,
-) -> ClockLayout {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let container = ctx.container;
-        let year_grid = grid_from_height(8.0, 0.25);
-        let date_grid = grid_from_height(24.0, 0.25);
-        let time_grid = grid_from_height(42.0, 0.25);
-        let board_grid = time_grid;
-        let gap_small = 2.0;
-        let gap_large = 2.0;
-
-        let year_size = measure_pixel_text(year_str, year_grid);
-        let date_size = measure_pixel_text(date_str, date_grid);
-        let time_size = measure_pixel_text(time_str, time_grid);
-        let am_pm_size = am_pm
-            .map(|value| measure_pixel_text(value, year_grid))
-            .unwrap_or(vec2(0.0, 0.0));
-        draw_background(board_grid);
-        draw_noise_pixels(board_grid);
-
-        let padding = 12.0;
-        let mut year_origin = vec2(container.x + padding, container.y + padding);
-        year_origin.x = snap_to_grid(container.x, year_origin.x, board_grid.step());
-        year_origin.y = snap_to_grid(container.y, year_origin.y, board_grid.step());
-
-        let mut date_origin = vec2(
-            container.x + padding,
-            year_origin.y + year_size.y + gap_small,
-        );
-        date_origin.x = snap_to_grid(container.x, date_origin.x, board_grid.step());
-        date_origin.y = snap_to_grid(container.y, date_origin.y, board_grid.step());
+        _ => None,
+    };
+    let mut saved_session = None;
+    let mut sun = config.location.map(sun::Sun::new);
+    let mut wake_light = config
+        .wake_time
+        .map(|at| wake::WakeLight::new(at, config.wake_ramp_minutes));
+    #[cfg(feature = "network")]
+    let mut exporter = config
+        .export
+        .as_ref()
+        .and_then(|target| export::Exporter::new(target, config.eink));
+    // `--record <file>` captures the first seconds of the board.
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut recorder = std::env::args()
+        .skip_while(|arg| arg != "--record")
+        .nth(1)
+        .and_then(|path| {
+            recording::Recorder::new(path.into(), config.record_secs, config.record_fps)
+        });
+    #[cfg(feature = "network")]
+    let mut metrics = config.metrics_addr.as_deref().and_then(MetricsServer::bind);
+    let mut team_sync = config
+        .team_sync
+        .then(|| teamsync::TeamSync::bind(config.team_sync_port))
+        .flatten();
 
-        let mut time_origin = vec2(
-            container.x + padding,
-            date_origin.y + date_size.y + gap_large,
+    loop {
+        let frame_start = get_time();
+        // Read once so the time, date and year rows turn over together, and
+        // the lighting agrees with them.
+        let frame_clock = FixedClock::snapshot(&*clock);
+        let now = frame_clock.0;
+        let power_save = power.active(frame_start);
+        let night_level = config
+            .night_hours
+            .map_or(0.0, |hours| night::level(now.time(), hours));
+        let brightness = presence
+            .update(frame_start)
+            .min(night::brightness(night_level, config.night_brightness));
+        let sunrise = wake_light.as_mut().and_then(|light| light.update(now));
+        let sun_times = sun.as_mut().map(|sun| sun.times(now));
+        let warmth = sun_times
+            .filter(|_| config.sunset_tint)
+            .map_or(0.0, |times| sun::warmth(now, times))
+            .max(night_level);
+        // The sunrise sets the brightness itself, idle dimming or not.
+        let brightness = sunrise.map_or(brightness, |sunrise| sunrise.brightness);
+        let sunrise_override = sunrise
+            .map(|sunrise| ThemeOverride {
+                active_color: Some(sunrise.accent),
+                noise_color: Some(sunrise.accent),
+                ..Default::default()
+            })
+            .unwrap_or_default();
+        let accent = ACCENT_PALETTE[accent_index];
+        // The dark or light theme goes between the installed one and the
+        // dashboard.
+        let system_theme = appearance
+            .as_mut()
+            .and_then(|monitor| monitor.update(frame_start))
+            .and_then(|mode| match mode {
+                Appearance::Dark => config.dark_theme.clone(),
+                Appearance::Light => config.light_theme.clone(),
+            })
+            .map(|theme| theme.over(&installed_theme));
+        let dashboard = config
+            .dashboards
+            .get(active_dashboard)
+            .cloned()
+            .unwrap_or_default()
+            .over(system_theme.as_ref().unwrap_or(&installed_theme));
+        let show_prs = dashboard.show_prs.unwrap_or(true);
+        let theme = Theme {
+            background_color: BACKGROUND_COLOR,
+            inactive_color: INACTIVE_COLOR,
+            active_color: accent,
+            noise_color: accent,
+            am_pm_color: None,
+            status: config.status_palette,
+            active_alpha: 0.82,
+            active_alpha_jitter: 0.4,
+            min_alpha: 0.2,
+            text_scale: 1.0,
+            hour_wave: false,
+            hour_chirp: false,
+            cell_shape: dashboard.cell_shape.unwrap_or(config.cell_shape),
+            speckle_placement: config.speckle_placement,
+            speckle_intensity: config.speckle_intensity,
+        }
+        .with_override(&config.colors)
+        .with_override(&dashboard.theme)
+        .with_override(&theme_override)
+        .for_frame(
+            config.high_contrast,
+            config.eink,
+            warmth,
+            &sunrise_override,
+            brightness,
         );
-        time_origin.x = snap_to_grid(container.x, time_origin.x, board_grid.step());
-        time_origin.y = snap_to_grid(container.y, time_origin.y, board_grid.step());
+        // Nothing moves on e-ink; effects stay off as in power save.
+        let calm = power_save || config.eink;
 
-        let active = ctx.theme.active_color;
+        let container = Rect::new(0.0, 0.0, screen_width(), screen_height());
+        update_context(theme, container);
 
-        let mut blocked = Vec::new();
-        blocked.extend(collect_glyph_rects(year_str, year_origin, year_grid));
-        blocked.extend(collect_glyph_rects(date_str, date_origin, date_grid));
-        blocked.extend(collect_glyph_rects(time_str, time_origin, time_grid));
+        // Tenths are meaningless at one frame per second.
+        let shown_format = if calm && time_format.subsecond() {
+            TimeFormat::HhMmSs
+        } else {
+            time_format.clone()
+        };
+        let ClockText {
+            time: time_string,
+            am_pm,
+            day_month: date_string,
+            year: year_string,
+        } = ClockText::read(
+            &frame_clock,
+            hour_format,
+            &shown_format,
+            config.date_format.as_ref(),
+        );
+        // The menu bar and screen readers follow whole seconds.
+        let steady_time = if shown_format.subsecond() {
+            format_time(&frame_clock, hour_format, &TimeFormat::HhMmSs)
+        } else {
+            time_string.clone()
+        };
+        // Both blink in step; e-ink keeps the colon and has no pulse.
+        let tick = tick_on(now, calm);
+        let colon_lit = !config.blink_colon || config.eink || tick;
+        let pulse = (config.seconds == SecondsStyle::Pulse && !config.eink).then_some(tick);
+        let weather_label = weather_widget
+            .as_mut()
+            .and_then(|widget| widget.weather(frame_start))
+            .map(|weather| weather.label());
+        let date_line = match &weather_label {
+            Some(label) => format!("{}  {}", date_string, label),
+            None => date_string.clone(),
+        };
 
-        let mut am_pm_origin = None;
-        if am_pm.is_some() {
-            let mut origin = vec2(
-                time_origin.x + time_size.x + time_grid.step(),
-                time_origin.y + time_size.y - am_pm_size.y,
-            );
-            origin.x = snap_to_grid(container.x, origin.x, board_grid.step());
-            origin.y = snap_to_grid(container.y, origin.y, board_grid.step());
-            am_pm_origin = Some(origin);
+        // The wake alarm rings through quiet hours; nothing else does.
+        let quiet = config
+            .quiet_hours
+            .is_some_and(|hours| hours.contains(now.time()));
+        if sunrise.is_some_and(|sunrise| sunrise.ring) {
+            webhooks.send(WebhookEvent::AlarmFired { time: now });
+            sounds.play(Cue::Chirp);
+            if !calm {
+                animations.trigger(Effect::HourWave, frame_start);
+            }
         }
 
-        if let (Some(suffix), Some(origin)) = (am_pm, am_pm_origin) {
-            blocked.extend(collect_glyph_rects(suffix, origin, year_grid));
+        if pomodoro.finished(frame_start) {
+            webhooks.send(WebhookEvent::PomodoroFinished { time: now });
         }
 
-        draw_active_speckles(board_grid, minute_seed, &blocked);
-        draw_pixel_text(year_str, year_origin, year_grid, active, false);
-        draw_pixel_text(date_str, date_origin, date_grid, active, false);
-        draw_pixel_text(time_str, time_origin, time_grid, active, false);
-
-        if let (Some(suffix), Some(origin)) = (am_pm, am_pm_origin) {
-            let am_pm_color = Color::new(active.r, active.g, active.b, 0.75);
-            draw_pixel_text(suffix, origin, year_grid, am_pm_color, false);
+        if last_hour.is_some_and(|hour| hour != now.hour()) {
+            webhooks.send(WebhookEvent::Hourly { time: now });
+            if theme.hour_wave && !calm {
+                animations.trigger(Effect::HourWave, frame_start);
+            }
+            if config.chime && !quiet {
+                sounds.play(Cue::Chime);
+            } else if theme.hour_chirp && !quiet {
+                sounds.play(Cue::Chirp);
+            }
+            let celebrate = match config.celebration {
+                Celebration::Off => false,
+                Celebration::NewYear => now.month() == 1 && now.day() == 1,
+                Celebration::Daily => true,
+            };
+            if now.hour() == 0 && celebrate && !calm {
+                animations.trigger(Effect::Fireworks, frame_start);
+            }
         }
+        last_hour = Some(now.hour());
 
-        ClockLayout {
-            time_bottom: time_origin.y + time_size.y,
-            left_x: year_origin.x,
-            board_grid,
-            pr_grid: year_grid,
+        let poll_secs = dashboard.poll_secs.unwrap_or(config.poll_secs);
+        let poll_secs = if power_save {
+            poll_secs.max(POWER_SAVE_POLL_SECS)
+        } else {
+            poll_secs
+        };
+        // With the quota nearly spent, wait for the window to reset instead
+        // of polling into errors.
+        let rate_limited = rate_limits.holding(now.timestamp()).is_some();
+        let poll_due = refresh_requested
+            || retry_at.is_some_and(|at| now.timestamp() >= at)
+            || (poll_secs > 0 && now.timestamp() - github_last_fetch >= poll_secs);
+        if poll_due && github_fetch.is_none() && !rate_limited {
+            refresh_requested = false;
+            retry_at = None;
+            github_last_fetch = now.timestamp();
+            fetch_started = frame_start;
+            github_status = if github_token.is_some() || !config.github_accounts.is_empty() {
+                ConnectionStatus::Unknown
+            } else {
+                ConnectionStatus::Disconnected
+            };
+            if github_token.is_some()
+                || !config.github_accounts.is_empty()
+                || !config.forges.is_empty()
+                || config.linear
+            {
+                github_fetch = Some(fetch::spawn(github_token.clone(), config.clone()));
+            } else {
+                github_prs.clear();
+            }
         }
-    })
-}
 
-fn github_button_rect(container: Rect, grid: PixelGrid) -> Rect {
-    let size = (grid.step() * 3.0).round().max(grid.step());
-    let padding = 8.0;
-    let mut x = container.x + container.w - size - padding;
-    let mut y = container.y + padding;
-    x = snap_to_grid(container.x, x, grid.step());
-    y = snap_to_grid(container.y, y, grid.step());
-    Rect::new(x, y, size, size)
-}
+        let mut fetch_died = false;
+        if let Some(Poll::Ready(received)) = github_fetch.as_mut().map(fetch::poll) {
+            fetch_died = received.is_none();
+            if let Some(result) = received {
+                #[cfg(feature = "network")]
+                if let Some(server) = metrics.as_mut() {
+                    server.metrics.fetch(
+                        frame_start - fetch_started,
+                        result.error.as_ref().map(ChronoError::kind),
+                    );
+                    server.metrics.set_rate_limits(result.rate_limits.clone());
+                }
+                rate_limits = result.rate_limits.clone();
+                last_updated = result.fetched_at.or(last_updated);
+                github_status = if result.connected {
+                    ConnectionStatus::Connected
+                } else if rate_limits.spent() || result.error == Some(ChronoError::RateLimited) {
+                    ConnectionStatus::RateLimited
+                } else if result.error.as_ref().is_some_and(ChronoError::is_transient) {
+                    // Try again well before the next regular poll.
+                    retry_at = Some(now.timestamp() + FETCH_RETRY_SECS);
+                    ConnectionStatus::Unreachable
+                } else {
+                    ConnectionStatus::Disconnected
+                };
+                github_error = result.error;
+                list_change = ListChange::between(&github_prs, &result.prs);
+                if !list_change.is_empty() && !calm && dashboard.effects.unwrap_or(true) {
+                    animations.trigger(Effect::ListChange, frame_start);
+                }
+                github_prs = result.prs;
+                // A failed fetch has no PRs and must not reset the baseline.
+                if (config.desktop_notifications || config.activity_sound) && result.connected {
+                    let new = pr_alerts.update(&github_prs, config.desktop_notifications);
+                    if new && config.activity_sound && !quiet {
+                        sounds.play(Cue::Activity);
+                    }
+                }
+                load_avatar_textures(&mut avatar_textures, &github_prs, config.avatars);
+                issues = result.issues;
+                issues_supported = result.capabilities.issues;
+                for (prefix, url) in result.issue_links {
+                    if !key_links.projects.iter().any(|(known, _)| *known == prefix) {
+                        key_links.projects.push((prefix, url));
+                    }
+                }
+                review_load = result.review_load;
+                ci_statuses = result.ci.clone();
+                review_decisions = result.decisions;
+                conflicts = result.conflicts;
+                releases = result.releases;
+                unread = result.unread;
+                if !result.contributions.is_empty() {
+                    heatmap_cells = heatmap::cells(&result.contributions);
+                }
+                last_push = result.last_push.or(last_push);
+                github_login = result.login.clone().or(github_login);
+                if result.connected {
+                    workflows = result.workflows;
+                }
+                notifications = result.notifications;
+                if unread == 0 {
+                    notifications_open = false;
+                }
+                github_fetch = None;
+                theme_override = rules::evaluate(
+                    &config.color_rules,
+                    &rules::RuleInputs {
+                        prs: github_prs.len(),
+                        reviews: result.reviews.len(),
+                        connected: result.connected,
+                        main_failing: result.main_ci == Some(CiState::Failure),
+                    },
+                );
 
-fn draw_github_button(status: ConnectionStatus, icon: Option<&Texture2D>, rect: Rect) {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let button_color = Color::new(
-            ctx.theme.inactive_color.r * 0.9,
-            ctx.theme.inactive_color.g * 0.9,
-            ctx.theme.inactive_color.b * 0.9,
-            1.0,
-        );
-        draw_rectangle(rect.x, rect.y, rect.w, rect.h, button_color);
+                if config.dock_badge
+                    && result.capabilities.reviews
+                    && result.reviews.len() != dock_badge
+                {
+                    dock_badge = result.reviews.len();
+                    platform::set_dock_badge(dock_badge);
+                }
 
-        let icon_color = match status {
-            ConnectionStatus::Connected => ctx.theme.active_color,
-            ConnectionStatus::Disconnected | ConnectionStatus::Unknown => {
-                Color::new(1.0, 1.0, 1.0, 1.0)
+                // The first successful fetch only establishes the baseline.
+                if result.capabilities.reviews {
+                    review_count = result.reviews.len();
+                    if let Some(known) = &known_reviews {
+                        for review in result.reviews.iter() {
+                            if !known.contains(&review.url) {
+                                webhooks.send(WebhookEvent::ReviewRequested {
+                                    title: review.title.clone(),
+                                    url: review.url.clone(),
+                                });
+                            }
+                        }
+                    }
+                    known_reviews = Some(
+                        result
+                            .reviews
+                            .iter()
+                            .map(|review| review.url.clone())
+                            .collect(),
+                    );
+                }
             }
-        };
+        }
 
-        if let Some(texture) = icon {
-            let icon_size = rect.w.min(rect.h);
-            let icon_x = rect.x + (rect.w - icon_size) * 0.5;
-            let icon_y = rect.y + (rect.h - icon_size) * 0.5;
-            draw_texture_ex(
-                texture,
-                icon_x,
-                icon_y,
-                icon_color,
-                DrawTextureParams {
-                    dest_size: Some(vec2(icon_size, icon_size)),
-                    ..Default::default()
-                },
+        // A fetch stuck on a slow proxy, or one where a provider panicked,
+        // would otherwise block every later poll. It is dropped, which
+        // cancels whatever has not started, and retried.
+        if github_fetch.is_some()
+            && (fetch_died || frame_start - fetch_started >= FETCH_DEADLINE_SECS)
+        {
+            log!(
+                "Fetch {}; retrying in {}s",
+                if fetch_died { "failed" } else { "timed out" },
+                FETCH_RETRY_SECS
             );
+            #[cfg(feature = "network")]
+            if let Some(server) = metrics.as_mut() {
+                server.metrics.fetch(
+                    frame_start - fetch_started,
+                    Some(if fetch_died { "aborted" } else { "timeout" }),
+                );
+            }
+            github_fetch = None;
+            github_status = ConnectionStatus::TimedOut;
+            retry_at = Some(now.timestamp() + FETCH_RETRY_SECS);
         }
-    });
-}
 
-fn collect_glyph_rects(text: &str, origin: Vec2, grid: PixelGrid) -> Vec<Rect> {
-    let step = grid.step();
-    let spacing = glyph_spacing(grid);
-    let mut rects = Vec::new();
-    let mut cursor_x = origin.x;
-    for ch in text.chars() {
-        let glyph = glyph_pattern(ch);
-        if let Some((min_x, max_x)) = glyph_bounds(glyph) {
-            let width_cols = (max_x - min_x + 1) as f32;
-            for (row, line) in glyph.iter().enumerate() {
-                for (col, cell) in line.chars().enumerate() {
-                    if cell == '#' {
-                        rects.push(Rect::new(
-                            cursor_x + (col as f32 - min_x as f32) * step,
-                            origin.y + row as f32 * step,
-                            grid.cell,
-                            grid.cell,
-                        ));
-                    }
+        if let Some(rx) = &sign_in_rx {
+            match rx.try_recv() {
+                Ok(DeviceFlowEvent::Code {
+                    user_code,
+                    verification_uri,
+                }) => {
+                    open_url(&verification_uri);
+                    sign_in = Some((user_code, verification_uri));
+                }
+                Ok(DeviceFlowEvent::Token(token)) => {
+                    sign_in_rx = None;
+                    sign_in = None;
+                    github_token = Some(token.clone());
+                    github_last_fetch = now.timestamp();
+                    github_status = ConnectionStatus::Unknown;
+                    github_fetch = Some(fetch::spawn(Some(token), config.clone()));
+                }
+                Ok(DeviceFlowEvent::Failed(e)) => {
+                    log!("GitHub sign-in failed: {e}");
+                    sign_in_rx = None;
+                    sign_in = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    sign_in_rx = None;
+                    sign_in = None;
                 }
             }
-            cursor_x += width_cols * step + spacing;
-        } else {
-            cursor_x += space_width_cols() * step + spacing;
         }
-    }
-    rects
-}
 
-fn wrap_text_to_width(text: &str, max_width: f32, font_size: u16) -> Vec<String> {
-    let mut lines = Vec::new();
-    let mut current = String::new();
-    for word in text.split_whitespace() {
-        let spacer = if current.is_empty() { "" } else { " " };
-        let candidate = format!("{}{}{}", current, spacer, word);
-        let candidate_width = measure_text(&candidate, None, font_size, 1.0).width;
+        let button_grid = grid_from_height(42.0, 0.25);
+        let button_rect = github_button_rect(container, button_grid);
 
-        if candidate_width <= max_width {
-            current = candidate;
-        } else {
-            if !current.is_empty() {
-                lines.push(current);
-            }
-            current = word.to_string();
+        let compact = compact_restore.is_some();
+        // Compact mode and the other pages go without the button and badge.
+        let bare = compact || page != Page::Clock;
+        let button_shown = cfg!(feature = "network") && !bare;
+        let badge_rect =
+            (unread > 0 && !bare).then(|| notification_badge_rect(unread, button_rect));
+
+        // An open context menu takes the next click, on an entry or not.
+        let mut clicked = is_mouse_button_pressed(MouseButton::Left);
+        let mut menu_action = None;
+        if clicked && context_menu.is_some() {
+            let point = Vec2::from(mouse_position());
+            menu_action = menu_hits
+                .iter()
+                .find(|(rect, _)| point_in_rect(point, *rect))
+                .map(|(_, action)| *action);
+            context_menu = None;
+            clicked = false;
         }
-    }
-
-    if !current.is_empty() {
-        lines.push(current);
-    }
-
-    if lines.is_empty() {
-        lines.push(String::new());
-    }
-
-    lines
-}
-
-fn is_jira_key(value: &str) -> bool {
-    if let Some((left, right)) = value.split_once('-') {
-        if left.len() >= 2
-            && right.len() >= 1
-            && left.chars().all(|c| c.is_ascii_uppercase())
-            && right.chars().all(|c| c.is_ascii_digit())
-        {
-            return true;
+        if is_mouse_button_pressed(MouseButton::Right) {
+            context_menu = Some(Vec2::from(mouse_position()));
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            context_menu = None;
         }
-    }
-    false
-}
-
-fn find_jira_in_line(line: &str) -> Option<(usize, usize, String)> {
-    let mut token = String::new();
-    let mut token_start = 0usize;
 
-    for (idx, ch) in line.char_indices() {
-        if ch.is_ascii_alphanumeric() || ch == '-' {
-            if token.is_empty() {
-                token_start = idx;
+        if let Some(drag) = window_drag.as_mut() {
+            let point = Vec2::from(mouse_position());
+            if !is_mouse_button_down(MouseButton::Left) {
+                window_drag = None;
+            } else if point != drag.last {
+                // Stale until the next motion event after a move, hence
+                // the check against the last position.
+                drag.last = point;
+                let offset = point - drag.grab;
+                drag.origin.0 += offset.x as i32;
+                drag.origin.1 += offset.y as i32;
+                platform::move_window(drag.origin.0, drag.origin.1);
             }
-            token.push(ch);
-        } else if !token.is_empty() {
-            if is_jira_key(&token) {
-                return Some((token_start, idx, token.clone()));
-            }
-            token.clear();
         }
-    }
 
-    if !token.is_empty() && is_jira_key(&token) {
-        return Some((token_start, line.len(), token));
-    }
-    None
-}
-
-fn draw_pr_list(prs: &[GithubPr], icon: Option<&Texture2D>, layout: ClockLayout) -> Vec<PrHit> {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let step = layout.board_grid.step();
-        let offset = step * 3.0;
-        let mut y = layout.time_bottom + offset;
-        y = snap_to_grid(ctx.container.y, y, layout.board_grid.step());
-
-        let icon_size = 16.0;
-        let font_size = 14u16;
-        let line_height = font_size as f32 + 4.0;
-        let item_gap = 6.0;
-        let mut line_y = y;
-        let mut hits = Vec::new();
-        for pr in prs.iter() {
-            let text_x = if icon.is_some() {
-                layout.left_x + icon_size + layout.pr_grid.step()
-            } else {
-                layout.left_x
-            };
-            let max_width = ctx.container.w - text_x - 12.0;
-            let wrapped = wrap_text_to_width(&pr.title, max_width, font_size);
-            if wrapped.iter().all(|line| line.trim().is_empty()) {
-                continue;
-            }
-            if let Some(texture) = icon {
-                let icon_y = line_y + (line_height - icon_size) * 0.5 + 2.0;
-                let (mx, my) = mouse_position();
-                let hover = point_in_rect(
-                    vec2(mx, my),
-                    Rect::new(layout.left_x, icon_y, icon_size, icon_size),
-                );
-                let icon_color = if hover {
-                    Color::new(1.0, 1.0, 1.0, 1.0)
+        if clicked {
+            let (mx, my) = mouse_position();
+            if badge_rect.is_some_and(|rect| point_in_rect(vec2(mx, my), rect)) {
+                notifications_open = !notifications_open;
+            } else if button_shown && point_in_rect(vec2(mx, my), button_rect) {
+                github_token = load_github_token();
+                github_status = if github_token.is_some() || !config.github_accounts.is_empty() {
+                    ConnectionStatus::Unknown
                 } else {
-                    ctx.theme.active_color
+                    ConnectionStatus::Disconnected
                 };
-                draw_texture_ex(
-                    texture,
-                    layout.left_x,
-                    icon_y,
-                    icon_color,
-                    DrawTextureParams {
-                        dest_size: Some(vec2(icon_size, icon_size)),
-                        ..Default::default()
-                    },
-                );
-                hits.push(PrHit {
-                    rect: Rect::new(layout.left_x, icon_y, icon_size, icon_size),
-                    url: pr.url.clone(),
-                });
-            }
-            let mut current_y = line_y;
-            for (idx, line) in wrapped.iter().enumerate() {
-                let y = current_y + font_size as f32 + line_height * idx as f32;
-                if let Some((start, end, jira_key)) = find_jira_in_line(line) {
-                    let before = &line[..start];
-                    let key_text = &line[start..end];
-                    let after = &line[end..];
-
-                    let before_width = measure_text(before, None, font_size, 1.0).width;
-                    let key_width = measure_text(key_text, None, font_size, 1.0).width;
-                    let key_rect = Rect::new(
-                        text_x + before_width,
-                        y - font_size as f32,
-                        key_width,
-                        line_height,
-                    );
-
-                    let (mx, my) = mouse_position();
-                    let hover = point_in_rect(vec2(mx, my), key_rect);
-                    let key_color = if hover {
-                        Color::new(1.0, 1.0, 1.0, 1.0)
-                    } else {
-                        ctx.theme.active_color
-                    };
-
-                    draw_text(
-                        before,
-                        text_x,
-                        y,
-                        font_size as f32,
-                        Color::new(1.0, 1.0, 1.0, 1.0),
-                    );
-                    draw_text(
-                        key_text,
-                        text_x + before_width,
-                        y,
-                        font_size as f32,
-                        key_color,
-                    );
-                    draw_text(
-                        after,
-                        text_x + before_width + key_width,
-                        y,
-                        font_size as f32,
-                        Color::new(1.0, 1.0, 1.0, 1.0),
-                    );
-
-                    hits.push(PrHit {
-                        rect: key_rect,
-                        url: format!("https://gspcloud.atlassian.net/browse/{}", jira_key),
-                    });
+                if github_token.is_some()
+                    || !config.github_accounts.is_empty()
+                    || !config.forges.is_empty()
+                    || config.linear
+                {
+                    fetch_started = get_time();
+                    github_fetch = Some(fetch::spawn(github_token.clone(), config.clone()));
                 } else {
-                    draw_text(
-                        line,
-                        text_x,
-                        y,
-                        font_size as f32,
-                        Color::new(1.0, 1.0, 1.0, 1.0),
-                    );
+                    github_prs.clear();
                 }
+                if let (None, Some(client_id), None) =
+                    (&github_token, config.github_client_id.clone(), &sign_in_rx)
+                {
+                    sign_in_rx = Some(oauth::start(client_id));
+                }
+            } else if config.drag_to_move
+                && !pr_hits
+                    .iter()
+                    .any(|hit| point_in_rect(vec2(mx, my), hit.rect))
+            {
+                window_drag = platform::window_position().map(|origin| WindowDrag {
+                    grab: vec2(mx, my),
+                    last: vec2(mx, my),
+                    origin,
+                });
             }
-            current_y += line_height * wrapped.len() as f32;
-
-            line_y = current_y + item_gap;
         }
-        hits
-    })
-}
 
-fn draw_loader_indicator(layout: ClockLayout) {
-    FRAME_CONTEXT.with(|ctx| {
-        let ctx = ctx.borrow();
-        let size = layout.board_grid.step() * 3.0;
-        let padding = layout.left_x - ctx.container.x;
-        let mut x = ctx.container.x + ctx.container.w - size - padding;
-        let mut y = ctx.container.y + ctx.container.h - size - padding;
-        x = snap_to_grid(ctx.container.x, x, layout.board_grid.step());
-        y = snap_to_grid(ctx.container.y, y, layout.board_grid.step());
-
-        // Soft bubble pulses around the loader.
-        let t = get_time() as f32;
-        let offsets = [vec2(-0.6, 0.08), vec2(0.45, -0.45), vec2(0.6, 0.5)];
-        let angle = t * 1.4;
-        let cos_a = angle.cos();
-        let sin_a = angle.sin();
-        for (idx, offset) in offsets.iter().enumerate() {
-            let phase = t * 0.9 + idx as f32 * 1.3;
-            let pulse = (phase.sin() * 0.5 + 0.5).clamp(0.0, 1.0);
-            let bubble_size = (layout.board_grid.cell * (0.5 + pulse * 0.5)).max(2.0);
-            let alpha = (0.15 + pulse * 0.35).min(0.5);
-            let rot_x = offset.x * cos_a - offset.y * sin_a;
-            let rot_y = offset.x * sin_a + offset.y * cos_a;
-            let bx = x + size * 0.5 + rot_x * size * 0.45 - bubble_size * 0.5;
-            let by = y + size * 0.5 + rot_y * size * 0.45 - bubble_size * 0.5;
-            draw_rectangle(
-                bx,
-                by,
-                bubble_size,
-                bubble_size,
-                Color::new(1.0, 1.0, 1.0, alpha),
-            );
-        }
-    });
-}
-
-fn glyph_pattern(ch: char) -> [&'static str; 7] {
-    match ch {
-        // Pixel Operator-inspired 5x7 glyphs.
-        '0' => [
-            ".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###.",
-        ],
-        '1' => [
-            "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
-        ],
-        '2' => [
-            ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
-        ],
-        '3' => [
-            ".###.", "#...#", "....#", "..##.", "....#", "#...#", ".###.",
-        ],
-        '4' => [
-            "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
-        ],
-        '5' => [
-            "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
-        ],
-        '6' => [
-            ".###.", "#...#", "#....", "####.", "#...#", "#...#", ".###.",
-        ],
-        '7' => [
-            "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
-        ],
-        '8' => [
-            ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
-        ],
-        '9' => [
-            ".###.", "#...#", "#...#", ".####", "....#", "#...#", ".###.",
-        ],
-        ':' => ["...", ".#.", ".#.", "...", ".#.", ".#.", "..."],
-        'A' => [
-            ".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
-        ],
-        'B' => [
-            "####.", "#...#", "#...#", "####.", "#...#", "#...#", "####.",
-        ],
-        'C' => [
-            ".###.", "#...#", "#....", "#....", "#....", "#...#", ".###.",
-        ],
-        'D' => [
-            "####.", "#...#", "#...#", "#...#", "#...#", "#...#", "####.",
-        ],
-        'E' => [
-            "#####", "#....", "#....", "####.", "#....", "#....", "#####",
-        ],
-        'F' => [
-            "#####", "#....", "#....", "####.", "#....", "#....", "#....",
-        ],
-        'G' => [
-            ".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".###.",
-        ],
-        'H' => [
-            "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
-        ],
-        'I' => [
-            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "#####",
-        ],
-        'J' => [
-            "..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##..",
-        ],
-        'K' => [
-            "#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#",
-        ],
-        'L' => [
-            "#....", "#....", "#....", "#....", "#....", "#....", "#####",
-        ],
-        'M' => [
-            "#...#", "##.##", "#.#.#", "#...#", "#...#", "#...#", "#...#",
-        ],
-        'N' => [
-            "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#", "#...#",
-        ],
-        'O' => [
-            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
-        ],
-        'P' => [
-            "####.", "#...#", "#...#", "####.", "#....", "#....", "#....",
-        ],
-        'Q' => [
-            ".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#",
-        ],
-        'R' => [
-            "####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#",
-        ],
-        'S' => [
-            ".###.", "#....", "#....", ".###.", "....#", "....#", "###..",
-        ],
-        'T' => [
-            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#..",
-        ],
-        'U' => [
-            "#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
-        ],
-        'V' => [
-            "#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#..",
-        ],
-        'W' => [
-            "#...#", "#...#", "#...#", "#.#.#", "#.#.#", "##.##", "#...#",
-        ],
-        'X' => [
-            "#...#", ".#.#.", "..#..", "..#..", "..#..", ".#.#.", "#...#",
-        ],
-        'Y' => [
-            "#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#..",
-        ],
-        'Z' => [
-            "#####", "....#", "...#.", "..#..", ".#...", "#....", "#####",
-        ],
-        ' ' => [
-            ".....", ".....", ".....", ".....", ".....", ".....", ".....",
-        ],
-        _ => [
-            ".....", ".....", ".....", ".....", ".....", ".....", ".....",
-        ],
-    }
-}
-
-#[macroquad::main(conf)]
-async fn main() {
-    let _ = dotenvy::dotenv();
-    let accent_palette = [
-        Color::new(0.09, 0.42, 0.2, 1.0),
-        Color::new(0.19, 0.63, 0.31, 1.0),
-        Color::new(0.25, 0.77, 0.39, 1.0),
-        Color::new(0.61, 0.91, 0.66, 1.0),
-        Color::new(0.18, 0.53, 0.88, 1.0),
-        Color::new(0.44, 0.67, 0.96, 1.0),
-        Color::new(0.96, 0.68, 0.24, 1.0),
-        Color::new(0.95, 0.55, 0.4, 1.0),
-        Color::new(0.78, 0.56, 0.95, 1.0),
-        Color::new(0.88, 0.45, 0.74, 1.0),
-    ];
+        let target_size = (container.w as u32, container.h as u32);
+        let target = match scene_target.take() {
+            Some(target)
+                if (
+                    target.texture.width() as u32,
+                    target.texture.height() as u32,
+                ) == target_size =>
+            {
+                target
+            }
+            _ => {
+                scene_key = None;
+                let target = render_target(target_size.0.max(1), target_size.1.max(1));
+                target.texture.set_filter(FilterMode::Nearest);
+                target
+            }
+        };
 
-    let mut accent_index = 0usize;
-    let mut hour_format = HourFormat::H24;
-    let mut time_format = TimeFormat::HhMmSs;
-    let mut github_status = ConnectionStatus::Unknown;
-    let mut github_rx: Option<mpsc::Receiver<GithubFetchResult>> = None;
-    let mut github_last_fetch = Local::now().timestamp() - 360;
-    let mut github_token = load_github_token();
-    let mut github_prs: Vec<GithubPr> = Vec::new();
-    let github_icon = load_github_icon_texture(96);
-    let pr_icon = load_pr_icon_texture(96);
+        let next_release = release::next_release(
+            releases
+                .iter()
+                .chain(config.release_calendar.iter())
+                .chain(dropped_events.iter()),
+            now,
+        );
 
-    loop {
-        let accent = accent_palette[accent_index];
-        let theme = Theme {
-            background_color: Color::new(0.06, 0.07, 0.08, 1.0),
-            inactive_color: Color::new(0.12, 0.13, 0.15, 1.0),
-            active_color: accent,
-            noise_color: accent,
-            active_alpha: 0.82,
-            active_alpha_jitter: 0.4,
+        let account_label = account_filter.map(|idx| config.github_accounts[idx].label.as_str());
+        let mut work_items = match work_panel {
+            WorkPanel::Prs => &github_prs,
+            WorkPanel::Issues => &issues,
+        }
+        .iter()
+        .filter(|item| account_label.is_none_or(|label| item.account.as_deref() == Some(label)))
+        .cloned()
+        .collect::<Vec<WorkItem>>();
+        if let (Some(days), true) = (config.stale_days, config.stale_last) {
+            // Stable, so both groups keep the fetch order.
+            work_items.sort_by_key(|item| item.is_stale(now.timestamp(), days));
+        }
+        let release_label = next_release.map(|release| release::countdown_label(release, now));
+        let next_meeting = meetings
+            .as_mut()
+            .and_then(|meetings| meetings.next(frame_start, now));
+        let (mx, my) = mouse_position();
+        // The wheel scrolls the list a whole item at a time.
+        let (_, wheel_y) = mouse_wheel();
+        if wheel_y != 0.0 && pr_area.is_some_and(|area| point_in_rect(vec2(mx, my), area)) {
+            pr_scroll = if wheel_y > 0.0 {
+                pr_scroll.saturating_sub(1)
+            } else {
+                pr_scroll + 1
+            };
+        }
+        pr_scroll = pr_scroll.min(work_items.len().saturating_sub(1));
+        let next_key = SceneKey {
+            container,
+            theme,
+            year: year_string.clone(),
+            date: date_string.clone(),
+            weather: weather_label.clone(),
+            time: time_string.clone(),
+            am_pm: am_pm.clone(),
+            colon_lit,
+            pulse,
+            github_status,
+            github_prs: github_prs.clone(),
+            issues: issues.clone(),
+            work_panel,
+            account_filter,
+            pr_scroll,
+            dashboard: active_dashboard,
+            ci_statuses: ci_statuses.clone(),
+            review_decisions: review_decisions.clone(),
+            conflicts: conflicts.clone(),
+            review_load: review_load.clone(),
+            release_label: release_label.clone(),
+            // A label ticking every second would defeat the e-ink profile.
+            updated_label: last_updated
+                .filter(|_| !config.eink)
+                .and_then(|at| updated_label(now.timestamp() - at, config.updated_label)),
+            timezone: config.show_timezone.then(|| format_timezone(&frame_clock)),
+            widget_lines: [
+                // The glyphs have no em dash.
+                diag::crashed().then(|| "CRASHED - REPORT SAVED".to_string()),
+                watched_file
+                    .as_mut()
+                    .and_then(|file| file.line(frame_start))
+                    .map(str::to_uppercase),
+                command_widget
+                    .as_mut()
+                    .and_then(|widget| widget.line(frame_start))
+                    .map(str::to_uppercase),
+                sun_times
+                    .filter(|_| config.sun_times)
+                    .map(|times| sun::sun_label(times, hour_format)),
+                pomodoro.label(frame_start),
+                team_sync
+                    .as_mut()
+                    .map(|sync| sync.update(frame_start, pomodoro.focusing(frame_start)))
+                    .filter(|focusing| *focusing > 0)
+                    .map(|focusing| format!("TEAM: {} FOCUSING", focusing)),
+                last_push
+                    .filter(|_| config.last_commit)
+                    .map(|at| last_commit_label(now.timestamp() - at)),
+            ]
+            .into_iter()
+            .flatten()
+            .map(WidgetLine::from)
+            .chain(next_meeting.as_ref().map(|meeting| WidgetLine {
+                text: meetings::meeting_label(meeting, now, hour_format),
+                // E-ink has no amber.
+                warning: !config.eink && meetings::starts_soon(meeting, now),
+            }))
+            .collect(),
+            status_label: rate_limits
+                .holding(now.timestamp())
+                .map(|limit| format!("LIMIT {}M", (limit.reset - now.timestamp()) / 60 + 1))
+                .or_else(|| match github_status {
+                    ConnectionStatus::TimedOut => Some("TIMEOUT".to_string()),
+                    ConnectionStatus::Connected => None,
+                    _ => github_error.as_ref().map(|error| error.label().to_string()),
+                }),
+            unread,
+            notifications: notifications_open.then(|| notifications.clone()),
+            sign_in_code: sign_in.as_ref().map(|(code, _)| code.clone()),
+            share_link: share.as_ref().map(|(url, _)| url.clone()),
+            hovered_hit: pr_hits
+                .iter()
+                .position(|hit| point_in_rect(vec2(mx, my), hit.rect)),
+            power_save,
+            compact,
+            page,
+            stats: if page == Page::Stats {
+                Stats::collect(
+                    &github_prs,
+                    review_count,
+                    &ci_statuses,
+                    &conflicts,
+                    config.stale_days,
+                    now.timestamp(),
+                )
+                .lines()
+                .into_iter()
+                .chain(last_push.map(|at| last_commit_label(now.timestamp() - at)))
+                .collect()
+            } else {
+                Vec::new()
+            },
+            heatmap: heatmap_cells.clone(),
+            workflows: workflows.clone(),
         };
+        #[cfg(feature = "network")]
+        if let Some(exporter) = exporter.as_mut() {
+            exporter.tick(frame_start, || {
+                board_json(&next_key, &work_items, config.eink && now.minute() == 0)
+            });
+        }
+        let effects = animations.frame(frame_start);
+        // The loader and one-shot effects animate continuously, so keep
+        // rendering while any of them runs.
+        let dirty = scene_key.as_ref() != Some(&next_key)
+            || (github_fetch.is_some() && !config.eink)
+            || !effects.is_empty();
+
+        if dirty && page != Page::Clock {
+            let mut camera = Camera2D::from_display_rect(container);
+            camera.render_target = Some(target.clone());
+            set_camera(&camera);
+            match page {
+                Page::Calendar => draw_calendar(now.date_naive(), config.first_day),
+                _ => draw_stats(&next_key.stats),
+            }
+            draw_page_dots(config.pages.len(), pages::index(&config.pages, page));
+            set_default_camera();
+            pr_area = None;
+            (pr_hits, pr_tooltips) = Default::default();
+            scene_key = Some(next_key);
+        } else if dirty {
+            let mut camera = Camera2D::from_display_rect(container);
+            camera.render_target = Some(target.clone());
+            set_camera(&camera);
+
+            let layout = draw_clock(
+                &year_string,
+                &date_line,
+                &time_string,
+                TimeMarks {
+                    am_pm: am_pm.as_deref().map(|suffix| match config.am_pm_style {
+                        AmPmStyle::Text => AmPmMark::Text(suffix),
+                        AmPmStyle::Dot => AmPmMark::Dot { pm: suffix == "PM" },
+                    }),
+                    colon: colon_lit,
+                    pulse,
+                    subsecond: shown_format.subsecond(),
+                },
+                now.minute() as i32,
+                !calm && dashboard.effects.unwrap_or(true),
+                if compact {
+                    layout::compact(container)
+                } else {
+                    layout::split(
+                        container,
+                        dashboard
+                            .pr_list_position
+                            .unwrap_or(config.pr_list_position),
+                        config.orientation,
+                        if show_prs {
+                            work_items.len() + usize::from(issues_supported)
+                        } else {
+                            0
+                        },
+                    )
+                },
+            );
 
-        let container = Rect::new(0.0, 0.0, screen_width(), screen_height());
-        update_context(theme, container);
-
-        let now = Local::now();
-        let time_string = format_time(hour_format, time_format);
-        let am_pm = am_pm_suffix(hour_format);
-        let date_string = format_day_month();
-        let year_string = format_year();
+            pr_area = (!compact).then_some(layout.pr_area);
+            // A build without the network feature has nothing to connect to.
+            if button_shown {
+                draw_github_button(github_status, &github_icon, button_rect);
+            }
+            if let Some(rect) = badge_rect {
+                draw_notification_badge(unread, rect);
+            }
+            if dashboard.show_team.unwrap_or(true) && !compact {
+                draw_review_load(&review_load, layout);
+            }
 
-        if now.timestamp() - github_last_fetch >= 300 && github_rx.is_none() {
-            github_last_fetch = now.timestamp();
-            if let Some(token) = github_token.clone() {
-                github_status = ConnectionStatus::Unknown;
-                github_rx = Some(spawn_github_fetch(token));
+            (pr_hits, pr_tooltips) = if compact {
+                Default::default()
+            } else if let Some((code, url)) = &sign_in {
+                (vec![draw_sign_in_code(code, url, layout)], Vec::new())
+            } else if let Some((url, code)) = &share {
+                (vec![draw_share_code(code, url, layout)], Vec::new())
+            } else if notifications_open {
+                draw_pr_list(
+                    PrListView {
+                        items: &notifications,
+                        heading: None,
+                        icon: None,
+                        avatars: &no_avatars,
+                        scroll: 0,
+                        now: now.timestamp(),
+                        stale_days: None,
+                        jira: &key_links,
+                        change: None,
+                    },
+                    &[],
+                    &[],
+                    &[],
+                    layout,
+                    config.text_overflow,
+                )
+            } else if !show_prs || (work_items.is_empty() && !issues_supported) {
+                Default::default()
             } else {
-                github_status = ConnectionStatus::Disconnected;
-                github_prs.clear();
-            }
-        }
+                draw_pr_list(
+                    PrListView {
+                        items: &work_items,
+                        heading: issues_supported.then(|| work_panel.heading()),
+                        icon: match work_panel {
+                            WorkPanel::Prs => Some(&pr_icon),
+                            WorkPanel::Issues => Some(&issue_icon),
+                        },
+                        avatars: match work_panel {
+                            WorkPanel::Prs => &avatar_textures,
+                            WorkPanel::Issues => &no_avatars,
+                        },
+                        scroll: pr_scroll,
+                        now: now.timestamp(),
+                        stale_days: config.stale_days,
+                        jira: &key_links,
+                        change: match work_panel {
+                            WorkPanel::Prs => effects
+                                .iter()
+                                .find(|(effect, _)| *effect == Effect::ListChange)
+                                .map(|(_, progress)| (&list_change, *progress)),
+                            WorkPanel::Issues => None,
+                        },
+                    },
+                    &ci_statuses,
+                    &review_decisions,
+                    &conflicts,
+                    layout,
+                    config.text_overflow,
+                )
+            };
 
-        if let Some(rx) = &github_rx {
-            if let Ok(result) = rx.try_recv() {
-                github_status = if result.connected {
-                    ConnectionStatus::Connected
-                } else {
-                    ConnectionStatus::Disconnected
-                };
-                github_prs = result.prs;
-                github_rx = None;
+            // The rate-limit countdown or a timeout takes the release
+            // countdown's spot while polls are on hold.
+            if compact {
+                // Nothing but the time.
+            } else if let Some(label) = next_key.status_label.as_ref() {
+                draw_button_label(label, None, button_rect, layout);
+            } else if let (Some(release), Some(label)) =
+                (next_release, next_key.release_label.as_ref())
+            {
+                let url = release.url.clone().or_else(|| {
+                    config
+                        .release_repo
+                        .as_ref()
+                        .map(|repo| format!("https://github.com/{}/milestones", repo))
+                });
+                if let Some(hit) = draw_button_label(label, url.as_deref(), button_rect, layout) {
+                    pr_hits.push(hit);
+                }
             }
-        }
 
-        let layout = draw_clock(
-            &year_string,
-            &date_string,
-            &time_string,
-            am_pm.as_deref(),
-            now.minute() as i32,
-        );
+            let status_line = [&next_key.timezone, &next_key.updated_label]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join("  ");
+            if !compact {
+                if !status_line.is_empty() {
+                    draw_status_line(&status_line);
+                }
+                if !next_key.heatmap.is_empty() {
+                    draw_heatmap(&next_key.heatmap, layout);
+                }
+                if !next_key.workflows.is_empty() {
+                    pr_hits.extend(draw_workflows(&next_key.workflows));
+                }
+                draw_widget_lines(&next_key.widget_lines);
+            }
 
-        let button_grid = grid_from_height(42.0, 0.25);
-        let button_rect = github_button_rect(container, button_grid);
-        draw_github_button(github_status, github_icon.as_ref(), button_rect);
+            if github_fetch.is_some() && !config.eink && !compact {
+                draw_loader_indicator(layout);
+            }
 
-        if is_mouse_button_pressed(MouseButton::Left) {
-            let (mx, my) = mouse_position();
-            if point_in_rect(vec2(mx, my), button_rect) {
-                github_token = load_github_token();
-                if let Some(token) = github_token.clone() {
-                    github_status = ConnectionStatus::Unknown;
-                    github_rx = Some(spawn_github_fetch(token));
-                } else {
-                    github_status = ConnectionStatus::Disconnected;
-                    github_prs.clear();
+            for (effect, progress) in effects.iter() {
+                match effect {
+                    Effect::HourWave => draw_hour_wave(layout.board_grid, *progress),
+                    Effect::Fireworks => draw_fireworks(layout.board_grid, *progress),
+                    // Drawn by the PR list itself.
+                    Effect::ListChange => {}
                 }
             }
+            if config.pages.len() > 1 && !compact {
+                draw_page_dots(config.pages.len(), 0);
+            }
+
+            set_default_camera();
+            scene_key = Some(next_key);
         }
 
-        let pr_hits = if github_prs.is_empty() {
-            Vec::new()
+        draw_texture_ex(
+            &target.texture,
+            0.0,
+            0.0,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(container.w, container.h)),
+                flip_y: true,
+                ..Default::default()
+            },
+        );
+        scene_target = Some(target);
+        let point = Vec2::from(mouse_position());
+        let hovered = if context_menu.is_some() {
+            None
+        } else if button_shown && point_in_rect(point, button_rect) {
+            let last_fetch = last_updated
+                .and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+                .map(|at| {
+                    format!(
+                        "Last fetch {}, {} ago",
+                        short_time(at.with_timezone(now.offset()), hour_format),
+                        age_label(now.timestamp() - at.timestamp())
+                    )
+                });
+            Some(Tooltip {
+                rect: button_rect,
+                lines: [
+                    Some(a11y::connection_label(github_status)),
+                    github_login
+                        .as_ref()
+                        .filter(|_| github_status != ConnectionStatus::Disconnected)
+                        .map(|login| format!("Signed in as {}", login)),
+                    last_fetch,
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            })
         } else {
-            draw_pr_list(&github_prs, pr_icon.as_ref(), layout)
+            pr_tooltips
+                .iter()
+                .find(|tooltip| point_in_rect(point, tooltip.rect))
+                .cloned()
         };
-
-        if github_rx.is_some() {
-            draw_loader_indicator(layout);
+        match (&hovered, tooltip_hover) {
+            (Some(tooltip), Some((rect, since))) if rect == tooltip.rect => {
+                if get_time() - since >= TOOLTIP_DELAY_SECS {
+                    draw_tooltip(tooltip);
+                }
+            }
+            (Some(tooltip), _) => tooltip_hover = Some((tooltip.rect, get_time())),
+            (None, _) => tooltip_hover = None,
+        }
+        menu_hits = context_menu
+            .map(|origin| draw_context_menu(origin, MENU_ACTIONS))
+            .unwrap_or_default();
+        if dirty && config.frame_history > 0 {
+            diag::capture(get_screen_data(), config.frame_history);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if recorder
+            .as_mut()
+            .is_some_and(|recorder| recorder.tick(frame_start, get_screen_data))
+        {
+            if let Some(recorder) = recorder.take() {
+                recorder.finish();
+            }
         }
 
-        if is_mouse_button_pressed(MouseButton::Left) {
+        if clicked {
             let (mx, my) = mouse_position();
             let point = vec2(mx, my);
             for hit in pr_hits.iter() {
@@ -1370,23 +1434,210 @@ async fn main() {
             }
         }
 
+        menu_bar.update(&steady_time, github_prs.len());
+        if config.window_title {
+            let title = format_window_title(
+                &frame_clock,
+                hour_format,
+                github_prs.len(),
+                release_label.as_deref(),
+            );
+            if title != window_title {
+                platform::set_window_title(&title);
+                window_title = title;
+            }
+        }
+        announcer.update(a11y::Snapshot {
+            time: match am_pm.as_deref() {
+                Some(suffix) => format!("{} {}", steady_time, suffix),
+                None => steady_time.clone(),
+            },
+            date: now.format("%A, %-d %B %Y").to_string(),
+            announcement: a11y::github_announcement(
+                github_status,
+                github_error.as_ref(),
+                &github_prs,
+            ),
+        });
+
         if is_key_pressed(KeyCode::F) {
-            time_format = match time_format {
-                TimeFormat::HhMmSs => TimeFormat::HhMm,
-                TimeFormat::HhMm => TimeFormat::MmSs,
-                TimeFormat::MmSs => TimeFormat::IsoTime,
-                TimeFormat::IsoTime => TimeFormat::HhMmSs,
+            time_format = time_format.next(config.time_format.as_ref());
+        }
+        if is_key_pressed(KeyCode::H) || menu_action == Some(MenuAction::ToggleHours) {
+            hour_format = hour_format.toggled();
+            prefs::save(&prefs::Preferences {
+                hour_format: Some(hour_format),
+            });
+        }
+        if is_key_pressed(KeyCode::C) {
+            accent_index = (accent_index + 1) % ACCENT_PALETTE.len();
+        }
+        if is_key_pressed(KeyCode::B) {
+            power.cycle_mode();
+        }
+        if is_key_pressed(KeyCode::I) && issues_supported {
+            work_panel = match work_panel {
+                WorkPanel::Prs => WorkPanel::Issues,
+                WorkPanel::Issues => WorkPanel::Prs,
             };
         }
-        if is_key_pressed(KeyCode::H) {
-            hour_format = if hour_format == HourFormat::H24 {
-                HourFormat::H12
+        if is_key_pressed(KeyCode::A) && config.github_accounts.len() > 1 {
+            account_filter = match account_filter {
+                None => Some(0),
+                Some(idx) if idx + 1 < config.github_accounts.len() => Some(idx + 1),
+                Some(_) => None,
+            };
+        }
+        if is_key_pressed(KeyCode::D) {
+            config.include_drafts = !config.include_drafts;
+            refresh_requested = true;
+        }
+        let number_keys = [
+            KeyCode::Key1,
+            KeyCode::Key2,
+            KeyCode::Key3,
+            KeyCode::Key4,
+            KeyCode::Key5,
+            KeyCode::Key6,
+            KeyCode::Key7,
+            KeyCode::Key8,
+            KeyCode::Key9,
+        ];
+        if let Some(idx) = number_keys.iter().position(|key| is_key_pressed(*key)) {
+            if idx < config.dashboards.len() {
+                active_dashboard = idx;
+            }
+        }
+        if is_key_pressed(KeyCode::R) || menu_action == Some(MenuAction::Refresh) {
+            refresh_requested = true;
+        }
+        if is_key_pressed(KeyCode::P) {
+            pomodoro.toggle(get_time());
+        }
+        let mut page_step = 0;
+        if is_key_pressed(KeyCode::Tab) {
+            page_step = if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                -1
             } else {
-                HourFormat::H24
+                1
             };
         }
-        if is_key_pressed(KeyCode::C) {
-            accent_index = (accent_index + 1) % accent_palette.len();
+        if is_key_pressed(KeyCode::Right) {
+            page_step = 1;
+        }
+        if is_key_pressed(KeyCode::Left) {
+            page_step = -1;
+        }
+        for touch in touches() {
+            match touch.phase {
+                TouchPhase::Started => swipe_start = Some(touch.position),
+                TouchPhase::Ended => {
+                    let Some(start) = swipe_start.take() else {
+                        continue;
+                    };
+                    let offset = touch.position - start;
+                    if offset.x.abs() >= SWIPE_MIN_PX && offset.x.abs() > offset.y.abs() * 2.0 {
+                        // Swiping left brings in the next page.
+                        page_step = if offset.x < 0.0 { 1 } else { -1 };
+                    }
+                }
+                TouchPhase::Cancelled => swipe_start = None,
+                _ => {}
+            }
+        }
+        if page_step != 0 && !compact {
+            page = pages::cycle(&config.pages, page, page_step);
+        }
+        if is_key_pressed(KeyCode::Z) {
+            let (width, height) = match compact_restore.take() {
+                Some(size) => size,
+                None => {
+                    page = Page::Clock;
+                    compact_restore = Some((screen_width(), screen_height()));
+                    compact_window_size(&time_string, am_pm.is_some() || pulse.is_some())
+                }
+            };
+            request_new_screen_size(width, height);
+        }
+        if is_key_pressed(KeyCode::Q) {
+            share = match (&share, &config.share_url, pomodoro.remaining(get_time())) {
+                (None, Some(base), Some(remaining)) => {
+                    share_code(base, now.timestamp() + remaining.ceil() as i64)
+                }
+                _ => None,
+            };
+        }
+        if !pomodoro.focusing(get_time()) {
+            share = None;
+        }
+        // Written when something changes rather than every frame.
+        let session_key = (
+            pomodoro.focusing(get_time()),
+            active_dashboard,
+            work_panel,
+            notifications_open,
+            share.is_some(),
+        );
+        if config.restore_session && saved_session != Some(session_key) {
+            saved_session = Some(session_key);
+            session::save(&session::Session {
+                focus_until: pomodoro
+                    .remaining(get_time())
+                    .map(|secs| now.timestamp() + secs.ceil() as i64),
+                dashboard: active_dashboard,
+                issues: work_panel == WorkPanel::Issues,
+                notifications_open,
+                share_open: share.is_some(),
+            });
+        }
+        for file in get_dropped_files() {
+            let name = file
+                .path
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let Some(bytes) = file
+                .bytes
+                .or_else(|| std::fs::read(file.path.as_ref()?).ok())
+            else {
+                log!("Could not read dropped file {}", name);
+                continue;
+            };
+            match import::import(&name, &bytes, *now.offset()) {
+                Some(import::Import::Events(events)) => dropped_events.extend(events),
+                Some(import::Import::Theme(theme)) => installed_theme = theme,
+                None => {}
+            }
+        }
+        match menu_action {
+            Some(MenuAction::CopyTime) => miniquad::window::clipboard_set(&steady_time),
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(MenuAction::Settings) => platform::open_config_dir(),
+            Some(MenuAction::Quit) => miniquad::window::order_quit(),
+            _ => {}
+        }
+        if is_key_pressed(KeyCode::F12) {
+            match diag::dump() {
+                Some(dir) => log!("Saved the last frames and log to {}", dir.display()),
+                None => log!("Could not save a report"),
+            }
+        }
+
+        let elapsed = get_time() - frame_start;
+        #[cfg(feature = "network")]
+        if let Some(server) = metrics.as_mut() {
+            server.metrics.frame(elapsed);
+            server.tick(get_time());
+        }
+        // Power save and e-ink drop to one frame per second, woken on the
+        // wall-clock tick.
+        if calm {
+            platform::sleep(until_next_second(clock.now()));
+        } else {
+            let frame_budget = 1.0 / config.max_fps as f64;
+            if elapsed < frame_budget {
+                platform::sleep(Duration::from_secs_f64(frame_budget - elapsed));
+            }
         }
 
         next_frame().await;
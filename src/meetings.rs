@@ -0,0 +1,205 @@
+// The next meeting from the `.ics` calendars in `CHRONO_CALENDARS`, URLs or
+// files, shown as `NEXT: STANDUP 10:30 IN 12M` above the bottom-left widget
+// lines. Calendars are polled off the render thread like the weather; a
+// calendar that fails to load keeps its last events.
+#![cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+
+use crate::clock::{short_time, HourFormat};
+use crate::ics;
+use crate::platform;
+use crate::release::Release;
+use chrono::{DateTime, FixedOffset};
+use std::sync::mpsc;
+
+// How far ahead each poll looks. Polls are minutes apart, so a week is
+// plenty.
+const LOOKAHEAD_DAYS: i64 = 7;
+// The label turns to the warning color this close to the start.
+const WARNING_SECS: i64 = 5 * 60;
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn download(url: &str) -> Option<String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(8))
+        .build();
+    // Private calendar links carry a token, so the URL is not logged.
+    match agent.get(url).set("User-Agent", "commit-clock").call() {
+        Ok(response) => response.into_string().ok(),
+        Err(ureq::Error::Status(code, _)) => {
+            log!("Calendar fetch failed: HTTP {}", code);
+            None
+        }
+        Err(ureq::Error::Transport(e)) => {
+            log!("Calendar fetch failed: {}", e.kind());
+            None
+        }
+    }
+}
+
+#[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+fn download(_url: &str) -> Option<String> {
+    None
+}
+
+fn load(source: &str) -> Option<String> {
+    if source.starts_with("https://") || source.starts_with("http://") {
+        return download(source);
+    }
+    match std::fs::read_to_string(source) {
+        Ok(text) => Some(text),
+        Err(e) => {
+            log!("Could not read calendar {}: {}", source, e);
+            None
+        }
+    }
+}
+
+// The first meeting that has not started yet.
+pub fn next_meeting(meetings: &[Release], now: DateTime<FixedOffset>) -> Option<&Release> {
+    crate::release::next_release(meetings, now)
+}
+
+// `NEXT: STANDUP 10:30 IN 12M`, with the weekday in front of the time when
+// it is not today.
+pub fn meeting_label(
+    meeting: &Release,
+    now: DateTime<FixedOffset>,
+    hour_format: HourFormat,
+) -> String {
+    let title = meeting
+        .title
+        .to_uppercase()
+        .chars()
+        .take(16)
+        .collect::<String>();
+    let remaining = meeting.due - now;
+    let when = if meeting.due.date_naive() == now.date_naive() {
+        short_time(meeting.due, hour_format)
+    } else {
+        format!(
+            "{} {}",
+            meeting.due.format("%a").to_string().to_uppercase(),
+            short_time(meeting.due, hour_format)
+        )
+    };
+    let countdown = if remaining.num_hours() >= 24 {
+        String::new()
+    } else if remaining.num_hours() >= 1 {
+        format!(" IN {}H", remaining.num_hours())
+    } else {
+        // Rounded up, so the last minute reads `IN 1M`.
+        format!(" IN {}M", (remaining.num_seconds() + 59) / 60)
+    };
+    format!("NEXT: {} {}{}", title.trim(), when, countdown)
+}
+
+pub fn starts_soon(meeting: &Release, now: DateTime<FixedOffset>) -> bool {
+    (meeting.due - now).num_seconds() <= WARNING_SECS
+}
+
+pub struct Meetings {
+    sources: Vec<String>,
+    interval: f64,
+    next_poll: f64,
+    rx: Option<mpsc::Receiver<Vec<Option<Vec<Release>>>>>,
+    // The last good read of each source.
+    loaded: Vec<Vec<Release>>,
+}
+
+impl Meetings {
+    pub fn new(sources: Vec<String>, interval_secs: f64) -> Self {
+        Meetings {
+            loaded: vec![Vec::new(); sources.len()],
+            sources,
+            interval: interval_secs,
+            next_poll: 0.0,
+            rx: None,
+        }
+    }
+
+    pub fn next(&mut self, now: f64, time: DateTime<FixedOffset>) -> Option<Release> {
+        if let Some(rx) = &self.rx {
+            match rx.try_recv() {
+                Ok(results) => {
+                    for (loaded, result) in self.loaded.iter_mut().zip(results) {
+                        if let Some(meetings) = result {
+                            *loaded = meetings;
+                        }
+                    }
+                    self.rx = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.rx = None,
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+        if self.rx.is_none() && now >= self.next_poll {
+            self.next_poll = now + self.interval;
+            let sources = self.sources.clone();
+            self.rx = Some(platform::background(move || {
+                sources
+                    .iter()
+                    .map(|source| {
+                        load(source).map(|text| ics::parse_meetings(&text, time, LOOKAHEAD_DAYS))
+                    })
+                    .collect()
+            }));
+        }
+        let meetings = self.loaded.concat();
+        next_meeting(&meetings, time).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    fn meeting(title: &str, due: &str) -> Release {
+        Release {
+            title: title.to_string(),
+            due: at(due),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn labels_count_down_and_warn() {
+        let now = at("2026-10-16T10:18:00+02:00");
+        let standup = meeting("Standup", "2026-10-16T10:30:00+02:00");
+        assert_eq!(
+            meeting_label(&standup, now, HourFormat::H24),
+            "NEXT: STANDUP 10:30 IN 12M"
+        );
+        assert!(!starts_soon(&standup, now));
+        assert!(starts_soon(&standup, at("2026-10-16T10:26:30+02:00")));
+        let review = meeting("Sprint review", "2026-10-19T15:00:00+02:00");
+        assert_eq!(
+            meeting_label(&review, now, HourFormat::H12),
+            "NEXT: SPRINT REVIEW MON 03:00PM"
+        );
+        let sync = meeting("Sync", "2026-10-16T13:00:00+02:00");
+        assert_eq!(
+            meeting_label(&sync, now, HourFormat::H24),
+            "NEXT: SYNC 13:00 IN 2H"
+        );
+        for ch in meeting_label(&review, now, HourFormat::H12).chars() {
+            assert!(crate::glyphs::has_glyph(ch), "no glyph for {:?}", ch);
+        }
+    }
+
+    #[test]
+    fn picks_the_first_meeting_not_started() {
+        let meetings = [
+            meeting("Sync", "2026-10-16T13:00:00+02:00"),
+            meeting("Standup", "2026-10-16T10:30:00+02:00"),
+        ];
+        let now = at("2026-10-16T10:31:00+02:00");
+        assert_eq!(next_meeting(&meetings, now).unwrap().title, "Sync");
+    }
+}
@@ -0,0 +1,112 @@
+// Mirrors the clock (and open PR count) into the macOS status bar. Clicking
+// the item brings the full pixel window to the front.
+
+#[cfg(target_os = "macos")]
+mod status_item {
+    use macroquad::miniquad;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel, YES};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    type Id = *mut Object;
+
+    const NS_UTF8_STRING_ENCODING: usize = 4;
+    const NS_VARIABLE_STATUS_ITEM_LENGTH: f64 = -1.0;
+
+    extern "C" fn open_window(_this: &Object, _cmd: Sel, _sender: Id) {
+        unsafe {
+            let app: Id = msg_send![class!(NSApplication), sharedApplication];
+            let () = msg_send![app, activateIgnoringOtherApps: YES];
+            let view = miniquad::window::apple_view();
+            let window: Id = msg_send![view, window];
+            let () = msg_send![window, makeKeyAndOrderFront: std::ptr::null_mut::<Object>()];
+        }
+    }
+
+    fn target_class() -> &'static Class {
+        if let Some(class) = Class::get("ChronoStatusItemTarget") {
+            return class;
+        }
+        let mut decl = ClassDecl::new("ChronoStatusItemTarget", class!(NSObject))
+            .expect("ChronoStatusItemTarget is declared once");
+        unsafe {
+            decl.add_method(sel!(open:), open_window as extern "C" fn(&Object, Sel, Id));
+        }
+        decl.register()
+    }
+
+    pub struct StatusItem {
+        item: Id,
+    }
+
+    impl StatusItem {
+        pub fn new() -> Self {
+            unsafe {
+                let bar: Id = msg_send![class!(NSStatusBar), systemStatusBar];
+                let item: Id = msg_send![bar, statusItemWithLength: NS_VARIABLE_STATUS_ITEM_LENGTH];
+                let _: Id = msg_send![item, retain];
+                // The target lives as long as the app; status items only hold it weakly.
+                let target: Id = msg_send![target_class(), new];
+                let button: Id = msg_send![item, button];
+                let () = msg_send![button, setTarget: target];
+                let () = msg_send![button, setAction: sel!(open:)];
+                StatusItem { item }
+            }
+        }
+
+        pub fn set_title(&self, title: &str) {
+            unsafe {
+                let string: Id = msg_send![class!(NSString), alloc];
+                let string: Id = msg_send![string,
+                    initWithBytes: title.as_ptr()
+                    length: title.len()
+                    encoding: NS_UTF8_STRING_ENCODING];
+                let button: Id = msg_send![self.item, button];
+                let () = msg_send![button, setTitle: string];
+                let () = msg_send![string, release];
+            }
+        }
+    }
+}
+
+pub struct MenuBar {
+    #[cfg(target_os = "macos")]
+    item: Option<status_item::StatusItem>,
+    title: String,
+}
+
+impl MenuBar {
+    pub fn new(enabled: bool) -> Self {
+        #[cfg(target_os = "macos")]
+        let item = enabled.then(status_item::StatusItem::new);
+
+        #[cfg(not(target_os = "macos"))]
+        if enabled {
+            log!("Menu bar mode is only available on macOS");
+        }
+
+        MenuBar {
+            #[cfg(target_os = "macos")]
+            item,
+            title: String::new(),
+        }
+    }
+
+    pub fn update(&mut self, time: &str, pr_count: usize) {
+        let title = if pr_count > 0 {
+            format!("{} · {} PR", time, pr_count)
+        } else {
+            time.to_string()
+        };
+        if title == self.title {
+            return;
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(item) = &self.item {
+            item.set_title(&title);
+        }
+
+        self.title = title;
+    }
+}
@@ -0,0 +1,285 @@
+// Prometheus metrics for wall-mounted instances, served as plain text on
+// `CHRONO_METRICS_ADDR`. Like the board export, requests are answered from
+// the frame loop, so the counters need no locking.
+use crate::provider::RateLimits;
+use std::fmt::Write as _;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+const FRAME_BUCKETS: [f64; 8] = [0.002, 0.005, 0.01, 0.0167, 0.033, 0.05, 0.1, 0.25];
+const FETCH_BUCKETS: [f64; 8] = [0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+// A scraper that has not sent its request headers by then is dropped.
+const REQUEST_TIMEOUT_SECS: f64 = 5.0;
+const MAX_REQUEST_BYTES: usize = 8192;
+
+struct Histogram {
+    bounds: &'static [f64],
+    // Cumulative, as Prometheus expects.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Histogram {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn write(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter()) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count);
+    }
+}
+
+pub struct Metrics {
+    frames: Histogram,
+    fetches: Histogram,
+    // Failed fetches by reason, in the order first seen.
+    failures: Vec<(&'static str, u64)>,
+    rate_limits: RateLimits,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            frames: Histogram::new(&FRAME_BUCKETS),
+            fetches: Histogram::new(&FETCH_BUCKETS),
+            failures: Vec::new(),
+            rate_limits: RateLimits::default(),
+        }
+    }
+}
+
+impl Metrics {
+    // Time spent on one frame, before sleeping to the frame budget.
+    pub fn frame(&mut self, secs: f64) {
+        self.frames.observe(secs);
+    }
+
+    pub fn fetch(&mut self, secs: f64, failure: Option<&'static str>) {
+        self.fetches.observe(secs);
+        let Some(reason) = failure else {
+            return;
+        };
+        match self.failures.iter_mut().find(|(known, _)| *known == reason) {
+            Some((_, count)) => *count += 1,
+            None => self.failures.push((reason, 1)),
+        }
+    }
+
+    pub fn set_rate_limits(&mut self, rate_limits: RateLimits) {
+        self.rate_limits = rate_limits;
+    }
+
+    // The text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.frames.write(
+            &mut out,
+            "chrono_frame_seconds",
+            "Time spent drawing a frame.",
+        );
+        self.fetches.write(
+            &mut out,
+            "chrono_fetch_seconds",
+            "Duration of provider fetches, failed ones included.",
+        );
+        out.push_str("# HELP chrono_fetch_failures_total Fetches that failed, by reason.\n");
+        out.push_str("# TYPE chrono_fetch_failures_total counter\n");
+        for (reason, count) in &self.failures {
+            let _ = writeln!(
+                out,
+                "chrono_fetch_failures_total{{reason=\"{}\"}} {}",
+                reason, count
+            );
+        }
+        if self.rate_limits.iter().next().is_some() {
+            out.push_str(
+                "# HELP chrono_rate_limit_remaining API requests left in the quota, by resource.\n",
+            );
+            out.push_str("# TYPE chrono_rate_limit_remaining gauge\n");
+            for limit in self.rate_limits.iter() {
+                let _ = writeln!(
+                    out,
+                    "chrono_rate_limit_remaining{{resource=\"{}\"}} {}",
+                    limit.resource.name(),
+                    limit.remaining
+                );
+            }
+            out.push_str(
+                "# HELP chrono_rate_limit_reset_timestamp_seconds When the quota resets.\n",
+            );
+            out.push_str("# TYPE chrono_rate_limit_reset_timestamp_seconds gauge\n");
+            for limit in self.rate_limits.iter() {
+                let _ = writeln!(
+                    out,
+                    "chrono_rate_limit_reset_timestamp_seconds{{resource=\"{}\"}} {}",
+                    limit.resource.name(),
+                    limit.reset
+                );
+            }
+        }
+        out
+    }
+}
+
+// The whole response to a request whose headers have arrived.
+fn respond(request: &str, metrics: &Metrics) -> String {
+    let mut parts = request.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics" | "/")) => ("200 OK", metrics.render()),
+        (Some("GET"), _) => ("404 Not Found", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".to_string()),
+    };
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+struct Client {
+    stream: TcpStream,
+    request: Vec<u8>,
+    connected_at: f64,
+}
+
+pub struct MetricsServer {
+    listener: TcpListener,
+    clients: Vec<Client>,
+    pub metrics: Metrics,
+}
+
+impl MetricsServer {
+    pub fn bind(addr: &str) -> Option<Self> {
+        let listener = TcpListener::bind(addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|e| log!("Metrics unavailable on {}: {}", addr, e))
+            .ok()?;
+        Some(MetricsServer {
+            listener,
+            clients: Vec::new(),
+            metrics: Metrics::default(),
+        })
+    }
+
+    // Accepts scrapers and answers the ones whose request is complete.
+    // `now` is `get_time()` seconds.
+    pub fn tick(&mut self, now: f64) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                self.clients.push(Client {
+                    stream,
+                    request: Vec::new(),
+                    connected_at: now,
+                });
+            }
+        }
+        let metrics = &self.metrics;
+        self.clients.retain_mut(|client| {
+            let mut buf = [0u8; 1024];
+            loop {
+                match client.stream.read(&mut buf) {
+                    Ok(0) => return false,
+                    Ok(len) => client.request.extend_from_slice(&buf[..len]),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(_) => return false,
+                }
+            }
+            if client.request.windows(4).any(|end| end == b"\r\n\r\n") {
+                let request = String::from_utf8_lossy(&client.request);
+                let _ = client
+                    .stream
+                    .write_all(respond(&request, metrics).as_bytes());
+                return false;
+            }
+            now - client.connected_at < REQUEST_TIMEOUT_SECS
+                && client.request.len() < MAX_REQUEST_BYTES
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{RateLimit, RateResource};
+
+    #[test]
+    fn histograms_are_cumulative() {
+        let mut metrics = Metrics::default();
+        metrics.frame(0.004);
+        metrics.frame(0.02);
+        let text = metrics.render();
+        assert!(text.contains("chrono_frame_seconds_bucket{le=\"0.002\"} 0\n"));
+        assert!(text.contains("chrono_frame_seconds_bucket{le=\"0.005\"} 1\n"));
+        assert!(text.contains("chrono_frame_seconds_bucket{le=\"0.033\"} 2\n"));
+        assert!(text.contains("chrono_frame_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("chrono_frame_seconds_count 2\n"));
+        // Nothing is known about the quota before the first fetch.
+        assert!(!text.contains("chrono_rate_limit_remaining"));
+    }
+
+    #[test]
+    fn counts_failures_by_reason() {
+        let mut metrics = Metrics::default();
+        metrics.fetch(1.2, None);
+        metrics.fetch(30.0, Some("timeout"));
+        metrics.fetch(0.3, Some("network"));
+        metrics.fetch(0.4, Some("network"));
+        let mut limits = RateLimits::default();
+        limits.record(RateLimit {
+            resource: RateResource::Core,
+            remaining: 4321,
+            reset: 1_760_000_000,
+        });
+        limits.record(RateLimit {
+            resource: RateResource::Search,
+            remaining: 27,
+            reset: 1_759_996_460,
+        });
+        metrics.set_rate_limits(limits);
+        let text = metrics.render();
+        assert!(text.contains("chrono_fetch_seconds_count 4\n"));
+        assert!(text.contains("chrono_fetch_failures_total{reason=\"timeout\"} 1\n"));
+        assert!(text.contains("chrono_fetch_failures_total{reason=\"network\"} 2\n"));
+        assert!(text.contains("chrono_rate_limit_remaining{resource=\"core\"} 4321\n"));
+        assert!(text.contains("chrono_rate_limit_remaining{resource=\"search\"} 27\n"));
+    }
+
+    #[test]
+    fn serves_only_the_metrics_path() {
+        let metrics = Metrics::default();
+        let ok = respond("GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n", &metrics);
+        assert!(ok.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(ok.ends_with(&metrics.render()));
+        let missing = respond("GET /favicon.ico HTTP/1.1\r\n\r\n", &metrics);
+        assert!(missing.starts_with("HTTP/1.1 404 "));
+        let post = respond("POST /metrics HTTP/1.1\r\n\r\n", &metrics);
+        assert!(post.starts_with("HTTP/1.1 405 "));
+    }
+}
@@ -0,0 +1,45 @@
+// The `CHRONO_NIGHT_HOURS` schedule: between those hours the board dims to
+// `CHRONO_NIGHT_BRIGHTNESS` and the accent warms as with the sunset tint,
+// without needing a location.
+use crate::sound::QuietHours;
+use chrono::{NaiveTime, Timelike};
+
+// Night fades in over the first half hour and out over the last.
+const FADE_SECS: i64 = 30 * 60;
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+// 0 outside the hours, 1 well inside them.
+pub fn level(time: NaiveTime, hours: QuietHours) -> f32 {
+    let secs = |time: NaiveTime| time.num_seconds_from_midnight() as i64;
+    let length = (secs(hours.end) - secs(hours.start)).rem_euclid(DAY_SECS);
+    let into = (secs(time) - secs(hours.start)).rem_euclid(DAY_SECS);
+    if into >= length {
+        return 0.0;
+    }
+    into.min(length - into).min(FADE_SECS) as f32 / FADE_SECS as f32
+}
+
+// The board brightness at `level`, from full down to `night_brightness`.
+pub fn brightness(level: f32, night_brightness: f32) -> f32 {
+    1.0 + (night_brightness - 1.0) * level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, "%H:%M").unwrap()
+    }
+
+    #[test]
+    fn fades_in_and_out_across_midnight() {
+        let hours: QuietHours = "22:00-07:00".parse().unwrap();
+        assert_eq!(level(at("21:00"), hours), 0.0);
+        assert_eq!(level(at("22:15"), hours), 0.5);
+        assert_eq!(level(at("02:00"), hours), 1.0);
+        assert_eq!(level(at("06:45"), hours), 0.5);
+        assert_eq!(level(at("07:00"), hours), 0.0);
+        assert_eq!(brightness(0.5, 0.4), 0.7);
+    }
+}
@@ -0,0 +1,132 @@
+// GitHub's device authorization flow: the user types a short code on
+// github.com/login/device while we poll for the resulting token.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+pub enum DeviceFlowEvent {
+    Code {
+        user_code: String,
+        verification_uri: String,
+    },
+    Token(String),
+    Failed(String),
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+mod native {
+    use super::DeviceFlowEvent;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
+    const ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+    const SCOPES: &str = "repo read:org notifications";
+
+    fn post_form(
+        agent: &ureq::Agent,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> Result<serde_json::Value, String> {
+        let body = agent
+            .post(url)
+            .set("User-Agent", "commit-clock")
+            .set("Accept", "application/json")
+            .send_form(form)
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())?;
+        serde_json::from_str(&body).map_err(|e| e.to_string())
+    }
+
+    fn json_str(json: &serde_json::Value, key: &str) -> Option<String> {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    }
+
+    fn run(client_id: &str, tx: &mpsc::Sender<DeviceFlowEvent>) -> Result<String, String> {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .build();
+
+        let code = post_form(
+            &agent,
+            DEVICE_CODE_URL,
+            &[("client_id", client_id), ("scope", SCOPES)],
+        )?;
+        let device_code = json_str(&code, "device_code")
+            .ok_or_else(|| json_str(&code, "error").unwrap_or("no device code".to_string()))?;
+        let user_code = json_str(&code, "user_code").ok_or("no user code")?;
+        let verification_uri = json_str(&code, "verification_uri")
+            .unwrap_or("https://github.com/login/device".to_string());
+        let expires_in = code
+            .get("expires_in")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(900);
+        let mut interval = code.get("interval").and_then(|v| v.as_u64()).unwrap_or(5);
+
+        let _ = tx.send(DeviceFlowEvent::Code {
+            user_code,
+            verification_uri,
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(expires_in);
+        while Instant::now() < deadline {
+            thread::sleep(Duration::from_secs(interval));
+            let response = post_form(
+                &agent,
+                ACCESS_TOKEN_URL,
+                &[
+                    ("client_id", client_id),
+                    ("device_code", &device_code),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ],
+            )?;
+            if let Some(token) = json_str(&response, "access_token") {
+                return Ok(token);
+            }
+            match json_str(&response, "error").as_deref() {
+                Some("authorization_pending") => {}
+                // GitHub asks us to back off by five seconds on every slow_down.
+                Some("slow_down") => interval += 5,
+                Some(error) => return Err(error.to_string()),
+                None => return Err("unexpected response".to_string()),
+            }
+        }
+        Err("device code expired".to_string())
+    }
+
+    pub fn start(client_id: String) -> mpsc::Receiver<DeviceFlowEvent> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let event = match run(&client_id, &tx) {
+                Ok(token) => {
+                    if let Err(e) = crate::secrets::store_github_token(&token) {
+                        log!("Failed to store GitHub token: {e}");
+                    }
+                    DeviceFlowEvent::Token(token)
+                }
+                Err(e) => DeviceFlowEvent::Failed(e),
+            };
+            let _ = tx.send(event);
+        });
+        rx
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub use native::start;
+
+// github.com does not send CORS headers for the device flow endpoints, and
+// builds without the network feature have no HTTP client.
+#[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+pub fn start(_client_id: String) -> std::sync::mpsc::Receiver<DeviceFlowEvent> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let _ = tx.send(DeviceFlowEvent::Failed(
+        "device sign-in is not available in this build".to_string(),
+    ));
+    rx
+}
@@ -0,0 +1,131 @@
+// The board's pages in `CHRONO_PAGES` order, cycled with Tab, the arrow
+// keys or a swipe: the clock with the PR list, the month calendar and the
+// GitHub stats.
+use crate::provider::{CiState, CiStatus, WorkItem};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Page {
+    Clock,
+    Calendar,
+    Stats,
+}
+
+impl FromStr for Page {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "clock" | "prs" => Ok(Page::Clock),
+            "calendar" | "month" => Ok(Page::Calendar),
+            "stats" => Ok(Page::Stats),
+            _ => Err(format!("unknown page {}", value)),
+        }
+    }
+}
+
+// `clock, calendar, stats`. The clock is kept first when left out, and
+// repeats are dropped.
+pub fn parse_pages(value: &str) -> Vec<Page> {
+    let mut pages = vec![Page::Clock];
+    for name in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match name.parse::<Page>() {
+            Ok(page) if !pages.contains(&page) => pages.push(page),
+            Ok(_) => {}
+            Err(e) => log!("Ignoring {} in CHRONO_PAGES", e),
+        }
+    }
+    pages
+}
+
+// Where `page` sits in `pages`, for the page dots.
+pub fn index(pages: &[Page], page: Page) -> usize {
+    pages.iter().position(|known| *known == page).unwrap_or(0)
+}
+
+// The page `offset` places away from `current`, wrapping around.
+pub fn cycle(pages: &[Page], current: Page, offset: isize) -> Page {
+    let Some(index) = pages.iter().position(|page| *page == current) else {
+        return pages.first().copied().unwrap_or(Page::Clock);
+    };
+    let next = (index as isize + offset).rem_euclid(pages.len() as isize);
+    pages[next as usize]
+}
+
+// What the stats page counts, as of the last fetch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub prs: usize,
+    pub reviews: usize,
+    pub failing: usize,
+    pub conflicts: usize,
+    pub stale: usize,
+}
+
+impl Stats {
+    pub fn collect(
+        prs: &[WorkItem],
+        reviews: usize,
+        ci: &[CiStatus],
+        conflicts: &[String],
+        stale_days: Option<u32>,
+        now: i64,
+    ) -> Stats {
+        Stats {
+            prs: prs.len(),
+            reviews,
+            failing: ci
+                .iter()
+                .filter(|status| status.state == CiState::Failure)
+                .count(),
+            conflicts: conflicts.len(),
+            stale: stale_days.map_or(0, |days| {
+                prs.iter().filter(|pr| pr.is_stale(now, days)).count()
+            }),
+        }
+    }
+
+    // `7 OPEN PRS`, a line per count. Failures, conflicts and stale PRs
+    // only show when there are any.
+    pub fn lines(&self) -> Vec<String> {
+        let plural = |count: usize, one: &str, many: &str| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        };
+        [
+            Some(plural(self.prs, "OPEN PR", "OPEN PRS")),
+            Some(format!("{} TO REVIEW", self.reviews)),
+            (self.failing > 0).then(|| format!("{} FAILING CI", self.failing)),
+            (self.conflicts > 0).then(|| plural(self.conflicts, "CONFLICT", "CONFLICTS")),
+            (self.stale > 0).then(|| plural(self.stale, "STALE PR", "STALE PRS")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_and_cycles_pages() {
+        let pages = parse_pages("stats, calendar, stats, weather");
+        assert_eq!(pages, [Page::Clock, Page::Stats, Page::Calendar]);
+        assert_eq!(cycle(&pages, Page::Clock, 1), Page::Stats);
+        assert_eq!(cycle(&pages, Page::Clock, -1), Page::Calendar);
+        assert_eq!(cycle(&pages, Page::Calendar, 1), Page::Clock);
+        assert_eq!(cycle(&[Page::Clock], Page::Stats, 1), Page::Clock);
+    }
+
+    #[test]
+    fn stats_leave_out_empty_problems() {
+        let stats = Stats {
+            prs: 1,
+            reviews: 3,
+            conflicts: 2,
+            ..Default::default()
+        };
+        assert_eq!(stats.lines(), ["1 OPEN PR", "3 TO REVIEW", "2 CONFLICTS"]);
+    }
+}
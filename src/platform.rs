@@ -0,0 +1,586 @@
+use chrono::{DateTime, FixedOffset};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    // Provided by web/chrono.js.
+    fn chrono_open_url(ptr: *const u8, len: usize);
+    fn chrono_token_length() -> usize;
+    fn chrono_token_copy(ptr: *mut u8);
+    fn chrono_timezone_offset_minutes() -> i32;
+    fn chrono_prefers_12_hour() -> i32;
+    fn chrono_prefers_dark() -> i32;
+    fn chrono_set_title(ptr: *const u8, len: usize);
+    fn chrono_notify(
+        title: *const u8,
+        title_len: usize,
+        body: *const u8,
+        body_len: usize,
+        url: *const u8,
+        url_len: usize,
+    );
+    fn chrono_page_query_length() -> usize;
+    fn chrono_page_query_copy(ptr: *mut u8);
+    fn chrono_http_get(url: *const u8, url_len: usize, token: *const u8, token_len: usize) -> u32;
+    fn chrono_http_status(id: u32) -> i32;
+    fn chrono_http_body_length(id: u32) -> usize;
+    fn chrono_http_body_copy(id: u32, ptr: *mut u8);
+    fn chrono_http_free(id: u32);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> DateTime<FixedOffset> {
+    chrono::Local::now().fixed_offset()
+}
+
+// The zone abbreviation in effect at `at` (`CET`, or `CEST` in summer) from
+// the C library's zone data. Zones without one report a bare offset such as
+// `+04`, which is dropped since the offset is shown next to it anyway.
+#[cfg(unix)]
+pub fn timezone_abbreviation(at: DateTime<FixedOffset>) -> Option<String> {
+    let time = at.timestamp() as libc::time_t;
+    let mut tm = unsafe { std::mem::zeroed::<libc::tm>() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() || tm.tm_zone.is_null() {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(tm.tm_zone) }
+        .to_str()
+        .ok()?;
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| name.to_ascii_uppercase())
+}
+
+// Windows only has long names ("W. Europe Standard Time") and the page
+// only passes the offset.
+#[cfg(not(unix))]
+pub fn timezone_abbreviation(_at: DateTime<FixedOffset>) -> Option<String> {
+    None
+}
+
+// chrono cannot read the clock or the timezone in the browser, so take the
+// timestamp from miniquad and the UTC offset from the page.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> DateTime<FixedOffset> {
+    let millis = (macroquad::miniquad::date::now() * 1000.0) as i64;
+    let offset = unsafe { chrono_timezone_offset_minutes() } * 60;
+    let offset = FixedOffset::east_opt(offset).unwrap_or(FixedOffset::east_opt(0).unwrap());
+    chrono::DateTime::from_timestamp_millis(millis)
+        .unwrap_or_default()
+        .with_timezone(&offset)
+}
+
+// Whether the user's region settings ask for a 12-hour clock, where the
+// system has a setting for it. Elsewhere the locale variables decide; see
+// `HourFormat::detect`.
+#[cfg(target_os = "macos")]
+pub fn prefers_12_hour() -> Option<bool> {
+    unsafe {
+        use objc::runtime::Object;
+        use objc::{class, msg_send, sel, sel_impl};
+        // The hour pattern for the current locale, e.g. `h a` or `HH`.
+        let locale: *mut Object = msg_send![class!(NSLocale), currentLocale];
+        let template: *mut Object =
+            msg_send![class!(NSString), stringWithUTF8String: c"j".as_ptr()];
+        let format: *mut Object = msg_send![class!(NSDateFormatter),
+            dateFormatFromTemplate: template
+            options: 0usize
+            locale: locale];
+        if format.is_null() {
+            return None;
+        }
+        let chars: *const std::ffi::c_char = msg_send![format, UTF8String];
+        if chars.is_null() {
+            return None;
+        }
+        Some(
+            std::ffi::CStr::from_ptr(chars)
+                .to_string_lossy()
+                .contains('a'),
+        )
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn prefers_12_hour() -> Option<bool> {
+    win32::prefers_12_hour()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn prefers_12_hour() -> Option<bool> {
+    match unsafe { chrono_prefers_12_hour() } {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_arch = "wasm32")))]
+pub fn prefers_12_hour() -> Option<bool> {
+    None
+}
+
+// Whether the system is in dark mode, where it says. Desktop platforms ask
+// a subprocess, so call this through `background`.
+#[cfg(target_os = "macos")]
+pub fn prefers_dark() -> Option<bool> {
+    // The key is only set in dark mode.
+    let output = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleInterfaceStyle"])
+        .output()
+        .ok()?;
+    Some(output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "Dark")
+}
+
+// Keeps a console window from flashing up for each helper process.
+#[cfg(target_os = "windows")]
+pub const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+#[cfg(target_os = "windows")]
+pub fn prefers_dark() -> Option<bool> {
+    use std::os::windows::process::CommandExt;
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = stdout.split_whitespace().last()?;
+    match value {
+        "0x0" => Some(true),
+        "0x1" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn prefers_dark() -> Option<bool> {
+    match unsafe { chrono_prefers_dark() } {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+// GNOME's color scheme, which other desktops following the freedesktop
+// portal mirror; `default` reads as light.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn prefers_dark() -> Option<bool> {
+    let output = std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "color-scheme"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).contains("prefer-dark"))
+}
+
+#[cfg(not(any(unix, target_os = "windows", target_arch = "wasm32")))]
+pub fn prefers_dark() -> Option<bool> {
+    None
+}
+
+// Runs `task` off the render thread where threads exist; in the browser it
+// runs inline, so only cheap work should go through here there.
+pub fn background<T, F>(task: F) -> mpsc::Receiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::spawn(move || {
+        let _ = tx.send(task());
+    });
+    #[cfg(target_arch = "wasm32")]
+    let _ = tx.send(task());
+    rx
+}
+
+pub fn sleep(duration: Duration) {
+    // The browser drives frames with requestAnimationFrame and cannot block.
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::sleep(duration);
+    #[cfg(target_arch = "wasm32")]
+    let _ = duration;
+}
+
+pub fn open_url(url: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("open").arg(url).spawn();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = std::process::Command::new("cmd")
+            .args(["/C", "start", "", url])
+            .spawn();
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let _ = std::process::Command::new("xdg-open").arg(url).spawn();
+    }
+    #[cfg(target_arch = "wasm32")]
+    unsafe {
+        chrono_open_url(url.as_ptr(), url.len());
+    }
+}
+
+// miniquad only sets the title at startup, so reach for the native window.
+// X11 and Wayland keep their handles private and are left alone.
+pub fn set_window_title(title: &str) {
+    #[cfg(target_os = "macos")]
+    unsafe {
+        use objc::runtime::Object;
+        use objc::{class, msg_send, sel, sel_impl};
+        const NS_UTF8_STRING_ENCODING: usize = 4;
+        let string: *mut Object = msg_send![class!(NSString), alloc];
+        let string: *mut Object = msg_send![string,
+            initWithBytes: title.as_ptr()
+            length: title.len()
+            encoding: NS_UTF8_STRING_ENCODING];
+        let view = macroquad::miniquad::window::apple_view();
+        let window: *mut Object = msg_send![view, window];
+        let () = msg_send![window, setTitle: string];
+        let () = msg_send![string, release];
+    }
+    #[cfg(target_os = "windows")]
+    win32::set_window_title(title);
+    #[cfg(target_arch = "wasm32")]
+    unsafe {
+        chrono_set_title(title.as_ptr(), title.len());
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let _ = title;
+}
+
+// Where the window sits on screen, for dragging it by its background.
+// miniquad only reports and moves it on Windows and Linux.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub fn window_position() -> Option<(i32, i32)> {
+    let (x, y) = macroquad::miniquad::window::get_window_position();
+    Some((x as i32, y as i32))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn window_position() -> Option<(i32, i32)> {
+    None
+}
+
+pub fn move_window(x: i32, y: i32) {
+    macroquad::miniquad::window::set_window_position(x.max(0) as u32, y.max(0) as u32);
+}
+
+// Shows `count` on the Dock icon, or clears it at zero. Only macOS has a
+// badge; Windows taskbar overlays would need an icon rendered per count.
+pub fn set_dock_badge(count: usize) {
+    #[cfg(target_os = "macos")]
+    unsafe {
+        use objc::runtime::Object;
+        use objc::{class, msg_send, sel, sel_impl};
+        const NS_UTF8_STRING_ENCODING: usize = 4;
+        let label = if count > 0 {
+            count.to_string()
+        } else {
+            String::new()
+        };
+        let string: *mut Object = msg_send![class!(NSString), alloc];
+        let string: *mut Object = msg_send![string,
+            initWithBytes: label.as_ptr()
+            length: label.len()
+            encoding: NS_UTF8_STRING_ENCODING];
+        let app: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+        let tile: *mut Object = msg_send![app, dockTile];
+        let () = msg_send![tile, setBadgeLabel: string];
+        let () = msg_send![string, release];
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = count;
+}
+
+#[cfg(target_os = "windows")]
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// A desktop notification; clicking it opens `url` where the system reports
+// clicks back: libnotify's `--wait` on Linux, a protocol toast on Windows
+// and the Notification API in the browser. macOS shows it without a click
+// action, since `osascript` notifications open Script Editor.
+pub fn notify(title: &str, body: &str, url: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("osascript")
+            .args([
+                "-e",
+                "on run argv",
+                "-e",
+                "display notification (item 2 of argv) with title \"Chrono\" subtitle (item 1 of argv)",
+                "-e",
+                "end run",
+                title,
+                body,
+            ])
+            .spawn();
+        let _ = url;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // PowerShell's own app id, since toasts from an unregistered one
+        // are dropped.
+        const SCRIPT: &str = "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+            [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] > $null; \
+            $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; \
+            $xml.LoadXml($env:CHRONO_TOAST); \
+            [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe').Show([Windows.UI.Notifications.ToastNotification]::new($xml))";
+        let toast = format!(
+            "<toast activationType=\"protocol\" launch=\"{}\"><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>",
+            xml_escape(url),
+            xml_escape(title),
+            xml_escape(body)
+        );
+        let _ = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+            .env("CHRONO_TOAST", toast)
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn();
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let (title, body, url) = (title.to_string(), body.to_string(), url.to_string());
+        // `--wait` blocks until the notification is clicked or closed, and
+        // prints the action taken.
+        std::thread::spawn(move || {
+            let output = std::process::Command::new("notify-send")
+                .args([
+                    "--app-name=Chrono",
+                    "--action=default=Open",
+                    "--wait",
+                    &title,
+                    &body,
+                ])
+                .output();
+            if output.is_ok_and(|output| output.stdout.trim_ascii() == b"default") {
+                open_url(&url);
+            }
+        });
+    }
+    #[cfg(target_arch = "wasm32")]
+    unsafe {
+        chrono_notify(
+            title.as_ptr(),
+            title.len(),
+            body.as_ptr(),
+            body.len(),
+            url.as_ptr(),
+            url.len(),
+        );
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use std::ffi::c_void;
+
+    type Hwnd = *mut c_void;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn EnumThreadWindows(
+            thread: u32,
+            callback: extern "system" fn(Hwnd, isize) -> i32,
+            param: isize,
+        ) -> i32;
+        fn SetWindowTextW(hwnd: Hwnd, text: *const u16) -> i32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentThreadId() -> u32;
+        fn GetLocaleInfoEx(name: *const u16, lc_type: u32, data: *mut u16, len: i32) -> i32;
+    }
+
+    // The user's time pattern, `h:mm:ss tt` or `HH:mm:ss`.
+    pub fn prefers_12_hour() -> Option<bool> {
+        const LOCALE_STIMEFORMAT: u32 = 0x1003;
+        let mut pattern = [0u16; 80];
+        let len = unsafe {
+            GetLocaleInfoEx(
+                std::ptr::null(),
+                LOCALE_STIMEFORMAT,
+                pattern.as_mut_ptr(),
+                pattern.len() as i32,
+            )
+        };
+        if len <= 1 {
+            return None;
+        }
+        Some(String::from_utf16_lossy(&pattern[..len as usize - 1]).contains('h'))
+    }
+
+    extern "system" fn set_text(hwnd: Hwnd, text: isize) -> i32 {
+        unsafe { SetWindowTextW(hwnd, text as *const u16) };
+        1
+    }
+
+    // The window belongs to the main thread, which is also where frames run.
+    pub fn set_window_title(title: &str) {
+        let wide = title
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect::<Vec<u16>>();
+        unsafe { EnumThreadWindows(GetCurrentThreadId(), set_text, wide.as_ptr() as isize) };
+    }
+}
+
+// `~/.cache/chrono`, or under `XDG_CACHE_HOME` when set.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn cache_dir() -> Option<std::path::PathBuf> {
+    let dir = match std::env::var("XDG_CACHE_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => std::path::PathBuf::from(dir),
+        _ => std::path::PathBuf::from(std::env::var("HOME").ok()?).join(".cache"),
+    };
+    Some(dir.join("chrono"))
+}
+
+// `~/.config/chrono`, or under `XDG_CONFIG_HOME` when set.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn config_dir() -> Option<std::path::PathBuf> {
+    let dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => std::path::PathBuf::from(dir),
+        _ => std::path::PathBuf::from(std::env::var("HOME").ok()?).join(".config"),
+    };
+    Some(dir.join("chrono"))
+}
+
+// Where the installed theme lives; the rest of the settings are
+// environment variables.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_config_dir() {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+    open_url(&dir.to_string_lossy());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_github_token() -> Option<String> {
+    #[cfg(feature = "network")]
+    if let Some(token) = crate::secrets::github_token() {
+        return Some(token);
+    }
+
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    if let Ok(token) = std::env::var("CHRONO_GITHUB_TOKEN") {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    let home = std::env::var("HOME").ok()?;
+    let path = format!("{}/.config/chrono/token", home);
+    let token = std::fs::read_to_string(path).ok()?;
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+// A link chrono was started with: `--join <url>` natively, the page's own
+// query string in the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn launch_link() -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--join");
+    args.next()?;
+    args.next()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn launch_link() -> Option<String> {
+    let len = unsafe { chrono_page_query_length() };
+    if len == 0 {
+        return None;
+    }
+    let mut bytes = vec![0u8; len];
+    unsafe { chrono_page_query_copy(bytes.as_mut_ptr()) };
+    String::from_utf8(bytes).ok()
+}
+
+// A GitHub API GET through the page's `fetch()`. The browser answers
+// asynchronously and nothing wakes the task, which is fine since the render
+// loop polls the fetch every frame anyway.
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+pub struct HttpGet(u32);
+
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+pub fn http_get(url: &str, token: &str) -> HttpGet {
+    HttpGet(unsafe { chrono_http_get(url.as_ptr(), url.len(), token.as_ptr(), token.len()) })
+}
+
+// The status and body, or an error when no answer came at all.
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+impl std::future::Future for HttpGet {
+    type Output = Result<(u16, String), String>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        use std::task::Poll;
+        match unsafe { chrono_http_status(self.0) } {
+            -1 => Poll::Pending,
+            0 => Poll::Ready(Err("request failed".to_string())),
+            status => {
+                let mut body = vec![0u8; unsafe { chrono_http_body_length(self.0) }];
+                unsafe { chrono_http_body_copy(self.0, body.as_mut_ptr()) };
+                Poll::Ready(Ok((
+                    status as u16,
+                    String::from_utf8_lossy(&body).into_owned(),
+                )))
+            }
+        }
+    }
+}
+
+// A superseded fetch drops its requests; the page forgets their answers.
+#[cfg(all(feature = "network", target_arch = "wasm32"))]
+impl Drop for HttpGet {
+    fn drop(&mut self) {
+        unsafe { chrono_http_free(self.0) };
+    }
+}
+
+// The page reads `?token=` once, keeps it in localStorage and hands it over here.
+#[cfg(target_arch = "wasm32")]
+pub fn load_github_token() -> Option<String> {
+    let len = unsafe { chrono_token_length() };
+    if len == 0 {
+        return None;
+    }
+    let mut bytes = vec![0u8; len];
+    unsafe { chrono_token_copy(bytes.as_mut_ptr()) };
+    let token = String::from_utf8(bytes).ok()?.trim().to_string();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
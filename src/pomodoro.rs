@@ -0,0 +1,72 @@
+// A focus session started and stopped with P. Times are `get_time()`
+// seconds.
+pub struct Pomodoro {
+    length_secs: f64,
+    ends_at: Option<f64>,
+}
+
+impl Pomodoro {
+    pub fn new(minutes: f64) -> Self {
+        Pomodoro {
+            length_secs: minutes * 60.0,
+            ends_at: None,
+        }
+    }
+
+    pub fn toggle(&mut self, now: f64) {
+        self.ends_at = match self.ends_at {
+            Some(end) if end > now => None,
+            _ => Some(now + self.length_secs),
+        };
+    }
+
+    // Starts a session that ends `remaining_secs` from now, for one shared
+    // by someone else.
+    pub fn join(&mut self, now: f64, remaining_secs: f64) {
+        if remaining_secs > 0.0 {
+            self.ends_at = Some(now + remaining_secs);
+        }
+    }
+
+    pub fn remaining(&self, now: f64) -> Option<f64> {
+        self.ends_at.map(|end| end - now).filter(|secs| *secs > 0.0)
+    }
+
+    // True once when a session runs out, rather than being stopped with P.
+    pub fn finished(&mut self, now: f64) -> bool {
+        let done = self.ends_at.is_some_and(|end| end <= now);
+        if done {
+            self.ends_at = None;
+        }
+        done
+    }
+
+    pub fn focusing(&self, now: f64) -> bool {
+        self.ends_at.is_some_and(|end| end > now)
+    }
+
+    // `FOCUS 24M` while a session runs.
+    pub fn label(&self, now: f64) -> Option<String> {
+        let end = self.ends_at.filter(|end| *end > now)?;
+        Some(format!("FOCUS {}M", ((end - now) / 60.0).ceil() as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finishes_once_when_the_session_runs_out() {
+        let mut pomodoro = Pomodoro::new(25.0);
+        pomodoro.toggle(0.0);
+        assert!(!pomodoro.finished(60.0));
+        assert!(pomodoro.finished(25.0 * 60.0));
+        assert!(!pomodoro.finished(25.0 * 60.0 + 1.0));
+
+        // Stopping early is not finishing.
+        pomodoro.toggle(0.0);
+        pomodoro.toggle(60.0);
+        assert!(!pomodoro.finished(25.0 * 60.0));
+    }
+}
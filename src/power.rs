@@ -0,0 +1,135 @@
+use crate::platform;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+const BATTERY_CHECK_SECS: f64 = 60.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerSaveMode {
+    Auto,
+    On,
+    Off,
+}
+
+impl PowerSaveMode {
+    pub fn next(self) -> Self {
+        match self {
+            PowerSaveMode::Auto => PowerSaveMode::On,
+            PowerSaveMode::On => PowerSaveMode::Off,
+            PowerSaveMode::Off => PowerSaveMode::Auto,
+        }
+    }
+}
+
+impl FromStr for PowerSaveMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(PowerSaveMode::Auto),
+            "on" | "true" | "1" => Ok(PowerSaveMode::On),
+            "off" | "false" | "0" => Ok(PowerSaveMode::Off),
+            _ => Err(format!("unknown power save mode {}", value)),
+        }
+    }
+}
+
+pub struct PowerMonitor {
+    mode: PowerSaveMode,
+    on_battery: bool,
+    last_check: Option<f64>,
+    rx: Option<mpsc::Receiver<bool>>,
+}
+
+impl PowerMonitor {
+    pub fn new(mode: PowerSaveMode) -> Self {
+        PowerMonitor {
+            mode,
+            on_battery: false,
+            last_check: None,
+            rx: None,
+        }
+    }
+
+    pub fn cycle_mode(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    pub fn active(&mut self, now: f64) -> bool {
+        match self.mode {
+            PowerSaveMode::On => true,
+            PowerSaveMode::Off => false,
+            PowerSaveMode::Auto => {
+                self.poll(now);
+                self.on_battery
+            }
+        }
+    }
+
+    fn poll(&mut self, now: f64) {
+        if let Some(rx) = &self.rx {
+            if let Ok(on_battery) = rx.try_recv() {
+                self.on_battery = on_battery;
+                self.rx = None;
+            }
+        }
+
+        let stale = self
+            .last_check
+            .map(|last| now - last >= BATTERY_CHECK_SECS)
+            .unwrap_or(true);
+        if stale && self.rx.is_none() {
+            self.last_check = Some(now);
+            // Some platforms need a subprocess to answer, keep it off the render thread.
+            self.rx = Some(platform::background(on_battery));
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn on_battery() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn on_battery() -> bool {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+    // ACLineStatus is 0 unplugged, 1 plugged in and 255 when unknown.
+    unsafe {
+        let mut status: SYSTEM_POWER_STATUS = std::mem::zeroed();
+        GetSystemPowerStatus(&mut status) != 0 && status.ACLineStatus == 0
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn on_battery() -> bool {
+    let entries = match std::fs::read_dir("/sys/class/power_supply") {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    let read = |path: std::path::PathBuf| {
+        std::fs::read_to_string(path)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read(path.join("type")).as_str() {
+            "Mains" | "USB" if read(path.join("online")) == "1" => return false,
+            "Battery" if read(path.join("status")) == "Discharging" => discharging = true,
+            _ => {}
+        }
+    }
+    discharging
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn on_battery() -> bool {
+    false
+}
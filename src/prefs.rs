@@ -0,0 +1,85 @@
+// Choices made on the board itself that should outlive any one session,
+// kept in the config directory whether or not `CHRONO_RESTORE_SESSION` is
+// on. Same `key=value` lines as the session file.
+use crate::clock::HourFormat;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Preferences {
+    // Set once the hour format is switched with H, overriding the system's.
+    pub hour_format: Option<HourFormat>,
+}
+
+impl Preferences {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(hour_format) = self.hour_format {
+            let hours = match hour_format {
+                HourFormat::H12 => 12,
+                HourFormat::H24 => 24,
+            };
+            text.push_str(&format!("hour_format={}\n", hours));
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Preferences {
+        let mut preferences = Preferences::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            if key.trim() == "hour_format" {
+                preferences.hour_format = value.trim().parse().ok();
+            }
+        }
+        preferences
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path() -> Option<std::path::PathBuf> {
+    Some(crate::platform::config_dir()?.join("preferences.txt"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(preferences: &Preferences) {
+    let Some(path) = path() else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = std::fs::write(&path, preferences.to_text()) {
+        log!("Failed to write {}: {e}", path.display());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load() -> Preferences {
+    path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| Preferences::from_text(&text))
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save(_preferences: &Preferences) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load() -> Preferences {
+    Preferences::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let preferences = Preferences {
+            hour_format: Some(HourFormat::H12),
+        };
+        assert_eq!(Preferences::from_text(&preferences.to_text()), preferences);
+        assert_eq!(
+            Preferences::from_text("focus_until=1\nhour_format=never\n"),
+            Preferences::default()
+        );
+    }
+}
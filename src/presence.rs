@@ -0,0 +1,61 @@
+use macroquad::prelude::*;
+
+// Seconds for a full swing between idle and full brightness.
+const DIM_SECS: f32 = 2.0;
+const WAKE_SECS: f32 = 0.3;
+
+// Dims the board after a stretch without mouse or keyboard activity.
+// macroquad reports no focus changes, and the cursor stops moving over an
+// unfocused window, so activity is the stand-in for presence.
+pub struct Presence {
+    idle_after: Option<f64>,
+    idle_brightness: f32,
+    last_active: f64,
+    last_mouse: (f32, f32),
+    last_frame: f64,
+    brightness: f32,
+}
+
+impl Presence {
+    pub fn new(idle_minutes: f64, idle_brightness: f32, now: f64) -> Self {
+        Presence {
+            idle_after: (idle_minutes > 0.0).then_some(idle_minutes * 60.0),
+            idle_brightness: idle_brightness.clamp(0.0, 1.0),
+            last_active: now,
+            last_mouse: mouse_position(),
+            last_frame: now,
+            brightness: 1.0,
+        }
+    }
+
+    // Returns the brightness multiplier for this frame.
+    pub fn update(&mut self, now: f64) -> f32 {
+        let mouse = mouse_position();
+        let active = mouse != self.last_mouse
+            || mouse_wheel() != (0.0, 0.0)
+            || is_mouse_button_down(MouseButton::Left)
+            || is_mouse_button_down(MouseButton::Right)
+            || !get_keys_down().is_empty();
+        self.last_mouse = mouse;
+        if active {
+            self.last_active = now;
+        }
+
+        let idle = self
+            .idle_after
+            .is_some_and(|after| now - self.last_active >= after);
+        let (target, secs) = if idle {
+            (self.idle_brightness, DIM_SECS)
+        } else {
+            (1.0, WAKE_SECS)
+        };
+        let step = (now - self.last_frame) as f32 / secs;
+        self.last_frame = now;
+        self.brightness = if self.brightness < target {
+            (self.brightness + step).min(target)
+        } else {
+            (self.brightness - step).max(target)
+        };
+        self.brightness
+    }
+}
@@ -0,0 +1,676 @@
+// Only the native fetch thread talks to providers, and only with the
+// network feature.
+#![cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+
+use crate::clock::age_label;
+use crate::error::ChronoError;
+use chrono::DateTime;
+
+// A PR or issue as listed on the board. `account` labels items when more
+// than one GitHub account is configured; `repo`, `number` and `updated_at`
+// feed the dim line under the title when the provider reports them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorkItem {
+    pub title: String,
+    pub url: String,
+    pub account: Option<String>,
+    pub repo: Option<String>,
+    pub number: Option<u64>,
+    // Unix seconds.
+    pub updated_at: Option<i64>,
+    // The author's avatar, drawn in place of the PR icon.
+    pub avatar_url: Option<String>,
+}
+
+impl WorkItem {
+    // `chrono#123 · 2d`, or None when there is nothing to show.
+    pub fn meta_line(&self, now: i64) -> Option<String> {
+        let repo = self
+            .repo
+            .as_deref()
+            .map(|repo| repo.rsplit('/').next().unwrap_or(repo));
+        let reference = match (repo, self.number) {
+            (Some(repo), Some(number)) => Some(format!("{}#{}", repo, number)),
+            (Some(repo), None) => Some(repo.to_string()),
+            (None, Some(number)) => Some(format!("#{}", number)),
+            (None, None) => None,
+        };
+        let age = self.updated_at.map(|at| age_label(now - at));
+        match (reference, age) {
+            (Some(reference), Some(age)) => Some(format!("{} \u{b7} {}", reference, age)),
+            (reference, age) => reference.or(age),
+        }
+    }
+
+    // Not updated for at least `days` days.
+    pub fn is_stale(&self, now: i64, days: u32) -> bool {
+        self.updated_at
+            .is_some_and(|at| now - at >= i64::from(days) * 86_400)
+    }
+}
+
+// Open review requests waiting on one teammate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewLoad {
+    pub login: String,
+    pub count: usize,
+}
+
+// What a provider can report. The UI hides what no connected provider
+// supports instead of showing it as empty.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub prs: bool,
+    pub reviews: bool,
+    pub issues: bool,
+    pub ci: bool,
+    pub decisions: bool,
+    pub conflicts: bool,
+}
+
+impl Capabilities {
+    pub fn union(self, other: Capabilities) -> Capabilities {
+        Capabilities {
+            prs: self.prs || other.prs,
+            reviews: self.reviews || other.reviews,
+            issues: self.issues || other.issues,
+            ci: self.ci || other.ci,
+            decisions: self.decisions || other.decisions,
+            conflicts: self.conflicts || other.conflicts,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CiState {
+    Pending,
+    Success,
+    Failure,
+}
+
+// Latest CI state of a PR, keyed by the PR's web URL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CiStatus {
+    pub url: String,
+    pub state: CiState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    Pending,
+}
+
+// Where a PR stands with its reviewers, keyed by the PR's web URL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewStatus {
+    pub url: String,
+    pub decision: ReviewDecision,
+}
+
+// Which of GitHub's quotas a response counted against, from
+// `x-ratelimit-resource`. Search allows 30 requests a minute while the core
+// REST API allows 5000 an hour, so they are never compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateResource {
+    Core,
+    Search,
+    Graphql,
+    Other,
+}
+
+impl RateResource {
+    // Responses without the header predate it and count against core.
+    pub fn from_header(value: Option<&str>) -> RateResource {
+        match value.map(str::trim) {
+            None | Some("core") => RateResource::Core,
+            Some("search") => RateResource::Search,
+            Some("graphql") => RateResource::Graphql,
+            Some(_) => RateResource::Other,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RateResource::Core => "core",
+            RateResource::Search => "search",
+            RateResource::Graphql => "graphql",
+            RateResource::Other => "other",
+        }
+    }
+
+    // Requests kept in hand; below this polls wait for the quota to reset.
+    // A poll spends a few searches but many core requests.
+    fn reserve(self) -> u32 {
+        match self {
+            RateResource::Core | RateResource::Graphql => 20,
+            RateResource::Search => 3,
+            RateResource::Other => 0,
+        }
+    }
+}
+
+// One quota as last seen. `reset` is a Unix timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub resource: RateResource,
+    pub remaining: u32,
+    pub reset: i64,
+}
+
+impl RateLimit {
+    // Nearly spent and not reset yet.
+    pub fn holding(&self, now: i64) -> bool {
+        self.remaining <= self.resource.reserve() && self.reset > now
+    }
+}
+
+// The lowest quota seen for each resource during a poll.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimits(Vec<RateLimit>);
+
+impl RateLimits {
+    pub fn record(&mut self, seen: RateLimit) {
+        match self
+            .0
+            .iter_mut()
+            .find(|known| known.resource == seen.resource)
+        {
+            Some(known) if seen.remaining < known.remaining => *known = seen,
+            Some(_) => {}
+            None => self.0.push(seen),
+        }
+    }
+
+    pub fn merge(&mut self, other: &RateLimits) {
+        for limit in other.iter() {
+            self.record(*limit);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RateLimit> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, resource: RateResource) -> Option<RateLimit> {
+        self.iter()
+            .find(|limit| limit.resource == resource)
+            .copied()
+    }
+
+    // The quota polls wait on; when several are low, the one resetting last.
+    pub fn holding(&self, now: i64) -> Option<RateLimit> {
+        self.iter()
+            .filter(|limit| limit.holding(now))
+            .max_by_key(|limit| limit.reset)
+            .copied()
+    }
+
+    // Whether any quota ran out completely.
+    pub fn spent(&self) -> bool {
+        self.iter().any(|limit| limit.remaining == 0)
+    }
+}
+
+// A code host the clock can show work from. Calls block, so providers only
+// run on the fetch pool.
+pub trait Provider: Send {
+    fn name(&self) -> &str;
+    fn capabilities(&self) -> Capabilities;
+    // Checks the credentials and remembers the account for later queries.
+    fn auth(&mut self) -> Result<(), ChronoError>;
+    fn fetch_prs(&self, limit: usize) -> Result<Vec<WorkItem>, String>;
+    fn fetch_reviews(&self, _limit: usize) -> Result<Vec<WorkItem>, String> {
+        Ok(Vec::new())
+    }
+    // Open issues assigned to the account.
+    fn fetch_issues(&self, _limit: usize) -> Result<Vec<WorkItem>, String> {
+        Ok(Vec::new())
+    }
+    fn fetch_ci(&self, _prs: &[WorkItem]) -> Result<Vec<CiStatus>, String> {
+        Ok(Vec::new())
+    }
+    fn fetch_decisions(&self, _prs: &[WorkItem]) -> Result<Vec<ReviewStatus>, String> {
+        Ok(Vec::new())
+    }
+    // Web URLs of the PRs that have merge conflicts.
+    fn fetch_conflicts(&self, _prs: &[WorkItem]) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
+    // `(key prefix, base URL)` pairs for linking issue keys in titles, like
+    // the configured Jira URLs.
+    fn issue_links(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProviderFetch {
+    pub capabilities: Capabilities,
+    pub prs: Vec<WorkItem>,
+    pub reviews: Vec<WorkItem>,
+    pub issues: Vec<WorkItem>,
+    pub ci: Vec<CiStatus>,
+    pub decisions: Vec<ReviewStatus>,
+    pub conflicts: Vec<String>,
+    pub issue_links: Vec<(String, String)>,
+}
+
+impl ProviderFetch {
+    pub fn label(&mut self, account: &str) {
+        for item in self
+            .prs
+            .iter_mut()
+            .chain(self.reviews.iter_mut())
+            .chain(self.issues.iter_mut())
+        {
+            item.account = Some(account.to_string());
+        }
+    }
+}
+
+// RFC 3339 timestamps as the forge APIs return them, as Unix seconds.
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|at| at.timestamp())
+}
+
+// GitHub's issue search results, read by the native provider and the web
+// build alike.
+#[cfg(feature = "network")]
+pub fn search_items(json: &serde_json::Value, limit: usize) -> Vec<WorkItem> {
+    json.get("items")
+        .and_then(|items| items.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let title = item.get("title").and_then(|t| t.as_str())?;
+                    let url = item.get("html_url").and_then(|u| u.as_str())?;
+                    Some(WorkItem {
+                        title: title.to_string(),
+                        url: url.to_string(),
+                        repo: item
+                            .get("repository_url")
+                            .and_then(|v| v.as_str())
+                            .and_then(|v| v.strip_prefix("https://api.github.com/repos/"))
+                            .map(str::to_string),
+                        number: item.get("number").and_then(|v| v.as_u64()),
+                        updated_at: item
+                            .get("updated_at")
+                            .and_then(|v| v.as_str())
+                            .and_then(parse_timestamp),
+                        avatar_url: item
+                            .get("user")
+                            .and_then(|u| u.get("avatar_url"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        ..Default::default()
+                    })
+                })
+                .take(limit)
+                .collect::<Vec<WorkItem>>()
+        })
+        .unwrap_or_default()
+}
+
+// Fails only when authentication does; a failing query only empties its
+// part.
+pub fn fetch(provider: &mut dyn Provider, pr_limit: usize) -> Result<ProviderFetch, ChronoError> {
+    let _span = tracing::debug_span!("fetch", provider = provider.name()).entered();
+    if let Err(e) = provider.auth() {
+        log!("{}: {}", provider.name(), e);
+        return Err(e);
+    }
+    let capabilities = provider.capabilities();
+    let report =
+        |what: &str, e: String| log!("{}: failed to fetch {}: {}", provider.name(), what, e);
+
+    let mut result = ProviderFetch {
+        capabilities,
+        issue_links: provider.issue_links(),
+        ..Default::default()
+    };
+    if capabilities.prs {
+        result.prs = provider.fetch_prs(pr_limit).unwrap_or_else(|e| {
+            report("PRs", e);
+            Vec::new()
+        });
+    }
+    if capabilities.reviews {
+        result.reviews = provider.fetch_reviews(10).unwrap_or_else(|e| {
+            report("review requests", e);
+            Vec::new()
+        });
+    }
+    if capabilities.issues {
+        result.issues = provider.fetch_issues(pr_limit).unwrap_or_else(|e| {
+            report("issues", e);
+            Vec::new()
+        });
+    }
+    if capabilities.ci && !result.prs.is_empty() {
+        result.ci = provider.fetch_ci(&result.prs).unwrap_or_else(|e| {
+            report("CI status", e);
+            Vec::new()
+        });
+    }
+    if capabilities.decisions && !result.prs.is_empty() {
+        result.decisions = provider.fetch_decisions(&result.prs).unwrap_or_else(|e| {
+            report("review decisions", e);
+            Vec::new()
+        });
+    }
+    if capabilities.conflicts && !result.prs.is_empty() {
+        result.conflicts = provider.fetch_conflicts(&result.prs).unwrap_or_else(|e| {
+            report("mergeability", e);
+            Vec::new()
+        });
+    }
+    Ok(result)
+}
+
+// Providers besides GitHub, which the fetch thread drives directly for its
+// GitHub-only extras (team review load, milestones).
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn registry(config: &crate::config::Config) -> Vec<Box<dyn Provider>> {
+    let mut providers = config
+        .forges
+        .iter()
+        .cloned()
+        .map(|instance| {
+            Box::new(crate::forges::ForgeProvider::new(
+                instance,
+                config.include_drafts,
+            )) as Box<dyn Provider>
+        })
+        .collect::<Vec<_>>();
+    if config.linear {
+        providers.push(Box::new(crate::linear::LinearProvider::new(
+            config.linear_api_key.clone(),
+        )));
+    }
+    providers
+}
+
+// Tries per request. Transient failures wait a growing, jittered pause
+// before the next try so clocks on one network do not retry in step.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+const ATTEMPTS: u32 = 3;
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+const BACKOFF_MS: u64 = 500;
+
+// No answer at all (timeouts, refused or dropped connections) or a 5xx.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn is_transient(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::Status(code, _) => *code >= 500,
+        ureq::Error::Transport(_) => true,
+    }
+}
+
+// Sends `request` (a POST when there is a body) until it succeeds, fails
+// for good (a rejected token comes back at once), or runs out of tries.
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+pub fn send(
+    request: &ureq::Request,
+    body: Option<&str>,
+) -> Result<ureq::Response, Box<ureq::Error>> {
+    let mut attempt = 0;
+    loop {
+        let result = match body {
+            Some(body) => request.clone().send_string(body),
+            None => request.clone().call(),
+        };
+        match result {
+            Err(e) if is_transient(&e) && attempt + 1 < ATTEMPTS => {
+                tracing::debug!(url = request.url(), error = %e, "retrying request");
+                std::thread::sleep(backoff(attempt));
+                attempt += 1;
+            }
+            result => return result.map_err(Box::new),
+        }
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn backoff(attempt: u32) -> std::time::Duration {
+    let base = BACKOFF_MS << attempt;
+    let jitter = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.subsec_nanos() as u64)
+        % base;
+    std::time::Duration::from_millis(base + jitter)
+}
+
+// A provider with canned answers, for testing what the fetch does with
+// them without a network.
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    pub struct MockProvider {
+        pub capabilities: Capabilities,
+        pub auth_error: Option<ChronoError>,
+        pub prs: Vec<WorkItem>,
+        pub reviews: Vec<WorkItem>,
+        pub ci: Vec<CiStatus>,
+        // URLs of the PRs in conflict.
+        pub conflicts: Vec<String>,
+        // Queries that fail, by the names in `query`.
+        pub failing: Vec<&'static str>,
+        // Queries answered so far.
+        pub queries: Cell<usize>,
+    }
+
+    impl MockProvider {
+        // A PR per title, at `https://example.com/<n>`.
+        pub fn with_prs(titles: &[&str]) -> MockProvider {
+            MockProvider {
+                capabilities: Capabilities {
+                    prs: true,
+                    ..Default::default()
+                },
+                prs: titles
+                    .iter()
+                    .enumerate()
+                    .map(|(n, title)| WorkItem {
+                        title: title.to_string(),
+                        url: format!("https://example.com/{}", n),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }
+        }
+
+        fn query<T>(&self, name: &str, answer: T) -> Result<T, String> {
+            self.queries.set(self.queries.get() + 1);
+            if self.failing.contains(&name) {
+                Err(format!("{} failed", name))
+            } else {
+                Ok(answer)
+            }
+        }
+    }
+
+    impl Provider for MockProvider {
+        fn name(&self) -> &str {
+            "Mock"
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            self.capabilities
+        }
+
+        fn auth(&mut self) -> Result<(), ChronoError> {
+            self.auth_error.clone().map_or(Ok(()), Err)
+        }
+
+        fn fetch_prs(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+            self.query("prs", self.prs.iter().take(limit).cloned().collect())
+        }
+
+        fn fetch_reviews(&self, limit: usize) -> Result<Vec<WorkItem>, String> {
+            self.query(
+                "reviews",
+                self.reviews.iter().take(limit).cloned().collect(),
+            )
+        }
+
+        fn fetch_ci(&self, prs: &[WorkItem]) -> Result<Vec<CiStatus>, String> {
+            let ci = self
+                .ci
+                .iter()
+                .filter(|status| prs.iter().any(|pr| pr.url == status.url))
+                .cloned()
+                .collect();
+            self.query("ci", ci)
+        }
+
+        fn fetch_conflicts(&self, prs: &[WorkItem]) -> Result<Vec<String>, String> {
+            let conflicts = prs
+                .iter()
+                .filter(|pr| self.conflicts.contains(&pr.url))
+                .map(|pr| pr.url.clone())
+                .collect();
+            self.query("conflicts", conflicts)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockProvider;
+    use super::*;
+
+    #[test]
+    fn fetches_only_what_the_provider_supports() {
+        let mut provider = MockProvider::with_prs(&["one", "two", "three"]);
+        provider.capabilities.ci = true;
+        provider.capabilities.conflicts = true;
+        provider.reviews = provider.prs.clone();
+        provider.ci = vec![CiStatus {
+            url: "https://example.com/1".to_string(),
+            state: CiState::Failure,
+        }];
+        provider.conflicts = vec!["https://example.com/2".to_string()];
+
+        let fetched = fetch(&mut provider, 2).unwrap();
+        assert_eq!(fetched.capabilities, provider.capabilities);
+        let titles: Vec<&str> = fetched.prs.iter().map(|pr| pr.title.as_str()).collect();
+        assert_eq!(titles, ["one", "two"]);
+        // Reviews are not among the capabilities, so never asked for.
+        assert!(fetched.reviews.is_empty());
+        assert_eq!(fetched.ci, provider.ci);
+        // The conflicting PR fell outside the limit.
+        assert!(fetched.conflicts.is_empty());
+        assert_eq!(provider.queries.get(), 3);
+    }
+
+    #[test]
+    fn a_failing_query_only_empties_its_part() {
+        let mut provider = MockProvider::with_prs(&["one"]);
+        provider.capabilities.reviews = true;
+        provider.capabilities.ci = true;
+        provider.reviews = provider.prs.clone();
+        provider.failing = vec!["ci"];
+        let fetched = fetch(&mut provider, 5).unwrap();
+        assert_eq!(fetched.prs.len(), 1);
+        assert_eq!(fetched.reviews.len(), 1);
+        assert!(fetched.ci.is_empty());
+
+        // Nothing to check CI for without PRs.
+        provider.failing = vec!["prs"];
+        provider.queries.set(0);
+        let fetched = fetch(&mut provider, 5).unwrap();
+        assert!(fetched.prs.is_empty());
+        assert_eq!(provider.queries.get(), 2);
+    }
+
+    #[test]
+    fn failed_auth_fails_the_fetch_before_any_query() {
+        let mut provider = MockProvider::with_prs(&["one"]);
+        provider.auth_error = Some(ChronoError::Auth("status 401".to_string()));
+        assert_eq!(
+            fetch(&mut provider, 5).unwrap_err(),
+            ChronoError::Auth("status 401".to_string())
+        );
+        assert_eq!(provider.queries.get(), 0);
+    }
+
+    #[test]
+    fn rate_limits_are_kept_per_resource() {
+        let limit = |resource, remaining, reset| RateLimit {
+            resource,
+            remaining,
+            reset,
+        };
+        let mut limits = RateLimits::default();
+        limits.record(limit(RateResource::Core, 4990, 3600));
+        limits.record(limit(RateResource::Search, 12, 60));
+        limits.record(limit(RateResource::Core, 4980, 3600));
+        limits.record(limit(RateResource::Core, 4985, 3600));
+        assert_eq!(
+            limits.get(RateResource::Core).map(|l| l.remaining),
+            Some(4980)
+        );
+        assert_eq!(
+            limits.get(RateResource::Search).map(|l| l.remaining),
+            Some(12)
+        );
+        // A search quota this far down is normal, not a reason to hold polls.
+        assert_eq!(limits.holding(0), None);
+
+        limits.record(limit(RateResource::Search, 2, 60));
+        assert_eq!(
+            limits.holding(0).map(|l| l.resource),
+            Some(RateResource::Search)
+        );
+        assert_eq!(limits.holding(60), None);
+        assert!(!limits.spent());
+        assert_eq!(
+            RateResource::from_header(Some("graphql")),
+            RateResource::Graphql
+        );
+        assert_eq!(RateResource::from_header(None), RateResource::Core);
+    }
+
+    #[test]
+    fn meta_line_shows_what_is_known() {
+        let item = WorkItem {
+            repo: Some("dmytropaduchak/chrono".to_string()),
+            number: Some(123),
+            updated_at: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            item.meta_line(1_000 + 2 * 86_400).as_deref(),
+            Some("chrono#123 \u{b7} 2d")
+        );
+        let bare = WorkItem {
+            number: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(bare.meta_line(0).as_deref(), Some("#7"));
+        assert_eq!(WorkItem::default().meta_line(0), None);
+    }
+
+    #[test]
+    fn stale_after_whole_days_without_updates() {
+        let item = WorkItem {
+            updated_at: Some(1_000),
+            ..Default::default()
+        };
+        assert!(!item.is_stale(1_000 + 7 * 86_400 - 1, 7));
+        assert!(item.is_stale(1_000 + 7 * 86_400, 7));
+        assert!(!WorkItem::default().is_stale(i64::MAX, 7));
+    }
+}
@@ -0,0 +1,238 @@
+// Records a few seconds of the board and encodes them as an animated GIF or
+// APNG, for sharing a theme setup: `chrono --record clock.gif`.
+use macroquad::texture::Image;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Frames are scaled down by whole steps to at most this width, which keeps
+// a recording small in memory and on disk.
+const MAX_WIDTH: usize = 800;
+// NeuQuant sampling; 10 is the gif crate's balance of speed and quality.
+const GIF_SPEED: i32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    Gif,
+    Apng,
+}
+
+impl RecordFormat {
+    pub fn from_path(path: &Path) -> Option<RecordFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "gif" => Some(RecordFormat::Gif),
+            "png" | "apng" => Some(RecordFormat::Apng),
+            _ => None,
+        }
+    }
+}
+
+// Opaque RGBA, top row first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
+impl Frame {
+    // Screen data comes back from the GPU bottom row first.
+    pub fn from_screen(image: &Image) -> Frame {
+        let (width, height) = (image.width as usize, image.height as usize);
+        let step = width.div_ceil(MAX_WIDTH).max(1);
+        let (out_width, out_height) = (width / step, height / step);
+        let mut rgba = Vec::with_capacity(out_width * out_height * 4);
+        for y in 0..out_height {
+            let row = height - 1 - y * step;
+            for x in 0..out_width {
+                let idx = (row * width + x * step) * 4;
+                rgba.extend_from_slice(&image.bytes[idx..idx + 3]);
+                rgba.push(255);
+            }
+        }
+        Frame {
+            width: out_width,
+            height: out_height,
+            rgba,
+        }
+    }
+}
+
+pub fn encode(
+    format: RecordFormat,
+    frames: &[Frame],
+    fps: f64,
+    out: impl Write,
+) -> Result<(), String> {
+    let first = frames.first().ok_or("no frames")?;
+    let (width, height) = (first.width, first.height);
+    match format {
+        RecordFormat::Gif => {
+            let mut encoder = gif::Encoder::new(out, width as u16, height as u16, &[])
+                .map_err(|e| e.to_string())?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|e| e.to_string())?;
+            // GIF delays are in hundredths of a second.
+            let delay = (100.0 / fps).round().max(2.0) as u16;
+            for frame in frames {
+                let mut rgba = frame.rgba.clone();
+                let mut gif_frame =
+                    gif::Frame::from_rgba_speed(width as u16, height as u16, &mut rgba, GIF_SPEED);
+                gif_frame.delay = delay;
+                encoder.write_frame(&gif_frame).map_err(|e| e.to_string())?;
+            }
+        }
+        RecordFormat::Apng => {
+            let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .set_animated(frames.len() as u32, 0)
+                .map_err(|e| e.to_string())?;
+            encoder
+                .set_frame_delay((1000.0 / fps).round() as u16, 1000)
+                .map_err(|e| e.to_string())?;
+            let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+            for frame in frames {
+                writer
+                    .write_image_data(&frame.rgba)
+                    .map_err(|e| e.to_string())?;
+            }
+            writer.finish().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+// Captures frames at `fps` for `secs` from the first frame on. Times are
+// `get_time()` seconds.
+pub struct Recorder {
+    path: PathBuf,
+    format: RecordFormat,
+    secs: f64,
+    fps: f64,
+    started_at: Option<f64>,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf, secs: f64, fps: f64) -> Option<Self> {
+        let Some(format) = RecordFormat::from_path(&path) else {
+            log!(
+                "Not recording to {}: use a .gif or .png file",
+                path.display()
+            );
+            return None;
+        };
+        Some(Recorder {
+            path,
+            format,
+            secs,
+            fps,
+            started_at: None,
+            frames: Vec::new(),
+        })
+    }
+
+    // Takes a frame from `screen` when one is due; true once the recording
+    // is complete.
+    pub fn tick(&mut self, now: f64, screen: impl FnOnce() -> Image) -> bool {
+        let started_at = *self.started_at.get_or_insert(now);
+        if now - started_at >= self.secs {
+            return true;
+        }
+        if now >= started_at + self.frames.len() as f64 / self.fps {
+            let frame = Frame::from_screen(&screen());
+            // A resized window would change the frame size mid-animation.
+            if self
+                .frames
+                .first()
+                .is_none_or(|first| (first.width, first.height) == (frame.width, frame.height))
+            {
+                self.frames.push(frame);
+            }
+        }
+        false
+    }
+
+    // Encodes on a thread of its own so the board keeps running.
+    pub fn finish(self) {
+        log!(
+            "Encoding {} frames to {}",
+            self.frames.len(),
+            self.path.display()
+        );
+        std::thread::spawn(move || {
+            let result = std::fs::File::create(&self.path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| encode(self.format, &self.frames, self.fps, BufWriter::new(file)));
+            match result {
+                Ok(()) => log!("Saved the recording to {}", self.path.display()),
+                Err(e) => log!("Recording to {} failed: {}", self.path.display(), e),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<Frame> {
+        [0u8, 200]
+            .iter()
+            .map(|&shade| Frame {
+                width: 4,
+                height: 2,
+                rgba: [shade, 100, 50, 255].repeat(8),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn picks_the_format_from_the_extension() {
+        let format = |path: &str| RecordFormat::from_path(Path::new(path));
+        assert_eq!(format("clock.GIF"), Some(RecordFormat::Gif));
+        assert_eq!(format("out/clock.png"), Some(RecordFormat::Apng));
+        assert_eq!(format("clock.mp4"), None);
+        assert_eq!(format("clock"), None);
+    }
+
+    #[test]
+    fn flips_and_scales_screen_frames() {
+        // 1000 wide scales down by 2; the bottom row comes first.
+        let mut bytes = vec![0u8; 1000 * 4 * 4];
+        let top = 3 * 1000 * 4;
+        bytes[top..top + 4].copy_from_slice(&[9, 8, 7, 0]);
+        let image = Image {
+            bytes,
+            width: 1000,
+            height: 4,
+        };
+        let frame = Frame::from_screen(&image);
+        assert_eq!((frame.width, frame.height), (500, 2));
+        assert_eq!(frame.rgba.len(), 500 * 2 * 4);
+        assert_eq!(frame.rgba[..4], [9, 8, 7, 255]);
+        assert!(frame.rgba[4..].chunks(4).all(|px| px == [0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn encodes_every_frame() {
+        let mut gif = Vec::new();
+        encode(RecordFormat::Gif, &frames(), 10.0, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            count += 1;
+        }
+        assert_eq!(count, 2);
+
+        let mut apng = Vec::new();
+        encode(RecordFormat::Apng, &frames(), 10.0, &mut apng).unwrap();
+        assert!(apng.windows(4).any(|chunk| chunk == b"acTL"));
+        assert!(encode(RecordFormat::Gif, &[], 10.0, Vec::new()).is_err());
+    }
+}
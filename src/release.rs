@@ -0,0 +1,65 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+// A milestone fetched from GitHub or an entry from the configured release
+// calendar, e.g. `v3.0 freeze=2026-11-01`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Release {
+    pub title: String,
+    pub due: DateTime<FixedOffset>,
+    pub url: Option<String>,
+}
+
+pub fn parse_calendar(source: &str, offset: FixedOffset) -> Vec<Release> {
+    source
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry.split_once('=').and_then(|(title, date)| {
+                let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+                let due = date
+                    .and_hms_opt(0, 0, 0)?
+                    .and_local_timezone(offset)
+                    .single()?;
+                Some(Release {
+                    title: title.trim().to_string(),
+                    due,
+                    url: None,
+                })
+            });
+            if parsed.is_none() {
+                log!("Ignoring release calendar entry `{}`", entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+pub fn next_release<'a>(
+    releases: impl IntoIterator<Item = &'a Release>,
+    now: DateTime<FixedOffset>,
+) -> Option<&'a Release> {
+    releases
+        .into_iter()
+        .filter(|release| release.due > now)
+        .min_by_key(|release| release.due)
+}
+
+// "V3.0 FREEZE IN 4D", switching to hours and minutes on the last day.
+pub fn countdown_label(release: &Release, now: DateTime<FixedOffset>) -> String {
+    let remaining = release.due - now;
+    let amount = if remaining.num_days() >= 1 {
+        format!("{}D", remaining.num_days())
+    } else if remaining.num_hours() >= 1 {
+        format!("{}H", remaining.num_hours())
+    } else {
+        format!("{}M", remaining.num_minutes().max(1))
+    };
+    let title = release
+        .title
+        .to_uppercase()
+        .chars()
+        .take(14)
+        .collect::<String>();
+    format!("{} IN {}", title.trim(), amount)
+}
@@ -0,0 +1,865 @@
+// Drawing on the cell grid: the theme, pixel text and the board-wide
+// effects. The theme and board rect for the frame being drawn are kept in a
+// thread-local so the draw calls do not have to pass them around.
+use crate::cells::{self, CellShape};
+use crate::glyphs::{glyph_bounds, glyph_pattern, space_width_cols};
+use crate::speckles;
+use macroquad::prelude::*;
+use std::cell::RefCell;
+
+pub const BACKGROUND_COLOR: Color = Color::new(0.06, 0.07, 0.08, 1.0);
+pub const INACTIVE_COLOR: Color = Color::new(0.12, 0.13, 0.15, 1.0);
+// The accents C cycles through, starting with the first.
+pub const ACCENT_PALETTE: [Color; 10] = [
+    Color::new(0.09, 0.42, 0.2, 1.0),
+    Color::new(0.19, 0.63, 0.31, 1.0),
+    Color::new(0.25, 0.77, 0.39, 1.0),
+    Color::new(0.61, 0.91, 0.66, 1.0),
+    Color::new(0.18, 0.53, 0.88, 1.0),
+    Color::new(0.44, 0.67, 0.96, 1.0),
+    Color::new(0.96, 0.68, 0.24, 1.0),
+    Color::new(0.95, 0.55, 0.4, 1.0),
+    Color::new(0.78, 0.56, 0.95, 1.0),
+    Color::new(0.88, 0.45, 0.74, 1.0),
+];
+
+// Colors that carry a status: CI and workflow runs, review decisions and
+// staleness on PR titles, the GitHub button, the notification badge and
+// warnings. Set apart from the accent so they can be told apart without
+// red and green.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusPalette {
+    pub success: Color,
+    pub pending: Color,
+    pub failure: Color,
+    // PR titles, lighter than the CI blocks next to them.
+    pub approved: Color,
+    pub changes_requested: Color,
+}
+
+impl StatusPalette {
+    pub const DEFAULT: StatusPalette = StatusPalette {
+        success: Color::new(0.25, 0.77, 0.39, 1.0),
+        pending: Color::new(0.96, 0.68, 0.24, 1.0),
+        failure: Color::new(0.93, 0.33, 0.31, 1.0),
+        approved: Color::new(0.61, 0.91, 0.66, 1.0),
+        changes_requested: Color::new(0.95, 0.55, 0.4, 1.0),
+    };
+    // Okabe-Ito's sky blue, yellow and vermillion, which stay distinct with
+    // either kind of red-green color blindness.
+    pub const OKABE_ITO: StatusPalette = StatusPalette {
+        success: Color::new(0.34, 0.71, 0.91, 1.0),
+        pending: Color::new(0.94, 0.89, 0.26, 1.0),
+        failure: Color::new(0.84, 0.37, 0.0, 1.0),
+        approved: Color::new(0.6, 0.82, 0.95, 1.0),
+        changes_requested: Color::new(0.93, 0.58, 0.36, 1.0),
+    };
+}
+
+impl Default for StatusPalette {
+    fn default() -> Self {
+        StatusPalette::DEFAULT
+    }
+}
+
+impl std::str::FromStr for StatusPalette {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "default" | "standard" => Ok(StatusPalette::DEFAULT),
+            "deuteranopia" | "protanopia" | "okabe-ito" | "colorblind" => {
+                Ok(StatusPalette::OKABE_ITO)
+            }
+            _ => Err(format!("unknown status palette {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PixelGrid {
+    pub cell: f32,
+    pub gap: f32,
+}
+
+impl PixelGrid {
+    pub fn step(self) -> f32 {
+        self.cell + self.gap
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub background_color: Color,
+    pub inactive_color: Color,
+    pub active_color: Color,
+    pub noise_color: Color,
+    // The AM/PM text; None draws it in a softer accent.
+    pub am_pm_color: Option<Color>,
+    pub status: StatusPalette,
+    pub active_alpha: f32,
+    pub active_alpha_jitter: f32,
+    // Floor for the lit cells and the dim PR meta text.
+    pub min_alpha: f32,
+    // PR list font sizes are multiplied by this.
+    pub text_scale: f32,
+    pub hour_wave: bool,
+    pub hour_chirp: bool,
+    pub cell_shape: CellShape,
+    pub speckle_placement: speckles::Placement,
+    pub speckle_intensity: speckles::Intensity,
+}
+
+// Partial theme applied on top of the base theme (e.g. by color rules).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThemeOverride {
+    pub background_color: Option<Color>,
+    pub inactive_color: Option<Color>,
+    pub active_color: Option<Color>,
+    pub noise_color: Option<Color>,
+    pub am_pm_color: Option<Color>,
+    // The hour-change effects, so a dashboard or theme file can turn them
+    // on or off.
+    pub hour_wave: Option<bool>,
+    pub hour_chirp: Option<bool>,
+}
+
+impl Theme {
+    pub fn with_override(self, theme_override: &ThemeOverride) -> Theme {
+        Theme {
+            background_color: theme_override
+                .background_color
+                .unwrap_or(self.background_color),
+            inactive_color: theme_override.inactive_color.unwrap_or(self.inactive_color),
+            active_color: theme_override.active_color.unwrap_or(self.active_color),
+            noise_color: theme_override.noise_color.unwrap_or(self.noise_color),
+            am_pm_color: theme_override.am_pm_color.or(self.am_pm_color),
+            hour_wave: theme_override.hour_wave.unwrap_or(self.hour_wave),
+            hour_chirp: theme_override.hour_chirp.unwrap_or(self.hour_chirp),
+            ..self
+        }
+    }
+
+    // White cells on black and nothing in between, for e-ink panels.
+    pub fn one_bit(self) -> Theme {
+        Theme {
+            background_color: BLACK,
+            inactive_color: BLACK,
+            active_color: WHITE,
+            noise_color: WHITE,
+            am_pm_color: None,
+            active_alpha: 1.0,
+            active_alpha_jitter: 0.0,
+            hour_wave: false,
+            cell_shape: CellShape::Square,
+            ..self
+        }
+    }
+
+    // Solid white on black with larger PR text, for poor screens and low
+    // vision. Status colors are kept so CI and reviews still read.
+    pub fn high_contrast(self) -> Theme {
+        Theme {
+            background_color: BLACK,
+            inactive_color: Color::new(0.16, 0.16, 0.16, 1.0),
+            active_color: WHITE,
+            noise_color: WHITE,
+            am_pm_color: Some(WHITE),
+            active_alpha: 1.0,
+            active_alpha_jitter: 0.0,
+            min_alpha: 0.8,
+            text_scale: 1.3,
+            ..self
+        }
+    }
+
+    // Blends the lit colors toward a warm amber; 0.0 leaves the theme as is.
+    pub fn warmed(self, amount: f32) -> Theme {
+        let warm = |color: Color| {
+            let mix = |from: f32, to: f32| from + (to - from) * amount * 0.5;
+            Color::new(
+                mix(color.r, 1.0),
+                mix(color.g, 0.62),
+                mix(color.b, 0.3),
+                color.a,
+            )
+        };
+        Theme {
+            active_color: warm(self.active_color),
+            noise_color: warm(self.noise_color),
+            am_pm_color: self.am_pm_color.map(warm),
+            ..self
+        }
+    }
+
+    // Scales every board color toward black; 1.0 leaves the theme as is.
+    pub fn dimmed(self, brightness: f32) -> Theme {
+        let dim = |color: Color| {
+            Color::new(
+                color.r * brightness,
+                color.g * brightness,
+                color.b * brightness,
+                color.a,
+            )
+        };
+        Theme {
+            background_color: dim(self.background_color),
+            inactive_color: dim(self.inactive_color),
+            active_color: dim(self.active_color),
+            noise_color: dim(self.noise_color),
+            am_pm_color: self.am_pm_color.map(dim),
+            ..self
+        }
+    }
+
+    // The theme as drawn this frame. High contrast goes under the warmth,
+    // sunrise and brightness so it still fades at night; e-ink ends up
+    // black and white whatever came before.
+    pub fn for_frame(
+        self,
+        high_contrast: bool,
+        eink: bool,
+        warmth: f32,
+        sunrise: &ThemeOverride,
+        brightness: f32,
+    ) -> Theme {
+        let theme = if high_contrast && !eink {
+            self.high_contrast()
+        } else {
+            self
+        }
+        .warmed(warmth)
+        .with_override(sunrise)
+        .dimmed(brightness);
+        if eink {
+            theme.one_bit()
+        } else {
+            theme
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FrameContext {
+    pub theme: Theme,
+    pub container: Rect,
+}
+
+impl Default for FrameContext {
+    fn default() -> Self {
+        let theme = Theme {
+            background_color: Color {
+                r: 0.06,
+                g: 0.07,
+                b: 0.08,
+                a: 1.0,
+            },
+            inactive_color: Color {
+                r: 0.12,
+                g: 0.13,
+                b: 0.15,
+                a: 1.0,
+            },
+            active_color: Color {
+                r: 0.2,
+                g: 0.85,
+                b: 0.82,
+                a: 1.0,
+            },
+            noise_color: Color {
+                r: 0.2,
+                g: 0.85,
+                b: 0.82,
+                a: 1.0,
+            },
+            am_pm_color: None,
+            status: StatusPalette::DEFAULT,
+            active_alpha: 0.82,
+            active_alpha_jitter: 0.4,
+            min_alpha: 0.2,
+            text_scale: 1.0,
+            hour_wave: false,
+            hour_chirp: false,
+            cell_shape: CellShape::Square,
+            speckle_placement: speckles::Placement::Checker,
+            speckle_intensity: speckles::Intensity::DEFAULT,
+        };
+
+        FrameContext {
+            theme,
+            container: Rect::new(20.0, 20.0, 440.0, 220.0),
+        }
+    }
+}
+
+thread_local! {
+    pub(crate) static FRAME_CONTEXT: RefCell<FrameContext> = RefCell::new(FrameContext::default());
+}
+
+pub fn update_context(theme: Theme, container: Rect) {
+    FRAME_CONTEXT.with(|ctx| {
+        let mut ctx = ctx.borrow_mut();
+        ctx.theme = theme;
+        ctx.container = container;
+    });
+}
+
+pub fn grid_from_height(target_height: f32, gap_ratio: f32) -> PixelGrid {
+    let cell = (target_height / 7.0).round().max(1.0);
+    let gap = (cell * gap_ratio).round().max(1.0);
+    PixelGrid { cell, gap }
+}
+
+pub fn draw_background(board_grid: PixelGrid) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        clear_background(ctx.theme.background_color);
+        draw_grid(ctx.container, board_grid, ctx.theme.inactive_color);
+    });
+}
+
+pub fn draw_noise_pixels(board_grid: PixelGrid) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let rect = ctx.container;
+        let step = (board_grid.step() * 1.4).round().max(6.0) as i32;
+        let dot = (board_grid.cell * 0.35).max(2.0);
+        let intensity = ctx.theme.speckle_intensity;
+        let threshold = intensity.count(22) as i32;
+        for y in (rect.y as i32..(rect.y + rect.h) as i32).step_by(step as usize) {
+            for x in (rect.x as i32..(rect.x + rect.w) as i32).step_by(step as usize) {
+                let hash = (x * 37 + y * 101) & 255;
+                if hash < threshold {
+                    let alpha = intensity.alpha(0.03 + (hash as f32 / 255.0) * 0.04);
+                    draw_rectangle(
+                        x as f32 + 2.0,
+                        y as f32 + 2.0,
+                        dot,
+                        dot,
+                        Color::new(
+                            ctx.theme.noise_color.r,
+                            ctx.theme.noise_color.g,
+                            ctx.theme.noise_color.b,
+                            alpha,
+                        ),
+                    );
+                }
+            }
+        }
+    });
+}
+
+pub fn draw_active_speckles(board_grid: PixelGrid, minute_seed: i32, blocked: &[Rect]) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let rect = ctx.container;
+        let step = board_grid.step();
+        let cols = (rect.w / step).ceil() as i32;
+        let rows = (rect.h / step).ceil() as i32;
+        let intensity = ctx.theme.speckle_intensity;
+        let picks = speckles::pick(
+            ctx.theme.speckle_placement,
+            cols,
+            rows,
+            intensity.seed(minute_seed),
+            intensity.count(9),
+        );
+
+        for (i, (row, col)) in picks.iter().enumerate() {
+            let alpha = if i < 3 {
+                0.35 + ((row * 13 + col * 7) & 15) as f32 / 120.0
+            } else if i < 6 {
+                0.65 + ((row * 23 + col * 11) & 15) as f32 / 120.0
+            } else {
+                0.95 + ((row * 31 + col * 17) & 7) as f32 / 100.0
+            };
+            let speck_rect = Rect::new(
+                rect.x + *col as f32 * step,
+                rect.y + *row as f32 * step,
+                board_grid.cell,
+                board_grid.cell,
+            );
+            if rect_overlaps_any(speck_rect, blocked) {
+                continue;
+            }
+            draw_cell(
+                speck_rect.x,
+                speck_rect.y,
+                speck_rect.w,
+                Color::new(
+                    ctx.theme.active_color.r,
+                    ctx.theme.active_color.g,
+                    ctx.theme.active_color.b,
+                    intensity.alpha(alpha),
+                ),
+            );
+        }
+    });
+}
+
+pub fn draw_grid(rect: Rect, grid: PixelGrid, color: Color) {
+    let step = grid.step();
+    let cols = (rect.w / step).ceil() as i32;
+    let rows = (rect.h / step).ceil() as i32;
+    for row in 0..rows {
+        for col in 0..cols {
+            let x = rect.x + col as f32 * step;
+            let y = rect.y + row as f32 * step;
+            draw_cell(x, y, grid.cell, color);
+        }
+    }
+}
+
+pub fn draw_cell(x: f32, y: f32, size: f32, color: Color) {
+    let shape = FRAME_CONTEXT.with(|ctx| ctx.borrow().theme.cell_shape);
+    cells::draw(shape, x, y, size, color);
+}
+
+pub fn draw_pixel_text(text: &str, origin: Vec2, grid: PixelGrid, color: Color, cutout: bool) {
+    draw_glyphs(text, origin, grid, color, cutout, None);
+}
+
+// Lays `text` out as usual but leaves the cells of `hidden` dark, so a
+// blinking character does not move the ones after it.
+pub fn draw_pixel_text_without(
+    text: &str,
+    origin: Vec2,
+    grid: PixelGrid,
+    color: Color,
+    hidden: char,
+) {
+    draw_glyphs(text, origin, grid, color, false, Some(hidden));
+}
+
+fn draw_glyphs(
+    text: &str,
+    origin: Vec2,
+    grid: PixelGrid,
+    color: Color,
+    cutout: bool,
+    hidden: Option<char>,
+) {
+    let step = grid.step();
+    let spacing = glyph_spacing(grid);
+    let mut cursor_x = origin.x;
+    for ch in text.chars() {
+        // 5x7 glyphs with pixel-based inter-character spacing.
+        let glyph = glyph_pattern(ch);
+        if let Some((min_x, max_x)) = glyph_bounds(glyph) {
+            let width_cols = (max_x - min_x + 1) as f32;
+            for (row, line) in glyph.iter().enumerate() {
+                for (col, cell) in line.chars().enumerate() {
+                    if cell == '#' && hidden != Some(ch) {
+                        let x = cursor_x + (col as f32 - min_x as f32) * step;
+                        let y = origin.y + row as f32 * step;
+                        let draw_color = if cutout {
+                            color
+                        } else {
+                            apply_active_alpha(color, x, y)
+                        };
+                        draw_cell(x, y, grid.cell, draw_color);
+                    }
+                }
+            }
+            cursor_x += width_cols * step + spacing;
+        } else {
+            cursor_x += space_width_cols() * step + spacing;
+        }
+    }
+}
+
+pub fn measure_pixel_text(text: &str, grid: PixelGrid) -> Vec2 {
+    let step = grid.step();
+    let spacing = glyph_spacing(grid);
+    let mut width = 0.0;
+    let mut count = 0usize;
+    for ch in text.chars() {
+        let glyph = glyph_pattern(ch);
+        let cols = if let Some((min_x, max_x)) = glyph_bounds(glyph) {
+            (max_x - min_x + 1) as f32
+        } else {
+            space_width_cols()
+        };
+        width += cols * step + spacing;
+        count += 1;
+    }
+    if count > 0 {
+        width -= spacing;
+    }
+    let height = step * 7.0 - grid.gap;
+    vec2(width, height)
+}
+
+pub fn collect_glyph_rects(text: &str, origin: Vec2, grid: PixelGrid) -> Vec<Rect> {
+    let step = grid.step();
+    let spacing = glyph_spacing(grid);
+    let mut rects = Vec::new();
+    let mut cursor_x = origin.x;
+    for ch in text.chars() {
+        let glyph = glyph_pattern(ch);
+        if let Some((min_x, max_x)) = glyph_bounds(glyph) {
+            let width_cols = (max_x - min_x + 1) as f32;
+            for (row, line) in glyph.iter().enumerate() {
+                for (col, cell) in line.chars().enumerate() {
+                    if cell == '#' {
+                        rects.push(Rect::new(
+                            cursor_x + (col as f32 - min_x as f32) * step,
+                            origin.y + row as f32 * step,
+                            grid.cell,
+                            grid.cell,
+                        ));
+                    }
+                }
+            }
+            cursor_x += width_cols * step + spacing;
+        } else {
+            cursor_x += space_width_cols() * step + spacing;
+        }
+    }
+    rects
+}
+
+pub fn glyph_spacing(grid: PixelGrid) -> f32 {
+    grid.step()
+}
+
+pub fn apply_active_alpha(color: Color, x: f32, y: f32) -> Color {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let hash = ((x as i32 * 29 + y as i32 * 91) & 255) as f32 / 255.0;
+        let jitter = (hash - 0.5) * 2.0 * ctx.theme.active_alpha_jitter;
+        let alpha = (ctx.theme.active_alpha + jitter).clamp(ctx.theme.min_alpha, 1.0);
+        Color::new(color.r, color.g, color.b, alpha)
+    })
+}
+
+pub fn snap_to_grid(origin: f32, value: f32, step: f32) -> f32 {
+    let offset = value - origin;
+    origin + (offset / step).round() * step
+}
+
+pub fn rect_overlaps_any(target: Rect, blocked: &[Rect]) -> bool {
+    blocked.iter().any(|rect| rects_intersect(target, *rect))
+}
+
+pub fn rects_intersect(a: Rect, b: Rect) -> bool {
+    a.x < b.x + b.w && a.x + a.w > b.x && a.y < b.y + b.h && a.y + a.h > b.y
+}
+
+pub fn point_in_rect(point: Vec2, rect: Rect) -> bool {
+    point.x >= rect.x
+        && point.x <= rect.x + rect.w
+        && point.y >= rect.y
+        && point.y <= rect.y + rect.h
+}
+
+pub fn wrap_text_to_width(text: &str, max_width: f32, font_size: u16) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let spacer = if current.is_empty() { "" } else { " " };
+        let candidate = format!("{}{}{}", current, spacer, word);
+        let candidate_width = measure_text(&candidate, None, font_size, 1.0).width;
+
+        if candidate_width <= max_width {
+            current = candidate;
+        } else {
+            if !current.is_empty() {
+                lines.push(current);
+            }
+            current = word.to_string();
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+}
+
+// The default font has no `…`, so titles end in three periods.
+pub fn truncate_text_to_width(text: &str, max_width: f32, font_size: u16) -> String {
+    if measure_text(text, None, font_size, 1.0).width <= max_width {
+        return text.to_string();
+    }
+    let mut chars = text.chars().collect::<Vec<char>>();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}...", chars.iter().collect::<String>().trim_end());
+        if measure_text(&candidate, None, font_size, 1.0).width <= max_width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+pub fn truncate_pixel_text(text: &str, max_width: f32, grid: PixelGrid) -> String {
+    if measure_pixel_text(text, grid).x <= max_width {
+        return text.to_string();
+    }
+    let mut chars = text.chars().collect::<Vec<char>>();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}…", chars.iter().collect::<String>().trim_end());
+        if measure_pixel_text(&candidate, grid).x <= max_width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+pub fn color_hex(color: Color) -> String {
+    let [r, g, b, _]: [u8; 4] = color.into();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// A diagonal band of lit cells sweeping left to right across the board.
+pub fn draw_hour_wave(board_grid: PixelGrid, progress: f32) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let rect = ctx.container;
+        let step = board_grid.step();
+        let cols = (rect.w / step).ceil() as i32;
+        let rows = (rect.h / step).ceil() as i32;
+        let width = 6.0;
+        let front = progress * (cols + rows) as f32 + width;
+        for row in 0..rows {
+            for col in 0..cols {
+                let distance = front - (col + row) as f32;
+                if !(0.0..width * 2.0).contains(&distance) {
+                    continue;
+                }
+                let alpha = 1.0 - (distance - width).abs() / width;
+                let color = ctx.theme.active_color;
+                draw_cell(
+                    rect.x + col as f32 * step,
+                    rect.y + row as f32 * step,
+                    board_grid.cell,
+                    Color::new(color.r, color.g, color.b, alpha * 0.8),
+                );
+            }
+        }
+    });
+}
+
+// Bursts at pseudo-random spots, each an expanding ring of cells that fades
+// out; colors come from a fixed festive palette rather than the theme.
+pub fn draw_fireworks(board_grid: PixelGrid, progress: f32) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let rect = ctx.container;
+        let step = board_grid.step();
+        let cols = (rect.w / step).floor().max(1.0) as i32;
+        let rows = (rect.h / step).floor().max(1.0) as i32;
+        let palette = [
+            Color::new(0.96, 0.68, 0.24, 1.0),
+            Color::new(0.93, 0.33, 0.31, 1.0),
+            Color::new(0.44, 0.67, 0.96, 1.0),
+            Color::new(0.61, 0.91, 0.66, 1.0),
+            Color::new(0.78, 0.56, 0.95, 1.0),
+        ];
+        let bursts = 10;
+        let burst_len = 0.3;
+        for burst in 0..bursts {
+            let start = burst as f32 / bursts as f32 * (1.0 - burst_len);
+            let t = (progress - start) / burst_len;
+            if !(0.0..1.0).contains(&t) {
+                continue;
+            }
+            let hash = (burst * 7919 + 104729) as u32;
+            let center_col = (hash % cols as u32) as f32;
+            let center_row = ((hash / 7) % rows as u32) as f32;
+            let color = palette[burst as usize % palette.len()];
+            let radius = 1.0 + t * 5.0;
+            let sparks = 12;
+            for spark in 0..sparks {
+                let angle = spark as f32 / sparks as f32 * std::f32::consts::TAU;
+                let col = (center_col + angle.cos() * radius).round();
+                // Sparks sag a little as they fly out.
+                let row = (center_row + angle.sin() * radius + t * t * 2.0).round();
+                if col < 0.0 || row < 0.0 || col >= cols as f32 || row >= rows as f32 {
+                    continue;
+                }
+                draw_cell(
+                    rect.x + col * step,
+                    rect.y + row * step,
+                    board_grid.cell,
+                    Color::new(color.r, color.g, color.b, 1.0 - t),
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_fits_seven_rows_in_the_height() {
+        let grid = grid_from_height(42.0, 0.25);
+        assert_eq!((grid.cell, grid.gap), (6.0, 2.0));
+        // Tiny text still gets whole cells and a gap.
+        let grid = grid_from_height(3.0, 0.25);
+        assert_eq!((grid.cell, grid.gap), (1.0, 1.0));
+    }
+
+    #[test]
+    fn measures_trimmed_glyphs_and_spacing() {
+        let grid = PixelGrid {
+            cell: 6.0,
+            gap: 2.0,
+        };
+        assert_eq!(measure_pixel_text("1", grid), vec2(24.0, 54.0));
+        assert_eq!(measure_pixel_text("11", grid), vec2(56.0, 54.0));
+        assert_eq!(measure_pixel_text("", grid).x, 0.0);
+    }
+
+    #[test]
+    fn every_glyph_fits_its_measured_box() {
+        for (cell, gap) in [(1.0, 1.0), (3.0, 1.0), (6.0, 2.0), (12.0, 3.0)] {
+            let grid = PixelGrid { cell, gap };
+            for (ch, lit, (min_x, max_x)) in crate::glyphs::tests::SNAPSHOT {
+                let text = ch.to_string();
+                let origin = vec2(10.0, 20.0);
+                let rects = collect_glyph_rects(&text, origin, grid);
+                let size = measure_pixel_text(&text, grid);
+                assert_eq!(rects.len(), lit, "{:?} at {}", ch, cell);
+                assert_eq!(size.x, (max_x - min_x + 1) as f32 * grid.step());
+                assert_eq!(size.y, 7.0 * grid.step() - gap);
+                assert!(
+                    rects.iter().all(|rect| rect.x >= origin.x
+                        && rect.y >= origin.y
+                        && rect.right() <= origin.x + size.x
+                        && rect.bottom() <= origin.y + size.y),
+                    "{:?} at {}",
+                    ch,
+                    cell
+                );
+                let right = rects.iter().map(|rect| rect.right()).fold(0.0, f32::max);
+                assert_eq!(right, origin.x + size.x - gap);
+            }
+        }
+    }
+
+    #[test]
+    fn glyph_rects_match_the_lit_cells() {
+        let grid = PixelGrid {
+            cell: 6.0,
+            gap: 2.0,
+        };
+        let rects = collect_glyph_rects("1", vec2(10.0, 20.0), grid);
+        assert_eq!(rects.len(), 10);
+        // The trimmed glyph starts at the origin.
+        assert_eq!(
+            rects.iter().map(|rect| rect.x).fold(f32::MAX, f32::min),
+            10.0
+        );
+        let width = measure_pixel_text("1", grid).x;
+        assert!(rects.iter().all(|rect| rect.x + rect.w <= 10.0 + width));
+    }
+
+    #[test]
+    fn truncates_with_an_ellipsis() {
+        let grid = PixelGrid {
+            cell: 2.0,
+            gap: 1.0,
+        };
+        let fits = measure_pixel_text("12…", grid).x;
+        assert_eq!(truncate_pixel_text("12345", fits, grid), "12…");
+        assert_eq!(truncate_pixel_text("12", fits, grid), "12");
+        assert_eq!(truncate_pixel_text("12345", 0.0, grid), "");
+    }
+
+    #[test]
+    fn snaps_to_the_nearest_cell() {
+        assert_eq!(snap_to_grid(10.0, 27.0, 8.0), 26.0);
+        assert_eq!(snap_to_grid(10.0, 31.0, 8.0), 34.0);
+    }
+
+    #[test]
+    fn touching_rects_do_not_intersect() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        assert!(rects_intersect(a, Rect::new(5.0, 5.0, 10.0, 10.0)));
+        assert!(!rects_intersect(a, Rect::new(10.0, 0.0, 10.0, 10.0)));
+        assert!(point_in_rect(vec2(10.0, 10.0), a));
+    }
+
+    #[test]
+    fn overrides_and_dimming_only_touch_colors() {
+        let theme = FrameContext::default().theme;
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let themed = theme.with_override(&ThemeOverride {
+            active_color: Some(red),
+            ..Default::default()
+        });
+        assert_eq!(themed.active_color, red);
+        assert_eq!(themed.background_color, theme.background_color);
+        let dimmed = themed.dimmed(0.5);
+        assert_eq!(dimmed.active_color, Color::new(0.5, 0.0, 0.0, 1.0));
+        assert_eq!(dimmed.active_alpha, theme.active_alpha);
+    }
+
+    #[test]
+    fn reads_status_palettes() {
+        assert_eq!(
+            "Deuteranopia".parse::<StatusPalette>(),
+            Ok(StatusPalette::OKABE_ITO)
+        );
+        assert_eq!(
+            "protanopia".parse::<StatusPalette>(),
+            Ok(StatusPalette::OKABE_ITO)
+        );
+        assert_eq!(
+            "default".parse::<StatusPalette>(),
+            Ok(StatusPalette::default())
+        );
+        assert!("sepia".parse::<StatusPalette>().is_err());
+    }
+
+    #[test]
+    fn am_pm_color_is_kept_until_overridden() {
+        let theme = FrameContext::default().theme.with_override(&ThemeOverride {
+            am_pm_color: Some(Color::new(0.8, 0.4, 0.2, 1.0)),
+            ..Default::default()
+        });
+        let themed = theme.with_override(&ThemeOverride::default());
+        assert_eq!(themed.am_pm_color, theme.am_pm_color);
+        assert!(!themed.hour_wave);
+        let waving = themed.with_override(&ThemeOverride {
+            hour_wave: Some(true),
+            ..Default::default()
+        });
+        assert!(waving.hour_wave && !waving.hour_chirp);
+        assert_eq!(
+            themed.dimmed(0.5).am_pm_color,
+            Some(Color::new(0.4, 0.2, 0.1, 1.0))
+        );
+        assert_eq!(themed.one_bit().am_pm_color, None);
+    }
+
+    #[test]
+    fn high_contrast_is_solid_white_on_black() {
+        let theme = FrameContext::default().theme.high_contrast();
+        assert_eq!(theme.background_color, BLACK);
+        assert_eq!(theme.active_color, WHITE);
+        assert_eq!(theme.active_alpha_jitter, 0.0);
+        assert!(theme.min_alpha > 0.2 && theme.text_scale > 1.0);
+        assert_eq!(theme.status, StatusPalette::DEFAULT);
+    }
+
+    #[test]
+    fn high_contrast_still_dims() {
+        let base = FrameContext::default().theme;
+        let none = ThemeOverride::default();
+        let theme = base.for_frame(true, false, 0.0, &none, 0.5);
+        assert_eq!(theme.active_color, Color::new(0.5, 0.5, 0.5, 1.0));
+        assert_eq!(theme.min_alpha, base.high_contrast().min_alpha);
+        assert_eq!(base.for_frame(true, true, 0.0, &none, 0.5), base.one_bit());
+    }
+}
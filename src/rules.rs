@@ -0,0 +1,221 @@
+use crate::config::parse_hex_color;
+use crate::render::ThemeOverride;
+use macroquad::prelude::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Metric {
+    Prs,
+    Reviews,
+    Connected,
+    MainFailing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ThemeField {
+    Background,
+    Inactive,
+    Accent,
+    Noise,
+}
+
+// `prs > 5 => accent #f0a030`: when the metric matches, the field is
+// overridden with the color. Later rules win over earlier ones.
+#[derive(Clone, Debug)]
+pub struct ColorRule {
+    metric: Metric,
+    comparison: Comparison,
+    threshold: f64,
+    field: ThemeField,
+    color: Color,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RuleInputs {
+    pub prs: usize,
+    pub reviews: usize,
+    pub connected: bool,
+    // CI failing on the `CHRONO_MAIN_CI` branch.
+    pub main_failing: bool,
+}
+
+impl ColorRule {
+    fn matches(&self, inputs: &RuleInputs) -> bool {
+        let flag = |on: bool| if on { 1.0 } else { 0.0 };
+        let value = match self.metric {
+            Metric::Prs => inputs.prs as f64,
+            Metric::Reviews => inputs.reviews as f64,
+            Metric::Connected => flag(inputs.connected),
+            Metric::MainFailing => flag(inputs.main_failing),
+        };
+        match self.comparison {
+            Comparison::Greater => value > self.threshold,
+            Comparison::GreaterOrEqual => value >= self.threshold,
+            Comparison::Less => value < self.threshold,
+            Comparison::LessOrEqual => value <= self.threshold,
+            Comparison::Equal => value == self.threshold,
+            Comparison::NotEqual => value != self.threshold,
+        }
+    }
+}
+
+fn parse_rule(source: &str) -> Result<ColorRule, String> {
+    let (condition, action) = source
+        .split_once("=>")
+        .ok_or_else(|| "expected `<condition> => <field> <color>`".to_string())?;
+
+    let condition = condition.split_whitespace().collect::<Vec<&str>>();
+    let [metric, comparison, threshold] = condition[..] else {
+        return Err("condition must look like `prs > 5`".to_string());
+    };
+    let metric = match metric {
+        "prs" => Metric::Prs,
+        "reviews" => Metric::Reviews,
+        "connected" => Metric::Connected,
+        "main_failing" => Metric::MainFailing,
+        _ => return Err(format!("unknown metric {}", metric)),
+    };
+    let comparison = match comparison {
+        ">" => Comparison::Greater,
+        ">=" => Comparison::GreaterOrEqual,
+        "<" => Comparison::Less,
+        "<=" => Comparison::LessOrEqual,
+        "==" => Comparison::Equal,
+        "!=" => Comparison::NotEqual,
+        _ => return Err(format!("unknown comparison {}", comparison)),
+    };
+    let threshold = threshold
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number {}", threshold))?;
+
+    let action = action.split_whitespace().collect::<Vec<&str>>();
+    let [field, color] = action[..] else {
+        return Err("action must look like `accent #f0a030`".to_string());
+    };
+    let field = match field {
+        "background" => ThemeField::Background,
+        "inactive" => ThemeField::Inactive,
+        "accent" => ThemeField::Accent,
+        "noise" => ThemeField::Noise,
+        _ => return Err(format!("unknown theme field {}", field)),
+    };
+    let color = parse_hex_color(color).ok_or_else(|| format!("invalid color {}", color))?;
+
+    Ok(ColorRule {
+        metric,
+        comparison,
+        threshold,
+        field,
+        color,
+    })
+}
+
+pub fn parse_rules(source: &str) -> Vec<ColorRule> {
+    source
+        .split(';')
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .filter_map(|rule| match parse_rule(rule) {
+            Ok(rule) => Some(rule),
+            Err(e) => {
+                log!("Ignoring color rule `{}`: {}", rule, e);
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn evaluate(rules: &[ColorRule], inputs: &RuleInputs) -> ThemeOverride {
+    let mut result = ThemeOverride::default();
+    for rule in rules.iter().filter(|rule| rule.matches(inputs)) {
+        match rule.field {
+            ThemeField::Background => result.background_color = Some(rule.color),
+            ThemeField::Inactive => result.inactive_color = Some(rule.color),
+            // The noise pixels follow the accent unless a rule says otherwise.
+            ThemeField::Accent => {
+                result.active_color = Some(rule.color);
+                result.noise_color = Some(rule.color);
+            }
+            ThemeField::Noise => result.noise_color = Some(rule.color),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        let rule = parse_rule("prs >= 5 => accent #f0a030").unwrap();
+        assert_eq!(rule.metric, Metric::Prs);
+        assert_eq!(rule.comparison, Comparison::GreaterOrEqual);
+        assert_eq!(rule.threshold, 5.0);
+        assert_eq!(rule.field, ThemeField::Accent);
+        assert_eq!(rule.color, parse_hex_color("#f0a030").unwrap());
+
+        // Tokens need spaces between them.
+        assert_eq!(
+            parse_rule("main_failing==1 => background #2a1010").unwrap_err(),
+            "condition must look like `prs > 5`"
+        );
+        let rule = parse_rule(" main_failing == 1  =>  background #2a1010 ").unwrap();
+        assert_eq!(rule.metric, Metric::MainFailing);
+        assert_eq!(rule.field, ThemeField::Background);
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        let error = |source: &str| parse_rule(source).unwrap_err();
+        assert_eq!(
+            error("prs > 5"),
+            "expected `<condition> => <field> <color>`"
+        );
+        assert_eq!(error("builds > 5 => accent #fff"), "unknown metric builds");
+        assert_eq!(error("prs ~ 5 => accent #fff"), "unknown comparison ~");
+        assert_eq!(error("prs > five => accent #fff"), "invalid number five");
+        assert_eq!(
+            error("prs > 5 => accent"),
+            "action must look like `accent #f0a030`"
+        );
+        assert_eq!(
+            error("prs > 5 => border #fff"),
+            "unknown theme field border"
+        );
+        assert_eq!(error("prs > 5 => accent orange"), "invalid color orange");
+    }
+
+    #[test]
+    fn skips_bad_rules_and_lets_later_ones_win() {
+        let rules = parse_rules("prs > 5 => accent #ff0000; nonsense; prs > 8 => accent #00ff00;");
+        assert_eq!(rules.len(), 2);
+        let busy = RuleInputs {
+            prs: 9,
+            ..Default::default()
+        };
+        let theme = evaluate(&rules, &busy);
+        assert_eq!(theme.active_color, parse_hex_color("#00ff00"));
+        assert_eq!(theme.noise_color, parse_hex_color("#00ff00"));
+        assert_eq!(evaluate(&rules, &RuleInputs::default()).active_color, None);
+
+        let red_main = parse_rules("main_failing == 1 => background #2a1010");
+        let failing = RuleInputs {
+            main_failing: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            evaluate(&red_main, &failing).background_color,
+            parse_hex_color("#2a1010")
+        );
+    }
+}
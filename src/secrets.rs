@@ -0,0 +1,97 @@
+use keyring::Entry;
+use std::io::Read;
+
+// Stored as a generic password: service "chrono", account "github-token".
+const SERVICE: &str = "chrono";
+const GITHUB_TOKEN_ACCOUNT: &str = "github-token";
+
+fn github_entry() -> Result<Entry, keyring::Error> {
+    Entry::new(SERVICE, GITHUB_TOKEN_ACCOUNT)
+}
+
+// Extra GitHub accounts are keyed by their label.
+fn account_entry(label: &str) -> Result<Entry, keyring::Error> {
+    Entry::new(SERVICE, &format!("{}:{}", GITHUB_TOKEN_ACCOUNT, label))
+}
+
+// Tokens for self-hosted GitLab/Gitea live under the instance URL.
+fn instance_entry(base_url: &str) -> Result<Entry, keyring::Error> {
+    Entry::new(
+        SERVICE,
+        &format!("token:{}", base_url.trim_end_matches('/')),
+    )
+}
+
+fn read_entry(entry: Result<Entry, keyring::Error>) -> Option<String> {
+    let token = match entry.and_then(|entry| entry.get_password()) {
+        Ok(token) => token,
+        Err(keyring::Error::NoEntry) => return None,
+        Err(e) => {
+            log!("Failed to read token from keychain: {e}");
+            return None;
+        }
+    };
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+pub fn github_token() -> Option<String> {
+    read_entry(github_entry())
+}
+
+pub fn account_token(label: &str) -> Option<String> {
+    read_entry(account_entry(label))
+}
+
+pub fn instance_token(base_url: &str) -> Option<String> {
+    read_entry(instance_entry(base_url))
+}
+
+pub fn store_github_token(token: &str) -> Result<(), keyring::Error> {
+    github_entry()?.set_password(token.trim())
+}
+
+pub enum TokenSlot<'a> {
+    Github,
+    GithubAccount(&'a str),
+    Instance(&'a str),
+}
+
+// `echo $TOKEN | chrono --store-github-token`, so the token never ends up in
+// shell history or the process list. `--store-github-token <label>` stores a
+// token for one of several accounts, `--store-token <url>` one for a
+// GitLab/Gitea instance.
+pub fn store_token_from_stdin(slot: TokenSlot) -> i32 {
+    let mut token = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut token) {
+        eprintln!("Failed to read token from stdin: {e}");
+        return 1;
+    }
+    if token.trim().is_empty() {
+        eprintln!("No token on stdin");
+        return 1;
+    }
+    let stored = match slot {
+        TokenSlot::Github => store_github_token(&token),
+        TokenSlot::GithubAccount(label) => {
+            account_entry(label).and_then(|entry| entry.set_password(token.trim()))
+        }
+        TokenSlot::Instance(base_url) => {
+            instance_entry(base_url).and_then(|entry| entry.set_password(token.trim()))
+        }
+    };
+    match stored {
+        Ok(()) => {
+            println!("Token stored in the system keychain");
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to store token: {e}");
+            1
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// The overlays and modes on screen, written next to the fetch cache whenever
+// they change and read back at launch, so a crash or restart in the middle
+// of a focus session picks up where it left off.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Session {
+    // Unix seconds the running focus session ends.
+    pub focus_until: Option<i64>,
+    pub dashboard: usize,
+    pub issues: bool,
+    pub notifications_open: bool,
+    pub share_open: bool,
+}
+
+impl Session {
+    // One `key=value` per line; unknown keys are skipped so older and newer
+    // builds can read each other's files.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(until) = self.focus_until {
+            text.push_str(&format!("focus_until={}\n", until));
+        }
+        text.push_str(&format!("dashboard={}\n", self.dashboard));
+        text.push_str(&format!("issues={}\n", self.issues));
+        text.push_str(&format!("notifications_open={}\n", self.notifications_open));
+        text.push_str(&format!("share_open={}\n", self.share_open));
+        text
+    }
+
+    pub fn from_text(text: &str) -> Session {
+        let mut session = Session::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "focus_until" => session.focus_until = value.parse().ok(),
+                "dashboard" => session.dashboard = value.parse().unwrap_or(0),
+                "issues" => session.issues = value == "true",
+                "notifications_open" => session.notifications_open = value == "true",
+                "share_open" => session.share_open = value == "true",
+                _ => {}
+            }
+        }
+        session
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path() -> Option<std::path::PathBuf> {
+    Some(crate::platform::cache_dir()?.join("session.txt"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(session: &Session) {
+    let Some(path) = path() else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = std::fs::write(&path, session.to_text()) {
+        log!("Failed to write {}: {e}", path.display());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load() -> Option<Session> {
+    let text = std::fs::read_to_string(path()?).ok()?;
+    Some(Session::from_text(&text))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save(_session: &Session) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load() -> Option<Session> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let session = Session {
+            focus_until: Some(1_760_000_000),
+            dashboard: 2,
+            issues: true,
+            notifications_open: false,
+            share_open: true,
+        };
+        assert_eq!(Session::from_text(&session.to_text()), session);
+        assert_eq!(
+            Session::from_text(&Session::default().to_text()),
+            Session::default()
+        );
+    }
+
+    #[test]
+    fn skips_unknown_and_broken_lines() {
+        let session = Session::from_text("theme=dark\nfocus_until=soon\ndashboard=1\njunk\n");
+        assert_eq!(
+            session,
+            Session {
+                dashboard: 1,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,54 @@
+// Sharing a running focus session: a link carrying its end time, shown on
+// the board as a QR code. Opening the link in the web build, or passing it
+// to `--join`, starts a session ending at the same moment.
+use qrcodegen::{QrCode, QrCodeEcc};
+
+const PARAM: &str = "focus_until";
+
+// `<base>?focus_until=<unix seconds>`, appended to any query `base` has.
+pub fn focus_url(base: &str, ends_at: i64) -> String {
+    let separator = if base.contains('?') { '&' } else { '?' };
+    format!("{}{}{}={}", base, separator, PARAM, ends_at)
+}
+
+// The shared end time from a link or a bare `?query`.
+pub fn focus_until(url: &str) -> Option<i64> {
+    let query = url.split_once('?').map_or(url, |(_, query)| query);
+    query
+        .split('#')
+        .next()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == PARAM)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+// Low error correction keeps the code small enough for the PR area; the
+// board is read off a screen, not a crumpled label.
+pub fn qr_code(text: &str) -> Option<QrCode> {
+    QrCode::encode_text(text, QrCodeEcc::Low).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_round_trip() {
+        let url = focus_url("https://example.com/chrono/", 1_760_000_000);
+        assert_eq!(url, "https://example.com/chrono/?focus_until=1760000000");
+        assert_eq!(focus_until(&url), Some(1_760_000_000));
+        let url = focus_url("https://example.com/?theme=dark", 42);
+        assert_eq!(focus_until(&url), Some(42));
+        assert_eq!(focus_until("?focus_until=7#top"), Some(7));
+        assert_eq!(focus_until("https://example.com/?focus_until=soon"), None);
+        assert_eq!(focus_until("https://example.com/"), None);
+    }
+
+    #[test]
+    fn links_fit_a_small_code() {
+        let url = focus_url("https://example.github.io/chrono/", 1_760_000_000);
+        let code = qr_code(&url).unwrap();
+        assert!(code.size() <= 33);
+    }
+}
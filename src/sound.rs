@@ -0,0 +1,195 @@
+// Short synthesized cues, so no audio assets have to ship with the binary.
+// Playback needs the `audio` cargo feature; without it the cues are silent.
+use chrono::NaiveTime;
+#[cfg(feature = "audio")]
+use macroquad::audio::{load_sound_from_bytes, play_sound, PlaySoundParams, Sound};
+use std::str::FromStr;
+
+#[cfg(feature = "audio")]
+const SAMPLE_RATE: u32 = 22_050;
+
+// 16-bit mono PCM wrapped in a minimal WAV header.
+#[cfg(feature = "audio")]
+fn wav(samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+// A quick rising sweep with a soft attack and decay.
+#[cfg(feature = "audio")]
+fn chirp_samples(start_hz: f32, end_hz: f32, seconds: f32) -> Vec<i16> {
+    let count = (SAMPLE_RATE as f32 * seconds) as usize;
+    let mut phase = 0.0f32;
+    (0..count)
+        .map(|idx| {
+            let t = idx as f32 / count as f32;
+            let freq = start_hz + (end_hz - start_hz) * t;
+            phase += freq / SAMPLE_RATE as f32 * std::f32::consts::TAU;
+            let envelope = (t * 20.0).min(1.0) * (1.0 - t).powi(2);
+            (phase.sin() * envelope * i16::MAX as f32 * 0.5) as i16
+        })
+        .collect()
+}
+
+// A bell-like note: a few inharmonic partials ringing down from a hard
+// strike, `delay` seconds into the sample.
+#[cfg(feature = "audio")]
+fn bell(samples: &mut [i16], hz: f32, delay: f32, ring: f32) {
+    let start = (SAMPLE_RATE as f32 * delay) as usize;
+    for (idx, sample) in samples.iter_mut().enumerate().skip(start) {
+        let t = (idx - start) as f32 / SAMPLE_RATE as f32;
+        let tone = [(1.0, 1.0), (2.76, 0.4), (5.4, 0.15)]
+            .iter()
+            .map(|(ratio, level)| (t * hz * ratio * std::f32::consts::TAU).sin() * level)
+            .sum::<f32>()
+            / 1.55;
+        let envelope = (t * 400.0).min(1.0) * (-t * 4.0 / ring).exp();
+        *sample = sample.saturating_add((tone * envelope * i16::MAX as f32 * 0.4) as i16);
+    }
+}
+
+// The on-the-hour chime: two soft bells, high then low.
+#[cfg(feature = "audio")]
+fn chime_samples() -> Vec<i16> {
+    let mut samples = vec![0; (SAMPLE_RATE as f32 * 1.6) as usize];
+    bell(&mut samples, 659.3, 0.0, 1.0);
+    bell(&mut samples, 523.3, 0.45, 1.1);
+    samples
+}
+
+// Two quick rising notes for new PR activity, short enough not to be
+// mistaken for the chime.
+#[cfg(feature = "audio")]
+fn activity_samples() -> Vec<i16> {
+    let mut samples = chirp_samples(880.0, 900.0, 0.07);
+    samples.extend(std::iter::repeat_n(0, (SAMPLE_RATE as f32 * 0.03) as usize));
+    samples.extend(chirp_samples(1320.0, 1350.0, 0.09));
+    samples
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cue {
+    // The wake alarm and the theme's hour chirp.
+    Chirp,
+    Chime,
+    Activity,
+}
+
+// `22:00-07:00`; may wrap past midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        value
+            .split_once('-')
+            .and_then(|(start, end)| {
+                Some(QuietHours {
+                    start: parse(start)?,
+                    end: parse(end)?,
+                })
+            })
+            .ok_or_else(|| format!("quiet hours should look like 22:00-07:00, not {}", value))
+    }
+}
+
+pub struct Sounds {
+    #[cfg(feature = "audio")]
+    volume: f32,
+    #[cfg(feature = "audio")]
+    sounds: Vec<(Cue, Sound)>,
+}
+
+impl Sounds {
+    // `volume` from 0 to 1.
+    pub async fn load(volume: f32) -> Self {
+        #[cfg(feature = "audio")]
+        let mut sounds = Vec::new();
+        #[cfg(feature = "audio")]
+        for (cue, samples) in [
+            (Cue::Chirp, chirp_samples(2200.0, 3200.0, 0.09)),
+            (Cue::Chime, chime_samples()),
+            (Cue::Activity, activity_samples()),
+        ] {
+            match load_sound_from_bytes(&wav(&samples)).await {
+                Ok(sound) => sounds.push((cue, sound)),
+                Err(e) => log!("Failed to load the {:?} sound: {e}", cue),
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = volume;
+
+        Sounds {
+            #[cfg(feature = "audio")]
+            volume,
+            #[cfg(feature = "audio")]
+            sounds,
+        }
+    }
+
+    pub fn play(&self, cue: Cue) {
+        #[cfg(feature = "audio")]
+        if let Some((_, sound)) = self.sounds.iter().find(|(loaded, _)| *loaded == cue) {
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume: self.volume,
+                },
+            );
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = cue;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let at = |time: &str| NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+        let night = "22:00-07:00".parse::<QuietHours>().unwrap();
+        assert!(night.contains(at("23:00")));
+        assert!(night.contains(at("00:00")));
+        assert!(!night.contains(at("07:00")));
+        assert!(!night.contains(at("12:00")));
+        let lunch = "12:00 - 13:00".parse::<QuietHours>().unwrap();
+        assert!(lunch.contains(at("12:30")));
+        assert!(!lunch.contains(at("21:00")));
+        assert!("22:00".parse::<QuietHours>().is_err());
+    }
+}
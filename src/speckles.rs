@@ -0,0 +1,281 @@
+use std::str::FromStr;
+
+// Where the lit speckles on the board may land. `Checker` keeps them on
+// even (row + col) cells, which reads as a faint checkerboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    Checker,
+    Any,
+    Clustered,
+    EdgeWeighted,
+}
+
+impl FromStr for Placement {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "checker" => Ok(Placement::Checker),
+            "any" | "random" => Ok(Placement::Any),
+            "clustered" | "cluster" => Ok(Placement::Clustered),
+            "edge" | "edge-weighted" | "edges" => Ok(Placement::EdgeWeighted),
+            _ => Err(format!("unknown speckle placement {}", value)),
+        }
+    }
+}
+
+// How many noise dots and lit speckles the board gets and how bright they
+// are, relative to the defaults. A density of 0 leaves the board clean.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Intensity {
+    pub density: f32,
+    pub brightness: f32,
+    // Off keeps the speckles in place instead of moving them every minute.
+    pub animated: bool,
+}
+
+impl Intensity {
+    pub const DEFAULT: Intensity = Intensity {
+        density: 1.0,
+        brightness: 1.0,
+        animated: true,
+    };
+
+    pub fn count(self, base: usize) -> usize {
+        (base as f32 * self.density.max(0.0)).round() as usize
+    }
+
+    pub fn alpha(self, base: f32) -> f32 {
+        (base * self.brightness.max(0.0)).min(1.0)
+    }
+
+    pub fn seed(self, minute: i32) -> i32 {
+        if self.animated {
+            minute
+        } else {
+            0
+        }
+    }
+}
+
+impl Default for Intensity {
+    fn default() -> Self {
+        Intensity::DEFAULT
+    }
+}
+
+// Cells a cluster may spread from its center.
+const CLUSTER_RADIUS: i32 = 3;
+// Offsets tried per speckle before a cluster falls back to probing.
+const CLUSTER_ATTEMPTS: i32 = 16;
+// Candidates drawn per speckle when favoring the edges.
+const EDGE_CANDIDATES: i32 = 4;
+
+// Up to `count` distinct (row, col) cells, the same for the same seed.
+pub fn pick(
+    placement: Placement,
+    cols: i32,
+    rows: i32,
+    seed: i32,
+    count: usize,
+) -> Vec<(i32, i32)> {
+    let cols = cols.max(1);
+    let rows = rows.max(1);
+    let total = cols * rows;
+    let mut picks: Vec<(i32, i32)> = Vec::new();
+    for i in 0..count as i32 {
+        let start = match placement {
+            Placement::Checker => seed * 997 + i * 379,
+            Placement::Any => hash(seed, i, 0),
+            Placement::Clustered => {
+                let center = hash(seed, 0, 1) % total;
+                let span = CLUSTER_RADIUS * 2 + 1;
+                let near = (0..CLUSTER_ATTEMPTS)
+                    .map(|attempt| {
+                        let d_row = hash(seed, i, 2 + attempt * 2) % span - CLUSTER_RADIUS;
+                        let d_col = hash(seed, i, 3 + attempt * 2) % span - CLUSTER_RADIUS;
+                        let row = (center / cols + d_row).clamp(0, rows - 1);
+                        let col = (center % cols + d_col).clamp(0, cols - 1);
+                        (row, col)
+                    })
+                    .find(|cell| !picks.contains(cell));
+                match near {
+                    Some(cell) => {
+                        picks.push(cell);
+                        continue;
+                    }
+                    None => center,
+                }
+            }
+            Placement::EdgeWeighted => (0..EDGE_CANDIDATES)
+                .map(|k| hash(seed, i, 4 + k) % total)
+                .min_by_key(|idx| edge_distance(idx / cols, idx % cols, cols, rows))
+                .unwrap_or(0),
+        };
+        let parity_only = placement == Placement::Checker;
+        match probe(start, total, cols, &picks, parity_only) {
+            Some(cell) => picks.push(cell),
+            None => break,
+        }
+    }
+    picks
+}
+
+// Walks forward from `start` to the first free cell.
+fn probe(
+    start: i32,
+    total: i32,
+    cols: i32,
+    taken: &[(i32, i32)],
+    parity_only: bool,
+) -> Option<(i32, i32)> {
+    let mut idx = (start % total).abs();
+    for _ in 0..total {
+        let row = idx / cols;
+        let col = idx % cols;
+        if (!parity_only || (row + col) & 1 == 0) && !taken.contains(&(row, col)) {
+            return Some((row, col));
+        }
+        idx = (idx + 1) % total;
+    }
+    None
+}
+
+fn edge_distance(row: i32, col: i32, cols: i32, rows: i32) -> i32 {
+    row.min(col).min(rows - 1 - row).min(cols - 1 - col)
+}
+
+fn hash(seed: i32, i: i32, salt: i32) -> i32 {
+    let mut h = (seed as u32).wrapping_mul(0x9E37_79B1)
+        ^ (i as u32).wrapping_mul(0x85EB_CA77)
+        ^ (salt as u32).wrapping_mul(0xC2B2_AE3D);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^= h >> 12;
+    (h >> 1) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Placement; 4] = [
+        Placement::Checker,
+        Placement::Any,
+        Placement::Clustered,
+        Placement::EdgeWeighted,
+    ];
+
+    #[test]
+    fn scales_count_and_alpha() {
+        let sparse = Intensity {
+            density: 0.5,
+            brightness: 2.0,
+            animated: false,
+        };
+        assert_eq!(Intensity::DEFAULT.count(9), 9);
+        assert_eq!(sparse.count(9), 5);
+        assert_eq!(sparse.alpha(0.35), 0.7);
+        assert_eq!(sparse.alpha(0.95), 1.0);
+        assert_eq!(sparse.seed(42), 0);
+        assert_eq!(Intensity::DEFAULT.seed(42), 42);
+        let off = Intensity {
+            density: 0.0,
+            ..Intensity::DEFAULT
+        };
+        assert_eq!(off.count(22), 0);
+    }
+
+    fn distinct(picks: &[(i32, i32)]) -> bool {
+        picks
+            .iter()
+            .enumerate()
+            .all(|(idx, cell)| !picks[..idx].contains(cell))
+    }
+
+    #[test]
+    fn every_placement_fills_in_bounds_distinct_cells() {
+        for placement in ALL {
+            for seed in 0..60 {
+                let picks = pick(placement, 40, 20, seed, 9);
+                assert_eq!(picks.len(), 9, "{placement:?} seed {seed}");
+                assert!(distinct(&picks), "{placement:?} seed {seed}");
+                assert!(picks
+                    .iter()
+                    .all(|(row, col)| (0..20).contains(row) && (0..40).contains(col)));
+            }
+        }
+    }
+
+    #[test]
+    fn picks_are_stable_for_a_seed() {
+        for placement in ALL {
+            assert_eq!(
+                pick(placement, 30, 12, 17, 9),
+                pick(placement, 30, 12, 17, 9)
+            );
+        }
+    }
+
+    #[test]
+    fn checker_stays_on_even_cells() {
+        for seed in 0..60 {
+            assert!(pick(Placement::Checker, 40, 20, seed, 9)
+                .iter()
+                .all(|(row, col)| (row + col) & 1 == 0));
+        }
+    }
+
+    #[test]
+    fn any_also_uses_odd_cells() {
+        let odd = (0..60)
+            .flat_map(|seed| pick(Placement::Any, 40, 20, seed, 9))
+            .filter(|(row, col)| (row + col) & 1 == 1)
+            .count();
+        assert!(odd > 0);
+    }
+
+    #[test]
+    fn clustered_stays_close_together() {
+        for seed in 0..60 {
+            let picks = pick(Placement::Clustered, 60, 30, seed, 9);
+            let rows = picks.iter().map(|(row, _)| *row);
+            let cols = picks.iter().map(|(_, col)| *col);
+            let height = rows.clone().max().unwrap() - rows.min().unwrap();
+            let width = cols.clone().max().unwrap() - cols.min().unwrap();
+            assert!(height <= CLUSTER_RADIUS * 2, "seed {seed}");
+            assert!(width <= CLUSTER_RADIUS * 2, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn edge_weighted_leans_toward_the_edges() {
+        let mean_distance = |placement| {
+            let picks = (0..60)
+                .flat_map(|seed| pick(placement, 60, 30, seed, 9))
+                .collect::<Vec<_>>();
+            picks
+                .iter()
+                .map(|(row, col)| edge_distance(*row, *col, 60, 30))
+                .sum::<i32>() as f32
+                / picks.len() as f32
+        };
+        assert!(mean_distance(Placement::EdgeWeighted) < mean_distance(Placement::Any));
+    }
+
+    #[test]
+    fn small_grids_do_not_loop_forever() {
+        for placement in ALL {
+            let picks = pick(placement, 2, 2, 5, 9);
+            assert!(picks.len() <= 4);
+            assert!(distinct(&picks));
+        }
+    }
+
+    #[test]
+    fn parses_placement_names() {
+        assert_eq!("ANY".parse::<Placement>(), Ok(Placement::Any));
+        assert_eq!("edge".parse::<Placement>(), Ok(Placement::EdgeWeighted));
+        assert!("spiral".parse::<Placement>().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+// Sunrise and sunset at `CHRONO_LOCATION`, from the sunrise equation (good
+// to a minute or two away from the poles), and how warm the board turns in
+// the evening with `CHRONO_SUNSET_TINT`.
+use crate::clock::{short_time, HourFormat};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
+use std::f64::consts::PI;
+
+// Julian day of 2000-01-01 12:00 UTC, and of the Unix epoch.
+const J2000: f64 = 2_451_545.0;
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+// The sun's upper edge on the horizon, with refraction.
+const HORIZON_DEGREES: f64 = -0.833;
+const OBLIQUITY_DEGREES: f64 = 23.4397;
+// The tint fades in over the hour after sunset and out over the hour
+// before sunrise.
+const FADE_SECS: i64 = 60 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SunTimes {
+    Rises {
+        sunrise: DateTime<FixedOffset>,
+        sunset: DateTime<FixedOffset>,
+    },
+    // Inside the polar circles the sun can stay up or down all day.
+    PolarDay,
+    PolarNight,
+}
+
+fn radians(degrees: f64) -> f64 {
+    degrees * PI / 180.0
+}
+
+fn from_julian(day: f64, offset: FixedOffset) -> Option<DateTime<FixedOffset>> {
+    let secs = ((day - UNIX_EPOCH_JD) * 86_400.0).round() as i64;
+    Some(Utc.timestamp_opt(secs, 0).single()?.with_timezone(&offset))
+}
+
+// East longitudes are positive.
+pub fn sun_times(date: NaiveDate, latitude: f64, longitude: f64, offset: FixedOffset) -> SunTimes {
+    // Days since J2000 at local noon.
+    let days = (date.num_days_from_ce() - 730_120) as f64;
+    let solar_noon = days - longitude / 360.0;
+    let anomaly = radians((357.5291 + 0.985_600_28 * solar_noon).rem_euclid(360.0));
+    let center =
+        1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic = radians((anomaly.to_degrees() + center + 180.0 + 102.9372).rem_euclid(360.0));
+    let transit = J2000 + solar_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+    let declination = (ecliptic.sin() * radians(OBLIQUITY_DEGREES).sin()).asin();
+    let latitude = radians(latitude);
+    let cos_hour_angle = (radians(HORIZON_DEGREES).sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if cos_hour_angle > 1.0 {
+        return SunTimes::PolarNight;
+    }
+    if cos_hour_angle < -1.0 {
+        return SunTimes::PolarDay;
+    }
+    let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+    match (
+        from_julian(transit - half_day, offset),
+        from_julian(transit + half_day, offset),
+    ) {
+        (Some(sunrise), Some(sunset)) => SunTimes::Rises { sunrise, sunset },
+        _ => SunTimes::PolarNight,
+    }
+}
+
+// `☀ ↑06:12 ↓19:48` for the bottom-left widget lines.
+pub fn sun_label(times: SunTimes, hour_format: HourFormat) -> String {
+    match times {
+        SunTimes::Rises { sunrise, sunset } => format!(
+            "☀ ↑{} ↓{}",
+            short_time(sunrise, hour_format),
+            short_time(sunset, hour_format)
+        ),
+        SunTimes::PolarDay => "☀ UP ALL DAY".to_string(),
+        SunTimes::PolarNight => "☀ DOWN ALL DAY".to_string(),
+    }
+}
+
+// 0 in daylight, 1 at night, ramping over an hour at either end.
+pub fn warmth(now: DateTime<FixedOffset>, times: SunTimes) -> f32 {
+    match times {
+        SunTimes::Rises { sunrise, sunset } => {
+            let after_sunset = (now - sunset).num_seconds();
+            let before_sunrise = (sunrise - now).num_seconds();
+            let dark = after_sunset.max(before_sunrise).clamp(0, FADE_SECS);
+            dark as f32 / FADE_SECS as f32
+        }
+        SunTimes::PolarDay => 0.0,
+        SunTimes::PolarNight => 1.0,
+    }
+}
+
+// The sun times for `CHRONO_LOCATION`, worked out once a day.
+pub struct Sun {
+    latitude: f64,
+    longitude: f64,
+    today: Option<(NaiveDate, SunTimes)>,
+}
+
+impl Sun {
+    pub fn new((latitude, longitude): (f64, f64)) -> Self {
+        Sun {
+            latitude,
+            longitude,
+            today: None,
+        }
+    }
+
+    pub fn times(&mut self, now: DateTime<FixedOffset>) -> SunTimes {
+        let date = now.date_naive();
+        match self.today {
+            Some((day, times)) if day == date => times,
+            _ => {
+                let times = sun_times(date, self.latitude, self.longitude, *now.offset());
+                self.today = Some((date, times));
+                times
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hm(times: SunTimes) -> (String, String) {
+        match times {
+            SunTimes::Rises { sunrise, sunset } => (
+                sunrise.format("%H:%M").to_string(),
+                sunset.format("%H:%M").to_string(),
+            ),
+            other => panic!("expected a sunrise, got {:?}", other),
+        }
+    }
+
+    fn minutes(time: &str) -> i32 {
+        let (hours, minutes) = time.split_once(':').unwrap();
+        hours.parse::<i32>().unwrap() * 60 + minutes.parse::<i32>().unwrap()
+    }
+
+    fn assert_near(actual: &str, expected: &str) {
+        assert!(
+            (minutes(actual) - minutes(expected)).abs() <= 3,
+            "{} is not near {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn matches_published_times() {
+        // London on the March equinox: 06:03 and 18:14 GMT.
+        let (sunrise, sunset) = hm(sun_times(
+            NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(),
+            51.5074,
+            -0.1278,
+            FixedOffset::east_opt(0).unwrap(),
+        ));
+        assert_near(&sunrise, "06:03");
+        assert_near(&sunset, "18:14");
+        // Kyiv at midsummer: 04:47 and 21:13 EEST.
+        let (sunrise, sunset) = hm(sun_times(
+            NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            50.45,
+            30.52,
+            FixedOffset::east_opt(3 * 3600).unwrap(),
+        ));
+        assert_near(&sunrise, "04:47");
+        assert_near(&sunset, "21:13");
+    }
+
+    #[test]
+    fn polar_days_and_nights() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let tromso = |month, day| {
+            sun_times(
+                NaiveDate::from_ymd_opt(2026, month, day).unwrap(),
+                69.65,
+                18.96,
+                offset,
+            )
+        };
+        assert_eq!(tromso(6, 21), SunTimes::PolarDay);
+        assert_eq!(tromso(12, 21), SunTimes::PolarNight);
+        assert_eq!(sun_label(tromso(12, 21), HourFormat::H24), "☀ DOWN ALL DAY");
+    }
+
+    #[test]
+    fn labels_and_warmth() {
+        let at = |time: &str| DateTime::parse_from_rfc3339(time).unwrap();
+        let times = SunTimes::Rises {
+            sunrise: at("2026-10-16T07:12:00+02:00"),
+            sunset: at("2026-10-16T18:30:00+02:00"),
+        };
+        assert_eq!(sun_label(times, HourFormat::H24), "☀ ↑07:12 ↓18:30");
+        assert_eq!(sun_label(times, HourFormat::H12), "☀ ↑07:12AM ↓06:30PM");
+        assert_eq!(warmth(at("2026-10-16T12:00:00+02:00"), times), 0.0);
+        assert_eq!(warmth(at("2026-10-16T19:00:00+02:00"), times), 0.5);
+        assert_eq!(warmth(at("2026-10-16T23:00:00+02:00"), times), 1.0);
+        assert_eq!(warmth(at("2026-10-16T06:42:00+02:00"), times), 0.5);
+        for ch in sun_label(times, HourFormat::H12).chars() {
+            assert!(crate::glyphs::has_glyph(ch), "no glyph for {:?}", ch);
+        }
+    }
+}
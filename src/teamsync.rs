@@ -0,0 +1,102 @@
+use std::net::{Ipv4Addr, UdpSocket};
+
+const MAGIC: &str = "chrono-focus/1";
+// How often this instance announces itself.
+const ANNOUNCE_SECS: f64 = 5.0;
+// A teammate not heard from for this long has closed chrono or left.
+const PEER_TIMEOUT_SECS: f64 = 20.0;
+
+struct Peer {
+    id: u64,
+    focusing: bool,
+    seen_at: f64,
+}
+
+// Shares whether a Pomodoro is running with other chrono instances on the
+// LAN over UDP broadcast, and counts who is focusing. Times are
+// `get_time()` seconds.
+pub struct TeamSync {
+    socket: UdpSocket,
+    port: u16,
+    id: u64,
+    focusing: bool,
+    last_announce: Option<f64>,
+    peers: Vec<Peer>,
+}
+
+impl TeamSync {
+    pub fn bind(port: u16) -> Option<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))
+            .and_then(|socket| {
+                socket.set_broadcast(true)?;
+                socket.set_nonblocking(true)?;
+                Ok(socket)
+            })
+            .map_err(|e| log!("Team sync unavailable on port {}: {}", port, e))
+            .ok()?;
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default();
+        Some(TeamSync {
+            socket,
+            port,
+            id: nanos ^ ((std::process::id() as u64) << 32),
+            focusing: false,
+            last_announce: None,
+            peers: Vec::new(),
+        })
+    }
+
+    // Returns how many people are focusing, counting this instance.
+    pub fn update(&mut self, now: f64, focusing: bool) -> usize {
+        let changed = focusing != self.focusing;
+        self.focusing = focusing;
+        if changed
+            || self
+                .last_announce
+                .is_none_or(|last| now - last >= ANNOUNCE_SECS)
+        {
+            self.last_announce = Some(now);
+            let message = format!("{} {} {}", MAGIC, self.id, u8::from(focusing));
+            let _ = self
+                .socket
+                .send_to(message.as_bytes(), (Ipv4Addr::BROADCAST, self.port));
+        }
+
+        let mut buf = [0u8; 64];
+        while let Ok((len, _)) = self.socket.recv_from(&mut buf) {
+            let Some((id, focusing)) = parse(&buf[..len]) else {
+                continue;
+            };
+            if id == self.id {
+                continue;
+            }
+            match self.peers.iter_mut().find(|peer| peer.id == id) {
+                Some(peer) => {
+                    peer.focusing = focusing;
+                    peer.seen_at = now;
+                }
+                None => self.peers.push(Peer {
+                    id,
+                    focusing,
+                    seen_at: now,
+                }),
+            }
+        }
+        self.peers
+            .retain(|peer| now - peer.seen_at < PEER_TIMEOUT_SECS);
+        self.peers.iter().filter(|peer| peer.focusing).count() + usize::from(focusing)
+    }
+}
+
+fn parse(message: &[u8]) -> Option<(u64, bool)> {
+    let message = std::str::from_utf8(message).ok()?;
+    let mut parts = message.split_whitespace();
+    if parts.next()? != MAGIC {
+        return None;
+    }
+    let id = parts.next()?.parse().ok()?;
+    let focusing = parts.next()? == "1";
+    Some((id, focusing))
+}
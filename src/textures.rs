@@ -0,0 +1,143 @@
+// Avatar textures under a memory budget. A texture that would go over it
+// pushes out the ones drawn longest ago; they are loaded again from the disk
+// cache if their PRs come back.
+use std::cell::Cell;
+use std::collections::HashMap;
+
+// Downscaling stops here, the size of a pixel avatar.
+const MIN_SIZE: u32 = 8;
+
+struct Entry<T> {
+    value: T,
+    bytes: usize,
+    used_at: Cell<u64>,
+}
+
+pub struct TextureCache<T> {
+    budget_bytes: usize,
+    used_bytes: usize,
+    // Bumped on every lookup to order entries by use.
+    clock: Cell<u64>,
+    entries: HashMap<String, Entry<T>>,
+}
+
+impl<T> TextureCache<T> {
+    pub fn new(budget_bytes: usize) -> Self {
+        TextureCache {
+            budget_bytes,
+            used_bytes: 0,
+            clock: Cell::new(0),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn budget_bytes(&self) -> usize {
+        self.budget_bytes
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    // Takes `&self` so the draw code can mark what it drew.
+    pub fn get(&self, key: &str) -> Option<&T> {
+        let entry = self.entries.get(key)?;
+        self.clock.set(self.clock.get() + 1);
+        entry.used_at.set(self.clock.get());
+        Some(&entry.value)
+    }
+
+    // A texture bigger than the whole budget is not kept.
+    pub fn insert(&mut self, key: String, value: T, bytes: usize) {
+        if bytes > self.budget_bytes {
+            return;
+        }
+        if let Some(old) = self.entries.remove(&key) {
+            self.used_bytes -= old.bytes;
+        }
+        while self.used_bytes + bytes > self.budget_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used_at.get())
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.used_bytes -= entry.bytes;
+            }
+        }
+        self.clock.set(self.clock.get() + 1);
+        self.used_bytes += bytes;
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                bytes,
+                used_at: Cell::new(self.clock.get()),
+            },
+        );
+    }
+}
+
+// RGBA bytes of a square texture.
+pub fn texture_bytes(size: u32) -> usize {
+    size as usize * size as usize * 4
+}
+
+// `size` halved until `count` textures fit the budget, so a board listing
+// dozens of authors keeps every avatar, just smaller.
+pub fn fitting_size(size: u32, count: usize, budget_bytes: usize) -> u32 {
+    let mut size = size;
+    while size > MIN_SIZE && count * texture_bytes(size) > budget_bytes {
+        size /= 2;
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_the_least_recently_used() {
+        let mut cache = TextureCache::new(300);
+        cache.insert("a".into(), 'a', 100);
+        cache.insert("b".into(), 'b', 100);
+        cache.insert("c".into(), 'c', 100);
+        // Drawing `a` makes `b` the oldest.
+        assert_eq!(cache.get("a"), Some(&'a'));
+        cache.insert("d".into(), 'd', 100);
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("a") && cache.contains("c") && cache.contains("d"));
+        assert_eq!(cache.used_bytes(), 300);
+        // Too big to keep at all.
+        cache.insert("e".into(), 'e', 400);
+        assert!(!cache.contains("e"));
+        assert_eq!(cache.used_bytes(), 300);
+    }
+
+    #[test]
+    fn replacing_an_entry_frees_its_bytes() {
+        let mut cache = TextureCache::new(300);
+        cache.insert("a".into(), 1, 200);
+        cache.insert("a".into(), 2, 100);
+        assert_eq!(cache.used_bytes(), 100);
+        assert_eq!(cache.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn scales_down_to_fit_the_budget() {
+        let budget = 256 * 1024;
+        assert_eq!(fitting_size(32, 10, budget), 32);
+        // 80 avatars at 32px would take 320 KiB.
+        assert_eq!(fitting_size(32, 80, budget), 16);
+        assert_eq!(fitting_size(32, 100_000, budget), 8);
+        assert_eq!(fitting_size(8, 100_000, budget), 8);
+    }
+}
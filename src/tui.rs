@@ -0,0 +1,207 @@
+// `chrono --tui`: the clock in a terminal, for use over SSH. The glyph rows
+// are the board's, drawn two to a line with the upper half block: the top
+// cell is the foreground color, the bottom one the background.
+use crate::clock::{time_source, ClockText, HourFormat, TimeFormat, TimeSource};
+use crate::config::Config;
+use crate::glyphs::glyph_rows;
+use crate::import;
+use crate::render::{ACCENT_PALETTE, BACKGROUND_COLOR, INACTIVE_COLOR};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Write};
+use std::time::Duration;
+
+// Seconds change on time without redrawing more than needed; tenths and
+// hundredths poll faster.
+const TICK: Duration = Duration::from_millis(200);
+const SUBSECOND_TICK: Duration = Duration::from_millis(20);
+// Terminal lines between the time and the date.
+const GAP: u16 = 1;
+
+struct Colors {
+    background: Color,
+    inactive: Color,
+    active: Color,
+}
+
+fn rgb(color: macroquad::color::Color) -> Color {
+    Color::Rgb {
+        r: (color.r * 255.0).round() as u8,
+        g: (color.g * 255.0).round() as u8,
+        b: (color.b * 255.0).round() as u8,
+    }
+}
+
+// Glyph rows paired up into terminal lines of (top, bottom) cells; the odd
+// seventh row gets an unlit bottom half.
+fn half_rows(rows: &[String]) -> Vec<Vec<(bool, bool)>> {
+    rows.chunks(2)
+        .map(|pair| {
+            let lit = |row: &String| row.chars().map(|cell| cell == '#').collect::<Vec<bool>>();
+            let top = lit(&pair[0]);
+            let bottom = pair.get(1).map_or_else(|| vec![false; top.len()], lit);
+            top.into_iter().zip(bottom).collect()
+        })
+        .collect()
+}
+
+fn draw(out: &mut impl Write, size: (u16, u16), texts: &[&str], colors: &Colors) -> io::Result<()> {
+    let (cols, lines) = size;
+    queue!(
+        out,
+        SetBackgroundColor(colors.background),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    let blocks = texts
+        .iter()
+        .map(|text| half_rows(&glyph_rows(text)))
+        .collect::<Vec<_>>();
+    let height = blocks.iter().map(|block| block.len() as u16).sum::<u16>()
+        + GAP * blocks.len().saturating_sub(1) as u16;
+    let mut y = lines.saturating_sub(height) / 2;
+    for block in blocks {
+        for line in block.iter() {
+            let x = cols.saturating_sub(line.len() as u16) / 2;
+            queue!(out, cursor::MoveTo(x, y))?;
+            for &(top, bottom) in line.iter().take(cols as usize) {
+                let color = |lit: bool| if lit { colors.active } else { colors.inactive };
+                queue!(
+                    out,
+                    SetForegroundColor(color(top)),
+                    SetBackgroundColor(color(bottom)),
+                    Print('▀')
+                )?;
+            }
+            y += 1;
+        }
+        y += GAP;
+    }
+    queue!(out, SetBackgroundColor(colors.background))?;
+    out.flush()
+}
+
+fn event_loop(
+    out: &mut impl Write,
+    config: &Config,
+    clock: &dyn TimeSource,
+    colors: &Colors,
+) -> io::Result<()> {
+    let mut hour_format = config.hour_format.unwrap_or_else(HourFormat::detect);
+    let mut time_format = config
+        .time_format
+        .clone()
+        .map_or(TimeFormat::HhMmSs, TimeFormat::Custom);
+    let mut drawn: Option<((u16, u16), String, String)> = None;
+    loop {
+        let size = terminal::size()?;
+        let text = ClockText::read(
+            clock,
+            hour_format,
+            &time_format,
+            config.date_format.as_ref(),
+        );
+        let time = match text.am_pm {
+            Some(suffix) => format!("{} {}", text.time, suffix),
+            None => text.time,
+        };
+        let date = format!("{} {}", text.day_month, text.year);
+        let frame = (size, time, date);
+        if drawn.as_ref() != Some(&frame) {
+            draw(out, size, &[&frame.1, &frame.2], colors)?;
+            drawn = Some(frame);
+        }
+        let tick = if time_format.subsecond() {
+            SUBSECOND_TICK
+        } else {
+            TICK
+        };
+        if !event::poll(tick)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char('h') => hour_format = hour_format.toggled(),
+            KeyCode::Char('f') => time_format = time_format.next(config.time_format.as_ref()),
+            _ => {}
+        }
+    }
+}
+
+// Runs until Q, Esc or Ctrl-C and returns the exit code.
+pub fn run() -> i32 {
+    let config = Config::load();
+    let clock = time_source(config.demo_time);
+    // The installed theme's colors, like the board under its dashboards.
+    let theme = import::installed_theme().unwrap_or_default().theme;
+    let base = config.colors;
+    let colors = Colors {
+        background: rgb(theme
+            .background_color
+            .or(base.background_color)
+            .unwrap_or(BACKGROUND_COLOR)),
+        inactive: rgb(theme
+            .inactive_color
+            .or(base.inactive_color)
+            .unwrap_or(INACTIVE_COLOR)),
+        active: rgb(theme
+            .active_color
+            .or(base.active_color)
+            .unwrap_or(ACCENT_PALETTE[0])),
+    };
+    let mut out = io::stdout();
+    let result = terminal::enable_raw_mode()
+        .and_then(|()| execute!(out, terminal::EnterAlternateScreen, cursor::Hide))
+        .and_then(|()| event_loop(&mut out, &config, &*clock, &colors));
+    let _ = execute!(
+        out,
+        ResetColor,
+        cursor::Show,
+        terminal::LeaveAlternateScreen
+    );
+    let _ = terminal::disable_raw_mode();
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            log!("Terminal output failed: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_two_glyph_rows_per_line() {
+        let lines = half_rows(&glyph_rows("1"));
+        assert_eq!(lines.len(), 4);
+        // ".#." over "##."
+        assert_eq!(lines[0], [(false, true), (true, true), (false, false)]);
+        // The seventh row, "###", has nothing below it.
+        assert_eq!(lines[3], [(true, false), (true, false), (true, false)]);
+    }
+
+    #[test]
+    fn draws_every_cell_of_each_block() {
+        let colors = Colors {
+            background: Color::Black,
+            inactive: Color::DarkGrey,
+            active: Color::Green,
+        };
+        let mut out = Vec::new();
+        draw(&mut out, (40, 20), &["12:00", "09MAR"], &colors).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        // Two blocks of four lines, each as wide as its glyph rows.
+        let width = glyph_rows("12:00")[0].len() + glyph_rows("09MAR")[0].len();
+        assert_eq!(text.matches('▀').count(), 4 * width);
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use macroquad::prelude::Color;
+
+// How long the board stays at full daylight after the wake time.
+const HOLD_SECS: i64 = 10 * 60;
+// Board brightness when the ramp starts.
+const START_BRIGHTNESS: f32 = 0.05;
+// The accent goes from a deep dawn red to warm daylight.
+const DAWN: Color = Color::new(0.55, 0.12, 0.05, 1.0);
+const DAYLIGHT: Color = Color::new(1.0, 0.78, 0.42, 1.0);
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sunrise {
+    pub brightness: f32,
+    pub accent: Color,
+    // True on the one frame the wake time is reached.
+    pub ring: bool,
+}
+
+// A sunrise alarm: over the last `ramp_minutes` before the wake time the
+// board brightens and the accent warms up, then the alarm goes off.
+pub struct WakeLight {
+    at: NaiveTime,
+    ramp_secs: i64,
+    rang: bool,
+    // Until the first update. A wake time already past by then was missed
+    // while chrono was not running, so it lights up without ringing.
+    started: bool,
+}
+
+impl WakeLight {
+    pub fn new(at: NaiveTime, ramp_minutes: f64) -> Self {
+        WakeLight {
+            at,
+            ramp_secs: (ramp_minutes * 60.0).max(60.0) as i64,
+            rang: false,
+            started: false,
+        }
+    }
+
+    // None outside the ramp and the hold after it.
+    pub fn update(&mut self, now: DateTime<FixedOffset>) -> Option<Sunrise> {
+        let wake = now.date_naive().and_time(self.at);
+        // Seconds since the nearest wake time, so a ramp that starts
+        // before midnight still counts down to tomorrow's alarm.
+        let mut elapsed = (now.naive_local() - wake).num_seconds();
+        if elapsed > DAY_SECS / 2 {
+            elapsed -= DAY_SECS;
+        } else if elapsed <= -DAY_SECS / 2 {
+            elapsed += DAY_SECS;
+        }
+        let first = !std::mem::replace(&mut self.started, true);
+        if elapsed < -self.ramp_secs || elapsed >= HOLD_SECS {
+            self.rang = false;
+            return None;
+        }
+        if first && elapsed >= 0 {
+            self.rang = true;
+        }
+        let progress = ((elapsed + self.ramp_secs) as f32 / self.ramp_secs as f32).min(1.0);
+        let ring = elapsed >= 0 && !self.rang;
+        self.rang |= ring;
+        let mix = |from: f32, to: f32| from + (to - from) * progress;
+        Some(Sunrise {
+            brightness: mix(START_BRIGHTNESS, 1.0),
+            accent: Color::new(
+                mix(DAWN.r, DAYLIGHT.r),
+                mix(DAWN.g, DAYLIGHT.g),
+                mix(DAWN.b, DAYLIGHT.b),
+                1.0,
+            ),
+            ring,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&format!("2024-03-09T{}+01:00", time)).unwrap()
+    }
+
+    fn alarm(wake: &str) -> WakeLight {
+        WakeLight::new(NaiveTime::parse_from_str(wake, "%H:%M").unwrap(), 30.0)
+    }
+
+    #[test]
+    fn ramps_up_rings_once_and_holds() {
+        let mut light = alarm("07:00");
+        assert_eq!(light.update(at("06:29:59")), None);
+        let start = light.update(at("06:30:00")).unwrap();
+        assert_eq!(start.brightness, START_BRIGHTNESS);
+        assert_eq!(start.accent, DAWN);
+        assert!(!start.ring);
+        let halfway = light.update(at("06:45:00")).unwrap();
+        assert!(halfway.brightness > START_BRIGHTNESS && halfway.brightness < 1.0);
+        assert!(!halfway.ring);
+
+        let wake = light.update(at("07:00:00")).unwrap();
+        assert_eq!(wake.brightness, 1.0);
+        assert_eq!(wake.accent, DAYLIGHT);
+        assert!(wake.ring);
+        assert!(!light.update(at("07:00:01")).unwrap().ring);
+        assert_eq!(light.update(at("07:09:59")).unwrap().brightness, 1.0);
+        assert_eq!(light.update(at("07:10:00")), None);
+    }
+
+    #[test]
+    fn ramps_across_midnight_and_rings_again_the_next_day() {
+        let mut light = alarm("00:10");
+        assert!(light.update(at("23:50:00")).is_some());
+        assert!(light.update(at("00:10:00")).unwrap().ring);
+        assert_eq!(light.update(at("12:00:00")), None);
+        assert!(light.update(at("00:10:00")).unwrap().ring);
+    }
+
+    #[test]
+    fn an_alarm_missed_before_startup_does_not_ring() {
+        let mut light = alarm("07:00");
+        let sunrise = light.update(at("07:05:00")).unwrap();
+        assert_eq!(sunrise.brightness, 1.0);
+        assert!(!sunrise.ring);
+        assert!(!light.update(at("07:06:00")).unwrap().ring);
+
+        // Started during the ramp, it still rings on time.
+        let mut light = alarm("07:00");
+        assert!(!light.update(at("06:59:00")).unwrap().ring);
+        assert!(light.update(at("07:00:00")).unwrap().ring);
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// How often the file's modification time is checked.
+const CHECK_SECS: f64 = 1.0;
+
+// The first line of a text file other scripts write to, re-read whenever
+// the file changes. A missing or empty file shows nothing.
+pub struct WatchedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_check: Option<f64>,
+    line: Option<String>,
+}
+
+impl WatchedFile {
+    pub fn new(path: PathBuf) -> Self {
+        WatchedFile {
+            path,
+            modified: None,
+            last_check: None,
+            line: None,
+        }
+    }
+
+    pub fn line(&mut self, now: f64) -> Option<&str> {
+        if self
+            .last_check
+            .is_none_or(|checked| now - checked >= CHECK_SECS)
+        {
+            self.last_check = Some(now);
+            let modified = std::fs::metadata(&self.path)
+                .and_then(|meta| meta.modified())
+                .ok();
+            if modified != self.modified {
+                self.modified = modified;
+                self.line = modified.and_then(|_| self.read_first_line());
+            }
+        }
+        self.line.as_deref()
+    }
+
+    fn read_first_line(&self) -> Option<String> {
+        let body = std::fs::read_to_string(&self.path).ok()?;
+        let line = body.lines().next()?.trim();
+        (!line.is_empty()).then(|| line.to_string())
+    }
+}
@@ -0,0 +1,358 @@
+// Current conditions next to the date: a condition glyph and the
+// temperature, from Open-Meteo (no key needed) or OpenWeather. Polled off
+// the render thread like the GitHub fetch; a failed poll keeps the last
+// reading and is retried sooner.
+#![cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+
+use crate::config::Config;
+use crate::platform;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+// A failed poll is retried after this instead of the full interval.
+const RETRY_SECS: f64 = 60.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeatherSource {
+    Off,
+    OpenMeteo,
+    OpenWeather,
+}
+
+impl FromStr for WeatherSource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "false" | "0" => Ok(WeatherSource::Off),
+            "open-meteo" | "openmeteo" | "on" | "true" | "1" => Ok(WeatherSource::OpenMeteo),
+            "openweather" | "openweathermap" => Ok(WeatherSource::OpenWeather),
+            _ => Err(format!("unknown weather source {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl FromStr for TemperatureUnit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "c" | "celsius" | "metric" => Ok(TemperatureUnit::Celsius),
+            "f" | "fahrenheit" | "imperial" => Ok(TemperatureUnit::Fahrenheit),
+            _ => Err(format!("unknown temperature unit {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    Clear,
+    Cloudy,
+    Fog,
+    Rain,
+    Snow,
+    Storm,
+}
+
+impl Condition {
+    // WMO weather interpretation codes, as Open-Meteo reports them.
+    pub fn from_wmo(code: u64) -> Condition {
+        match code {
+            0 | 1 => Condition::Clear,
+            45 | 48 => Condition::Fog,
+            51..=67 | 80..=82 => Condition::Rain,
+            71..=77 | 85 | 86 => Condition::Snow,
+            95..=99 => Condition::Storm,
+            _ => Condition::Cloudy,
+        }
+    }
+
+    // OpenWeather condition ids: 2xx thunderstorm, 3xx drizzle, 5xx rain,
+    // 6xx snow, 7xx mist and haze, 800 clear, 80x clouds.
+    pub fn from_openweather(id: u64) -> Condition {
+        match id {
+            200..=299 => Condition::Storm,
+            300..=599 => Condition::Rain,
+            600..=699 => Condition::Snow,
+            700..=799 => Condition::Fog,
+            800 | 801 => Condition::Clear,
+            _ => Condition::Cloudy,
+        }
+    }
+
+    // Its pixel-art glyph in the board font.
+    pub fn glyph(self) -> char {
+        match self {
+            Condition::Clear => '☀',
+            Condition::Cloudy => '☁',
+            Condition::Fog => '≡',
+            Condition::Rain => '☂',
+            Condition::Snow => '❄',
+            Condition::Storm => '⚡',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weather {
+    // In the configured unit.
+    pub temperature: f64,
+    pub condition: Condition,
+}
+
+impl Weather {
+    // `☀ 12°`
+    pub fn label(&self) -> String {
+        format!(
+            "{} {}°",
+            self.condition.glyph(),
+            self.temperature.round() as i64
+        )
+    }
+}
+
+// What to ask for and where from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    pub source: WeatherSource,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub unit: TemperatureUnit,
+    pub api_key: Option<String>,
+}
+
+impl Query {
+    // None when the panel is off or cannot be fetched as configured.
+    pub fn from_config(config: &Config) -> Option<Query> {
+        if config.weather == WeatherSource::Off {
+            return None;
+        }
+        let Some((latitude, longitude)) = config.location else {
+            log!("Weather is on but CHRONO_LOCATION is not set");
+            return None;
+        };
+        if config.weather == WeatherSource::OpenWeather && config.weather_api_key.is_none() {
+            log!("OpenWeather needs CHRONO_WEATHER_API_KEY");
+            return None;
+        }
+        Some(Query {
+            source: config.weather,
+            latitude,
+            longitude,
+            unit: config.weather_unit,
+            api_key: config.weather_api_key.clone(),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        match self.source {
+            WeatherSource::OpenWeather => format!(
+                "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&units={}&appid={}",
+                self.latitude,
+                self.longitude,
+                match self.unit {
+                    TemperatureUnit::Celsius => "metric",
+                    TemperatureUnit::Fahrenheit => "imperial",
+                },
+                self.api_key.as_deref().unwrap_or_default()
+            ),
+            WeatherSource::OpenMeteo | WeatherSource::Off => format!(
+                "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=temperature_2m,weather_code&temperature_unit={}",
+                self.latitude,
+                self.longitude,
+                match self.unit {
+                    TemperatureUnit::Celsius => "celsius",
+                    TemperatureUnit::Fahrenheit => "fahrenheit",
+                }
+            ),
+        }
+    }
+
+    #[cfg(feature = "network")]
+    pub fn parse(&self, body: &str) -> Option<Weather> {
+        let json: serde_json::Value = serde_json::from_str(body).ok()?;
+        match self.source {
+            WeatherSource::OpenWeather => Some(Weather {
+                temperature: json["main"]["temp"].as_f64()?,
+                condition: Condition::from_openweather(json["weather"][0]["id"].as_u64()?),
+            }),
+            WeatherSource::OpenMeteo | WeatherSource::Off => Some(Weather {
+                temperature: json["current"]["temperature_2m"].as_f64()?,
+                condition: Condition::from_wmo(json["current"]["weather_code"].as_u64()?),
+            }),
+        }
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn fetch(query: &Query) -> Option<Weather> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(4))
+        .build();
+    // Not retried in place: the next poll a minute later is the retry. The
+    // error is logged without the URL, which holds the OpenWeather key.
+    let body = match agent
+        .get(&query.url())
+        .set("User-Agent", "commit-clock")
+        .call()
+    {
+        Ok(response) => response.into_string().ok()?,
+        Err(ureq::Error::Status(code, _)) => {
+            log!("Weather fetch failed: HTTP {}", code);
+            return None;
+        }
+        Err(ureq::Error::Transport(e)) => {
+            log!("Weather fetch failed: {}", e.kind());
+            return None;
+        }
+    };
+    let weather = query.parse(&body);
+    if weather.is_none() {
+        log!("Unexpected weather response");
+    }
+    weather
+}
+
+// Blocking HTTP is not available in the browser, nor without the network
+// feature, so the panel stays empty there.
+#[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+fn fetch(_query: &Query) -> Option<Weather> {
+    None
+}
+
+pub struct WeatherWidget {
+    query: Query,
+    interval: f64,
+    next_poll: f64,
+    rx: Option<mpsc::Receiver<Option<Weather>>>,
+    weather: Option<Weather>,
+}
+
+impl WeatherWidget {
+    pub fn new(query: Query, interval_secs: f64) -> Self {
+        WeatherWidget {
+            query,
+            interval: interval_secs,
+            next_poll: 0.0,
+            rx: None,
+            weather: None,
+        }
+    }
+
+    pub fn weather(&mut self, now: f64) -> Option<Weather> {
+        if let Some(rx) = &self.rx {
+            match rx.try_recv() {
+                Ok(Some(weather)) => {
+                    self.weather = Some(weather);
+                    self.next_poll = now + self.interval;
+                    self.rx = None;
+                }
+                Ok(None) | Err(mpsc::TryRecvError::Disconnected) => {
+                    self.next_poll = now + RETRY_SECS.min(self.interval);
+                    self.rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+        if self.rx.is_none() && now >= self.next_poll {
+            // Pushed out while the poll runs; set properly once it lands.
+            self.next_poll = now + self.interval;
+            let query = self.query.clone();
+            self.rx = Some(platform::background(move || fetch(&query)));
+        }
+        self.weather
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(source: WeatherSource) -> Query {
+        Query {
+            source,
+            latitude: 50.45,
+            longitude: 30.52,
+            unit: TemperatureUnit::Celsius,
+            api_key: Some("KEY".to_string()),
+        }
+    }
+
+    #[test]
+    fn maps_conditions_from_both_sources() {
+        assert_eq!(Condition::from_wmo(0), Condition::Clear);
+        assert_eq!(Condition::from_wmo(3), Condition::Cloudy);
+        assert_eq!(Condition::from_wmo(48), Condition::Fog);
+        assert_eq!(Condition::from_wmo(81), Condition::Rain);
+        assert_eq!(Condition::from_wmo(75), Condition::Snow);
+        assert_eq!(Condition::from_wmo(95), Condition::Storm);
+        assert_eq!(Condition::from_openweather(211), Condition::Storm);
+        assert_eq!(Condition::from_openweather(301), Condition::Rain);
+        assert_eq!(Condition::from_openweather(601), Condition::Snow);
+        assert_eq!(Condition::from_openweather(741), Condition::Fog);
+        assert_eq!(Condition::from_openweather(800), Condition::Clear);
+        assert_eq!(Condition::from_openweather(804), Condition::Cloudy);
+    }
+
+    #[test]
+    fn labels_round_and_have_glyphs() {
+        let weather = Weather {
+            temperature: -2.6,
+            condition: Condition::Snow,
+        };
+        assert_eq!(weather.label(), "❄ -3°");
+        for condition in [
+            Condition::Clear,
+            Condition::Cloudy,
+            Condition::Fog,
+            Condition::Rain,
+            Condition::Snow,
+            Condition::Storm,
+        ] {
+            assert!(crate::glyphs::has_glyph(condition.glyph()));
+        }
+        assert!(crate::glyphs::has_glyph('°'));
+    }
+
+    #[test]
+    fn asks_each_source_in_the_configured_unit() {
+        let mut open_meteo = query(WeatherSource::OpenMeteo);
+        open_meteo.unit = TemperatureUnit::Fahrenheit;
+        assert!(open_meteo.url().contains("latitude=50.45&longitude=30.52"));
+        assert!(open_meteo.url().ends_with("temperature_unit=fahrenheit"));
+        let url = query(WeatherSource::OpenWeather).url();
+        assert!(url.contains("units=metric&appid=KEY"));
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn parses_both_responses() {
+        let body =
+            r#"{"current":{"time":"2026-10-16T09:00","temperature_2m":11.4,"weather_code":61}}"#;
+        assert_eq!(
+            query(WeatherSource::OpenMeteo).parse(body),
+            Some(Weather {
+                temperature: 11.4,
+                condition: Condition::Rain,
+            })
+        );
+        let body = r#"{"weather":[{"id":800,"main":"Clear"}],"main":{"temp":21.0}}"#;
+        assert_eq!(
+            query(WeatherSource::OpenWeather).parse(body),
+            Some(Weather {
+                temperature: 21.0,
+                condition: Condition::Clear,
+            })
+        );
+        assert_eq!(query(WeatherSource::OpenMeteo).parse("{}"), None);
+    }
+}
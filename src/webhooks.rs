@@ -0,0 +1,106 @@
+use chrono::{DateTime, FixedOffset};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    any(target_arch = "wasm32", not(feature = "network")),
+    allow(dead_code)
+)]
+pub enum WebhookEvent {
+    Hourly { time: DateTime<FixedOffset> },
+    ReviewRequested { title: String, url: String },
+    AlarmFired { time: DateTime<FixedOffset> },
+    PomodoroFinished { time: DateTime<FixedOffset> },
+}
+
+// Every name `CHRONO_WEBHOOK_EVENTS` accepts.
+pub const EVENT_NAMES: [&str; 4] = [
+    "hourly",
+    "review_requested",
+    "alarm_fired",
+    "pomodoro_finished",
+];
+
+impl WebhookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::Hourly { .. } => "hourly",
+            WebhookEvent::ReviewRequested { .. } => "review_requested",
+            WebhookEvent::AlarmFired { .. } => "alarm_fired",
+            WebhookEvent::PomodoroFinished { .. } => "pomodoro_finished",
+        }
+    }
+
+    #[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+    fn payload(&self) -> serde_json::Value {
+        use chrono::Timelike;
+        use serde_json::json;
+        match self {
+            WebhookEvent::Hourly { time } => json!({
+                "event": self.name(),
+                "time": time.to_rfc3339(),
+                "hour": time.hour(),
+            }),
+            WebhookEvent::ReviewRequested { title, url } => json!({
+                "event": self.name(),
+                "time": crate::platform::now().to_rfc3339(),
+                "title": title,
+                "url": url,
+            }),
+            WebhookEvent::AlarmFired { time } | WebhookEvent::PomodoroFinished { time } => json!({
+                "event": self.name(),
+                "time": time.to_rfc3339(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Webhooks {
+    urls: Vec<String>,
+    // Empty means every event is delivered.
+    events: Vec<String>,
+}
+
+impl Webhooks {
+    pub fn new(urls: Vec<String>, events: Vec<String>) -> Self {
+        Webhooks { urls, events }
+    }
+
+    pub fn send(&self, event: WebhookEvent) {
+        if self.urls.is_empty() {
+            return;
+        }
+        if !self.events.is_empty() && !self.events.iter().any(|name| name == event.name()) {
+            return;
+        }
+
+        deliver(self.urls.clone(), &event);
+    }
+}
+
+#[cfg(all(feature = "network", not(target_arch = "wasm32")))]
+fn deliver(urls: Vec<String>, event: &WebhookEvent) {
+    let body = event.payload().to_string();
+    std::thread::spawn(move || {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(std::time::Duration::from_secs(4))
+            .build();
+        for url in urls {
+            let resp = agent
+                .post(&url)
+                .set("User-Agent", "commit-clock")
+                .set("Content-Type", "application/json")
+                .send_string(&body);
+            if let Err(e) = resp {
+                log!("Webhook {} failed: {}", url, e);
+            }
+        }
+    });
+}
+
+#[cfg(any(target_arch = "wasm32", not(feature = "network")))]
+fn deliver(urls: Vec<String>, _event: &WebhookEvent) {
+    for url in urls {
+        log!("Webhook {} skipped: not supported in this build", url);
+    }
+}
@@ -0,0 +1,1477 @@
+// The pieces drawn on top of the board: the clock itself, the GitHub button
+// and its labels, and the PR list.
+use crate::actions::WorkflowRuns;
+use crate::calendar::{self, FirstDay};
+use crate::clock::AmPmMark;
+use crate::config::JiraLinks;
+use crate::fetch::ConnectionStatus;
+use crate::glyphs::widest_digits;
+use crate::layout::{self, TextOverflow};
+use crate::provider::{CiState, CiStatus, ReviewDecision, ReviewLoad, ReviewStatus, WorkItem};
+use crate::render::*;
+use crate::textures::TextureCache;
+use chrono::{Datelike, NaiveDate};
+use macroquad::prelude::*;
+use qrcodegen::QrCode;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ClockLayout {
+    pub time_bottom: f32,
+    pub left_x: f32,
+    pub right_x: f32,
+    pub board_grid: PixelGrid,
+    pub pr_grid: PixelGrid,
+    pub pr_area: Rect,
+}
+
+// Which work list the lower panel shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkPanel {
+    Prs,
+    Issues,
+}
+
+impl WorkPanel {
+    pub fn heading(self) -> &'static str {
+        match self {
+            WorkPanel::Prs => "MY PRS",
+            WorkPanel::Issues => "MY ISSUES",
+        }
+    }
+}
+
+// What is drawn around and between the time digits.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeMarks<'a> {
+    pub am_pm: Option<AmPmMark<'a>>,
+    // False leaves the `:` cells dark, for the blinking colon.
+    pub colon: bool,
+    // The seconds pulse cell next to the time, and whether it is lit.
+    pub pulse: Option<bool>,
+    // Tenths or hundredths, which change too fast to size the clock by.
+    pub subsecond: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemHit {
+    pub rect: Rect,
+    pub url: String,
+}
+
+// Text shown after the cursor rests on `rect` for a moment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tooltip {
+    pub rect: Rect,
+    pub lines: Vec<String>,
+}
+
+pub fn draw_clock(
+    year_str: &str,
+    date_str: &str,
+    time_str: &str,
+    marks: TimeMarks,
+    minute_seed: i32,
+    effects: bool,
+    areas: layout::Areas,
+) -> ClockLayout {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let container = ctx.container;
+        let area = areas.clock;
+        let board_grid = grid_from_height(42.0, 0.25);
+        let gap_small = 2.0;
+        let gap_large = 2.0;
+        let padding = 12.0;
+
+        let (year_str, date_str) = if areas.compact {
+            ("", "")
+        } else {
+            (year_str, date_str)
+        };
+        let am_pm = marks.am_pm;
+        // Portrait stacks the hours, minutes and seconds as rows.
+        let time_rows = if areas.portrait {
+            time_str.split(':').collect::<Vec<_>>()
+        } else {
+            vec![time_str]
+        };
+        // Sub-second digits are sized as if every digit were a `0`, so a `1`
+        // ticking past ten times a second does not rescale the clock or
+        // shake what sits to the right of the time.
+        let templates = time_rows
+            .iter()
+            .map(|row| {
+                if marks.subsecond {
+                    widest_digits(row)
+                } else {
+                    row.to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        let rows = templates.len() as f32;
+        // Shrink the clock when the PR list takes part of the window.
+        let natural_width = templates
+            .iter()
+            .map(|template| measure_pixel_text(template, board_grid).x)
+            .fold(0.0, f32::max)
+            + match am_pm {
+                Some(AmPmMark::Text(_)) => board_grid.step() * 3.0,
+                Some(AmPmMark::Dot { .. }) => board_grid.step() * 2.0,
+                None if marks.pulse.is_some() => board_grid.step() * 2.0,
+                None => 0.0,
+            };
+        // The date row is wider than a stacked time row.
+        let natural_width = if areas.portrait {
+            natural_width.max(measure_pixel_text(date_str, grid_from_height(24.0, 0.25)).x)
+        } else {
+            natural_width
+        };
+        let header = if areas.compact {
+            0.0
+        } else {
+            8.0 + 24.0 + gap_small + gap_large
+        };
+        let natural_height = header + 42.0 * rows + board_grid.step() * (rows - 1.0);
+        // Stacked rows are narrow enough to grow, leaving room for the list.
+        let (max_scale, max_height) = if areas.portrait {
+            (2.0, area.h * 0.6)
+        } else {
+            (1.0, area.h)
+        };
+        let scale = ((area.w - padding * 2.0) / natural_width)
+            .min((max_height - padding * 2.0) / natural_height)
+            .clamp(0.3, max_scale);
+        let year_grid = grid_from_height(8.0 * scale.max(0.9), 0.25);
+        let date_grid = grid_from_height(24.0 * scale, 0.25);
+        let time_grid = grid_from_height(42.0 * scale, 0.25);
+
+        let year_size = measure_pixel_text(year_str, year_grid);
+        let date_size = measure_pixel_text(date_str, date_grid);
+        let suffix = match am_pm {
+            Some(AmPmMark::Text(suffix)) => Some(suffix),
+            _ => None,
+        };
+        let am_pm_size = suffix
+            .map(|value| measure_pixel_text(value, year_grid))
+            .unwrap_or(vec2(0.0, 0.0));
+        draw_background(board_grid);
+        if effects {
+            draw_noise_pixels(board_grid);
+        }
+
+        let mut year_origin = vec2(area.x + padding, area.y + padding);
+        year_origin.x = snap_to_grid(container.x, year_origin.x, board_grid.step());
+        year_origin.y = snap_to_grid(container.y, year_origin.y, board_grid.step());
+
+        let mut date_origin = vec2(area.x + padding, year_origin.y + year_size.y + gap_small);
+        date_origin.x = snap_to_grid(container.x, date_origin.x, board_grid.step());
+        date_origin.y = snap_to_grid(container.y, date_origin.y, board_grid.step());
+
+        let mut row_top = if areas.compact {
+            area.y + padding
+        } else {
+            date_origin.y + date_size.y + gap_large
+        };
+        let mut time_origins = Vec::new();
+        for template in &templates {
+            let origin = vec2(
+                snap_to_grid(container.x, area.x + padding, board_grid.step()),
+                snap_to_grid(container.y, row_top, board_grid.step()),
+            );
+            row_top = origin.y + measure_pixel_text(template, time_grid).y + time_grid.step();
+            time_origins.push(origin);
+        }
+        // The AM/PM mark and the pulse sit next to the last row.
+        let time_origin = *time_origins.last().unwrap_or(&date_origin);
+        let time_size = measure_pixel_text(templates.last().map_or("", String::as_str), time_grid);
+
+        let active = ctx.theme.active_color;
+
+        let mut blocked = Vec::new();
+        blocked.extend(collect_glyph_rects(year_str, year_origin, year_grid));
+        blocked.extend(collect_glyph_rects(date_str, date_origin, date_grid));
+        for (row, origin) in time_rows.iter().zip(&time_origins) {
+            blocked.extend(collect_glyph_rects(row, *origin, time_grid));
+        }
+
+        let mut am_pm_origin = None;
+        if suffix.is_some() {
+            let mut origin = vec2(
+                time_origin.x + time_size.x + time_grid.step(),
+                time_origin.y + time_size.y - am_pm_size.y,
+            );
+            origin.x = snap_to_grid(container.x, origin.x, board_grid.step());
+            origin.y = snap_to_grid(container.y, origin.y, board_grid.step());
+            am_pm_origin = Some(origin);
+        }
+
+        if let (Some(suffix), Some(origin)) = (suffix, am_pm_origin) {
+            blocked.extend(collect_glyph_rects(suffix, origin, year_grid));
+        }
+
+        // Top-right corner of the time block.
+        let pm_dot = matches!(am_pm, Some(AmPmMark::Dot { pm: true })).then(|| {
+            let x = snap_to_grid(
+                container.x,
+                time_origin.x + time_size.x + time_grid.step(),
+                board_grid.step(),
+            );
+            Rect::new(x, time_origin.y, time_grid.cell, time_grid.cell)
+        });
+        blocked.extend(pm_dot);
+        // Bottom-right, or top-right under an AM/PM text.
+        let pulse = marks.pulse.map(|lit| {
+            let x = snap_to_grid(
+                container.x,
+                time_origin.x + time_size.x + time_grid.step(),
+                board_grid.step(),
+            );
+            let y = match am_pm {
+                Some(AmPmMark::Text(_)) => time_origin.y,
+                _ => time_origin.y + time_size.y - time_grid.cell,
+            };
+            (Rect::new(x, y, time_grid.cell, time_grid.cell), lit)
+        });
+        blocked.extend(pulse.map(|(rect, _)| rect));
+
+        if effects {
+            draw_active_speckles(board_grid, minute_seed, &blocked);
+        }
+        draw_pixel_text(year_str, year_origin, year_grid, active, false);
+        draw_pixel_text(date_str, date_origin, date_grid, active, false);
+        for (row, origin) in time_rows.iter().zip(&time_origins) {
+            if marks.colon {
+                draw_pixel_text(row, *origin, time_grid, active, false);
+            } else {
+                draw_pixel_text_without(row, *origin, time_grid, active, ':');
+            }
+        }
+
+        if let Some(dot) = pm_dot {
+            draw_cell(dot.x, dot.y, dot.w, active);
+        }
+        if let Some((cell, true)) = pulse {
+            draw_cell(cell.x, cell.y, cell.w, active);
+        }
+
+        if let (Some(suffix), Some(origin)) = (suffix, am_pm_origin) {
+            let am_pm_color = ctx
+                .theme
+                .am_pm_color
+                .unwrap_or(Color::new(active.r, active.g, active.b, 0.75));
+            draw_pixel_text(suffix, origin, year_grid, am_pm_color, false);
+        }
+
+        let time_bottom = time_origin.y + time_size.y;
+        let right_x = area.x + area.w - (year_origin.x - area.x);
+        let pr_area = areas.prs.unwrap_or_else(|| {
+            let y = snap_to_grid(
+                container.y,
+                time_bottom + board_grid.step() * 3.0,
+                board_grid.step(),
+            );
+            Rect::new(
+                year_origin.x,
+                y,
+                right_x - year_origin.x,
+                container.y + container.h - y,
+            )
+        });
+
+        ClockLayout {
+            time_bottom,
+            left_x: year_origin.x,
+            right_x,
+            board_grid,
+            pr_grid: year_grid,
+            pr_area,
+        }
+    })
+}
+
+// A window just big enough for the time at full size in compact mode;
+// `marked` leaves room for an AM/PM mark or the seconds pulse.
+pub fn compact_window_size(time_str: &str, marked: bool) -> (f32, f32) {
+    let grid = grid_from_height(42.0, 0.25);
+    let size = measure_pixel_text(&widest_digits(time_str), grid);
+    let marks = if marked { grid.step() * 3.0 } else { 0.0 };
+    // The clock's padding on both sides, and a step for snapping.
+    let padding = 12.0 * 2.0 + grid.step();
+    ((size.x + marks + padding).ceil(), (size.y + padding).ceil())
+}
+
+pub fn github_button_rect(container: Rect, grid: PixelGrid) -> Rect {
+    let size = (grid.step() * 3.0).round().max(grid.step());
+    let padding = 8.0;
+    let mut x = container.x + container.w - size - padding;
+    let mut y = container.y + padding;
+    x = snap_to_grid(container.x, x, grid.step());
+    y = snap_to_grid(container.y, y, grid.step());
+    Rect::new(x, y, size, size)
+}
+
+pub fn draw_github_button(status: ConnectionStatus, icon: &Texture2D, rect: Rect) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let button_color = Color::new(
+            ctx.theme.inactive_color.r * 0.9,
+            ctx.theme.inactive_color.g * 0.9,
+            ctx.theme.inactive_color.b * 0.9,
+            1.0,
+        );
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, button_color);
+
+        let icon_color = match status {
+            ConnectionStatus::Connected => ctx.theme.active_color,
+            ConnectionStatus::RateLimited => ctx.theme.status.pending,
+            ConnectionStatus::TimedOut | ConnectionStatus::Unreachable => ctx.theme.status.failure,
+            ConnectionStatus::Disconnected | ConnectionStatus::Unknown => {
+                Color::new(1.0, 1.0, 1.0, 1.0)
+            }
+        };
+
+        let icon_size = rect.w.min(rect.h);
+        let icon_x = rect.x + (rect.w - icon_size) * 0.5;
+        let icon_y = rect.y + (rect.h - icon_size) * 0.5;
+        draw_texture_ex(
+            icon,
+            icon_x,
+            icon_y,
+            icon_color,
+            DrawTextureParams {
+                dest_size: Some(vec2(icon_size, icon_size)),
+                ..Default::default()
+            },
+        );
+    });
+}
+
+pub fn notification_badge_label(count: usize) -> String {
+    if count > 99 {
+        "99".to_string()
+    } else {
+        count.to_string()
+    }
+}
+
+// Straddles the top-right corner of the GitHub button.
+pub fn notification_badge_rect(count: usize, button: Rect) -> Rect {
+    let grid = grid_from_height(8.0, 0.25);
+    let size = measure_pixel_text(&notification_badge_label(count), grid);
+    let w = size.x + grid.step() * 2.0;
+    let h = size.y + grid.step() * 2.0;
+    Rect::new(
+        button.x + button.w - w * 0.5,
+        (button.y - h * 0.5).max(0.0),
+        w,
+        h,
+    )
+}
+
+pub fn draw_notification_badge(count: usize, rect: Rect) {
+    let grid = grid_from_height(8.0, 0.25);
+    let color = FRAME_CONTEXT.with(|ctx| ctx.borrow().theme.status.failure);
+    draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+    draw_pixel_text(
+        &notification_badge_label(count),
+        vec2(rect.x + grid.step(), rect.y + grid.step()),
+        grid,
+        Color::new(1.0, 1.0, 1.0, 1.0),
+        false,
+    );
+}
+
+pub fn is_jira_key(value: &str) -> bool {
+    if let Some((left, right)) = value.split_once('-') {
+        if left.len() >= 2
+            && !right.is_empty()
+            && left.chars().all(|c| c.is_ascii_uppercase())
+            && right.chars().all(|c| c.is_ascii_digit())
+        {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn find_jira_in_line(line: &str) -> Option<(usize, usize, String)> {
+    let mut token = String::new();
+    let mut token_start = 0usize;
+
+    for (idx, ch) in line.char_indices() {
+        if ch.is_ascii_alphanumeric() || ch == '-' {
+            if token.is_empty() {
+                token_start = idx;
+            }
+            token.push(ch);
+        } else if !token.is_empty() {
+            if is_jira_key(&token) {
+                return Some((token_start, idx, token.clone()));
+            }
+            token.clear();
+        }
+    }
+
+    if !token.is_empty() && is_jira_key(&token) {
+        return Some((token_start, line.len(), token));
+    }
+    None
+}
+
+// The rows a fetch added and removed, animated by `Effect::ListChange`.
+// Removed rows stay in the list at their old place until they have slid out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListChange {
+    entering: HashSet<String>,
+    leaving: Vec<(usize, WorkItem)>,
+}
+
+impl ListChange {
+    pub fn between(old: &[WorkItem], new: &[WorkItem]) -> ListChange {
+        let old_urls = old.iter().map(|item| &item.url).collect::<HashSet<_>>();
+        let new_urls = new.iter().map(|item| &item.url).collect::<HashSet<_>>();
+        ListChange {
+            entering: new
+                .iter()
+                .filter(|item| !old_urls.contains(&item.url))
+                .map(|item| item.url.clone())
+                .collect(),
+            leaving: old
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| !new_urls.contains(&item.url))
+                .map(|(idx, item)| (idx, item.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entering.is_empty() && self.leaving.is_empty()
+    }
+
+    // `items` with the leaving rows put back where they were.
+    pub fn merged(&self, items: &[WorkItem]) -> Vec<WorkItem> {
+        let mut merged = items.to_vec();
+        for (idx, item) in self.leaving.iter() {
+            merged.insert((*idx).min(merged.len()), item.clone());
+        }
+        merged
+    }
+
+    // How much of a row is shown, eased: entering rows grow from 0 to 1,
+    // leaving rows shrink back to 0.
+    pub fn presence(&self, url: &str, progress: f32) -> f32 {
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        if self.entering.contains(url) {
+            eased
+        } else if self.leaving.iter().any(|(_, item)| item.url == url) {
+            1.0 - eased
+        } else {
+            1.0
+        }
+    }
+}
+
+// What the lower panel lists; `scroll` is the number of items scrolled past.
+#[derive(Clone, Copy)]
+pub struct PrListView<'a> {
+    pub items: &'a [WorkItem],
+    pub heading: Option<&'a str>,
+    pub icon: Option<&'a Texture2D>,
+    // Author avatars by URL; items without one keep `icon`.
+    pub avatars: &'a TextureCache<Texture2D>,
+    pub scroll: usize,
+    // Unix seconds, for the age on each item's meta line.
+    pub now: i64,
+    // Items not updated for this many days are drawn dim amber.
+    pub stale_days: Option<u32>,
+    pub jira: &'a JiraLinks,
+    // Rows sliding in and out, with the effect's progress.
+    pub change: Option<(&'a ListChange, f32)>,
+}
+
+pub fn draw_pr_list(
+    view: PrListView,
+    ci: &[CiStatus],
+    decisions: &[ReviewStatus],
+    conflicts: &[String],
+    layout: ClockLayout,
+    overflow: TextOverflow,
+) -> (Vec<ItemHit>, Vec<Tooltip>) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let area = layout.pr_area;
+        let left_x = area.x;
+
+        let icon_size = 16.0;
+        let font_size = (14.0 * ctx.theme.text_scale).round() as u16;
+        let line_height = font_size as f32 + 4.0;
+        let meta_font_size = (11.0 * ctx.theme.text_scale).round() as u16;
+        let meta_line_height = meta_font_size as f32 + 3.0;
+        let meta_color = Color::new(1.0, 1.0, 1.0, ctx.theme.min_alpha.max(0.45));
+        let item_gap = 6.0;
+        let mut line_y = area.y;
+        let mut hits = Vec::new();
+        let mut tooltips = Vec::new();
+        let icon = view.icon;
+        if let Some(heading) = view.heading {
+            if line_y + line_height <= area.y + area.h {
+                draw_text(
+                    heading,
+                    left_x,
+                    line_y + font_size as f32,
+                    font_size as f32,
+                    ctx.theme.active_color,
+                );
+                line_y += line_height + item_gap;
+            }
+        }
+        let ci_size = 6.0;
+        let list_top = line_y;
+        let mut drawn = 0usize;
+        let merged;
+        let items = match view.change {
+            Some((change, _)) => {
+                merged = change.merged(view.items);
+                &merged[..]
+            }
+            None => view.items,
+        };
+        for pr in items.iter().skip(view.scroll) {
+            let presence = view
+                .change
+                .map_or(1.0, |(change, progress)| change.presence(&pr.url, progress));
+            let fade = |color: Color| Color {
+                a: color.a * presence,
+                ..color
+            };
+            // Rows slide in from, and out to, the right.
+            let row_x = left_x + (1.0 - presence) * area.w * 0.25;
+            let mut text_x = if icon.is_some() {
+                row_x + icon_size + layout.pr_grid.step()
+            } else {
+                row_x
+            };
+            let ci_state = ci
+                .iter()
+                .find(|status| status.url == pr.url)
+                .map(|status| status.state);
+            if ci_state.is_some() {
+                text_x += ci_size + layout.pr_grid.step() * 3.0;
+            }
+            let conflicted = conflicts.contains(&pr.url);
+            if conflicted {
+                text_x += ci_size + layout.pr_grid.step() * 3.0;
+            }
+            // Titles double as a merge-readiness board; gone-quiet PRs stand
+            // out whatever their reviews say.
+            let stale = view
+                .stale_days
+                .is_some_and(|days| pr.is_stale(view.now, days));
+            let title_color = fade(if stale {
+                let pending = ctx.theme.status.pending;
+                Color::new(pending.r, pending.g, pending.b, 0.7)
+            } else {
+                match decisions
+                    .iter()
+                    .find(|status| status.url == pr.url)
+                    .map(|status| status.decision)
+                {
+                    Some(ReviewDecision::Approved) => ctx.theme.status.approved,
+                    Some(ReviewDecision::ChangesRequested) => ctx.theme.status.changes_requested,
+                    Some(ReviewDecision::Pending) | None => Color::new(1.0, 1.0, 1.0, 1.0),
+                }
+            });
+            let max_width = area.x + area.w - text_x;
+            let title = match &pr.account {
+                Some(account) => format!("[{}] {}", account, pr.title),
+                None => pr.title.clone(),
+            };
+            let wrapped = match overflow {
+                TextOverflow::Wrap => wrap_text_to_width(&title, max_width, font_size),
+                TextOverflow::Ellipsis => {
+                    vec![truncate_text_to_width(&title, max_width, font_size)]
+                }
+            };
+            if wrapped.iter().all(|line| line.trim().is_empty()) {
+                continue;
+            }
+            if line_y + line_height > area.y + area.h {
+                break;
+            }
+            let avatar = pr.avatar_url.as_ref().and_then(|url| view.avatars.get(url));
+            if let Some(texture) = avatar.or(icon) {
+                let icon_y = line_y + (line_height - icon_size) * 0.5 + 2.0;
+                let (mx, my) = mouse_position();
+                let hover =
+                    point_in_rect(vec2(mx, my), Rect::new(row_x, icon_y, icon_size, icon_size));
+                // Avatars keep their own colors and dim slightly on hover.
+                let icon_color = match (avatar.is_some(), hover) {
+                    (true, false) => Color::new(1.0, 1.0, 1.0, 1.0),
+                    (true, true) => Color::new(1.0, 1.0, 1.0, 0.7),
+                    (false, true) => Color::new(1.0, 1.0, 1.0, 1.0),
+                    (false, false) => ctx.theme.active_color,
+                };
+                draw_texture_ex(
+                    texture,
+                    row_x,
+                    icon_y,
+                    fade(icon_color),
+                    DrawTextureParams {
+                        dest_size: Some(vec2(icon_size, icon_size)),
+                        ..Default::default()
+                    },
+                );
+                if presence >= 1.0 {
+                    hits.push(ItemHit {
+                        rect: Rect::new(row_x, icon_y, icon_size, icon_size),
+                        url: pr.url.clone(),
+                    });
+                }
+            }
+            let mark_y = line_y + (line_height - ci_size) * 0.5 + 2.0;
+            if let Some(state) = ci_state {
+                let color = ci_color(state, &ctx.theme.status);
+                let slots = if conflicted { 2.0 } else { 1.0 };
+                draw_rectangle(
+                    text_x - (ci_size + layout.pr_grid.step() * 3.0) * slots,
+                    mark_y,
+                    ci_size,
+                    ci_size,
+                    fade(color),
+                );
+            }
+            // A small cross for a branch that needs a rebase.
+            if conflicted {
+                let x = text_x - ci_size - layout.pr_grid.step() * 3.0;
+                let color = fade(ci_color(CiState::Failure, &ctx.theme.status));
+                draw_line(x, mark_y, x + ci_size, mark_y + ci_size, 1.5, color);
+                draw_line(x + ci_size, mark_y, x, mark_y + ci_size, 1.5, color);
+            }
+            let mut current_y = line_y;
+            for (idx, line) in wrapped.iter().enumerate() {
+                let y = current_y + font_size as f32 + line_height * idx as f32;
+                let jira_link = find_jira_in_line(line)
+                    .and_then(|(start, end, key)| Some((start, end, view.jira.url(&key)?)));
+                if let Some((start, end, jira_url)) = jira_link {
+                    let before = &line[..start];
+                    let key_text = &line[start..end];
+                    let after = &line[end..];
+
+                    let before_width = measure_text(before, None, font_size, 1.0).width;
+                    let key_width = measure_text(key_text, None, font_size, 1.0).width;
+                    let key_rect = Rect::new(
+                        text_x + before_width,
+                        y - font_size as f32,
+                        key_width,
+                        line_height,
+                    );
+
+                    let (mx, my) = mouse_position();
+                    let hover = point_in_rect(vec2(mx, my), key_rect);
+                    let key_color = fade(if hover {
+                        Color::new(1.0, 1.0, 1.0, 1.0)
+                    } else {
+                        ctx.theme.active_color
+                    });
+
+                    draw_text(before, text_x, y, font_size as f32, title_color);
+                    draw_text(
+                        key_text,
+                        text_x + before_width,
+                        y,
+                        font_size as f32,
+                        key_color,
+                    );
+                    draw_text(
+                        after,
+                        text_x + before_width + key_width,
+                        y,
+                        font_size as f32,
+                        title_color,
+                    );
+
+                    if presence >= 1.0 {
+                        hits.push(ItemHit {
+                            rect: key_rect,
+                            url: jira_url,
+                        });
+                    }
+                } else {
+                    draw_text(line, text_x, y, font_size as f32, title_color);
+                }
+            }
+            current_y += line_height * wrapped.len() as f32;
+            if let Some(meta) = pr.meta_line(view.now) {
+                if current_y + meta_line_height <= area.y + area.h {
+                    draw_text(
+                        &meta,
+                        text_x,
+                        current_y + meta_font_size as f32,
+                        meta_font_size as f32,
+                        fade(meta_color),
+                    );
+                    current_y += meta_line_height;
+                }
+            }
+
+            if presence >= 1.0 {
+                let row = Rect::new(row_x, line_y, area.x + area.w - row_x, current_y - line_y);
+                let (mx, my) = mouse_position();
+                if point_in_rect(vec2(mx, my), row) {
+                    let accent = ctx.theme.active_color;
+                    draw_rectangle(
+                        row.x,
+                        row.y,
+                        row.w,
+                        row.h,
+                        Color::new(accent.r, accent.g, accent.b, 0.08),
+                    );
+                }
+                // After the icon and Jira hits, which win where they overlap.
+                hits.push(ItemHit {
+                    rect: row,
+                    url: pr.url.clone(),
+                });
+                // The full title, which wrapping or `...` may have cut.
+                tooltips.push(Tooltip {
+                    rect: row,
+                    lines: [
+                        Some(title),
+                        pr.repo.as_ref().map(|repo| match pr.number {
+                            Some(number) => format!("{}#{}", repo, number),
+                            None => repo.clone(),
+                        }),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                });
+            }
+
+            // Entering and leaving rows take part of their height, so the
+            // rows below slide instead of jumping.
+            line_y += (current_y + item_gap - line_y) * presence;
+            drawn += 1;
+        }
+
+        // A thin track along the right edge once the list does not fit.
+        let total = view.items.len();
+        if total > 0 && (view.scroll > 0 || view.scroll + drawn < total) {
+            let track = Rect::new(
+                area.x + area.w - 2.0,
+                list_top,
+                2.0,
+                area.y + area.h - list_top,
+            );
+            draw_rectangle(
+                track.x,
+                track.y,
+                track.w,
+                track.h,
+                Color::new(1.0, 1.0, 1.0, 0.08),
+            );
+            draw_rectangle(
+                track.x,
+                track.y + track.h * view.scroll as f32 / total as f32,
+                track.w,
+                (track.h * drawn.max(1) as f32 / total as f32).max(4.0),
+                ctx.theme.active_color,
+            );
+        }
+        (hits, tooltips)
+    })
+}
+
+// One column per teammate, growing up from the baseline of the time row.
+// Reviewers well above the team average are drawn in a warning color.
+pub fn draw_review_load(load: &[ReviewLoad], layout: ClockLayout) {
+    if load.is_empty() {
+        return;
+    }
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = layout.board_grid;
+        let label_grid = layout.pr_grid;
+        let step = grid.step();
+        let max_cells = 5usize;
+        let max_count = load
+            .iter()
+            .map(|member| member.count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mean = load.iter().map(|member| member.count).sum::<usize>() as f32 / load.len() as f32;
+        let column = step.max(measure_pixel_text("W", label_grid).x + label_grid.step());
+        let right = layout.right_x;
+        let baseline =
+            layout.time_bottom - measure_pixel_text("W", label_grid).y - label_grid.step();
+        let warning = ctx.theme.status.pending;
+
+        for (idx, member) in load.iter().rev().enumerate() {
+            let x = snap_to_grid(ctx.container.x, right - column * (idx + 1) as f32, step);
+            let overloaded = member.count >= 3 && member.count as f32 > mean * 1.5;
+            let color = if overloaded {
+                warning
+            } else {
+                ctx.theme.active_color
+            };
+            let cells = if member.count == 0 {
+                0
+            } else {
+                ((member.count * max_cells) as f32 / max_count as f32)
+                    .ceil()
+                    .max(1.0) as usize
+            };
+            for cell in 0..max_cells {
+                let y = baseline - (cell + 1) as f32 * step;
+                let cell_color = if cell < cells {
+                    color
+                } else {
+                    Color::new(1.0, 1.0, 1.0, 0.06)
+                };
+                draw_cell(x, y, grid.cell, cell_color);
+            }
+            let initial = member
+                .login
+                .chars()
+                .next()
+                .map(|ch| ch.to_ascii_uppercase().to_string())
+                .unwrap_or_default();
+            draw_pixel_text(
+                &initial,
+                vec2(x, baseline + label_grid.step()),
+                label_grid,
+                color,
+                false,
+            );
+        }
+    });
+}
+
+// Shown where the PR list goes; clicking reopens the verification page.
+pub fn draw_sign_in_code(code: &str, url: &str, layout: ClockLayout) -> ItemHit {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let label = format!("CODE {}", code.to_uppercase());
+        let size = measure_pixel_text(&label, layout.pr_grid);
+        let rect = Rect::new(layout.pr_area.x, layout.pr_area.y, size.x, size.y);
+        let (mx, my) = mouse_position();
+        let color = if point_in_rect(vec2(mx, my), rect) {
+            Color::new(1.0, 1.0, 1.0, 1.0)
+        } else {
+            ctx.theme.active_color
+        };
+        draw_pixel_text(&label, vec2(rect.x, rect.y), layout.pr_grid, color, false);
+        ItemHit {
+            rect,
+            url: url.to_string(),
+        }
+    })
+}
+
+// A shared focus session's QR code where the PR list goes, dark on light
+// with a two-module quiet zone so phone cameras pick it up. Clicking it
+// opens the link.
+pub fn draw_share_code(code: &QrCode, url: &str, layout: ClockLayout) -> ItemHit {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let area = layout.pr_area;
+        let modules = code.size() + 4;
+        let module = (area.w.min(area.h) / modules as f32).floor().max(1.0);
+        let side = module * modules as f32;
+        let rect = Rect::new(area.x, area.y, side, side);
+        draw_rectangle(rect.x, rect.y, side, side, WHITE);
+        for y in 0..code.size() {
+            for x in 0..code.size() {
+                if code.get_module(x, y) {
+                    draw_rectangle(
+                        rect.x + (x + 2) as f32 * module,
+                        rect.y + (y + 2) as f32 * module,
+                        module,
+                        module,
+                        BLACK,
+                    );
+                }
+            }
+        }
+        let caption = "SCAN TO JOIN";
+        let caption_x = rect.x + side + layout.pr_grid.step() * 2.0;
+        if caption_x + measure_pixel_text(caption, layout.pr_grid).x <= area.x + area.w {
+            draw_pixel_text(
+                caption,
+                vec2(caption_x, rect.y),
+                layout.pr_grid,
+                ctx.theme.active_color,
+                false,
+            );
+        }
+        ItemHit {
+            rect,
+            url: url.to_string(),
+        }
+    })
+}
+
+// Right-aligned next to the GitHub button, on the same row as the year.
+pub fn draw_button_label(
+    label: &str,
+    url: Option<&str>,
+    button: Rect,
+    layout: ClockLayout,
+) -> Option<ItemHit> {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = layout.pr_grid;
+        // Leave room for the year at the start of the row.
+        let max_width = button.x
+            - layout.board_grid.step() * 2.0
+            - layout.left_x
+            - measure_pixel_text("0000", grid).x;
+        let label = truncate_pixel_text(label, max_width, grid);
+        let size = measure_pixel_text(&label, grid);
+        let x = button.x - layout.board_grid.step() - size.x;
+        let y = button.y;
+        let rect = Rect::new(x, y, size.x, size.y);
+        let (mx, my) = mouse_position();
+        let hover = url.is_some() && point_in_rect(vec2(mx, my), rect);
+        let color = if hover {
+            Color::new(1.0, 1.0, 1.0, 1.0)
+        } else {
+            ctx.theme.active_color
+        };
+        draw_pixel_text(&label, vec2(x, y), grid, color, false);
+        url.map(|url| ItemHit {
+            rect,
+            url: url.to_string(),
+        })
+    })
+}
+
+// The timezone and the last poll, small and dim in the bottom-right corner
+// out of the clock's way.
+pub fn draw_status_line(label: &str) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = grid_from_height(7.0, 0.25);
+        let size = measure_pixel_text(label, grid);
+        let padding = 6.0;
+        let origin = vec2(
+            ctx.container.x + ctx.container.w - size.x - padding,
+            ctx.container.y + ctx.container.h - size.y - padding,
+        );
+        let color = ctx.theme.active_color;
+        draw_pixel_text(
+            label,
+            origin,
+            grid,
+            Color::new(color.r, color.g, color.b, 0.45),
+            true,
+        );
+    });
+}
+
+// A line in the bottom-left stack; `warning` draws it in amber, as for a
+// meeting about to start.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetLine {
+    pub text: String,
+    pub warning: bool,
+}
+
+impl From<String> for WidgetLine {
+    fn from(text: String) -> Self {
+        WidgetLine {
+            text,
+            warning: false,
+        }
+    }
+}
+
+// Watched file and command output, stacked up from the bottom-left corner
+// across from the updated label.
+pub fn draw_widget_lines(lines: &[WidgetLine]) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = grid_from_height(10.0, 0.25);
+        let padding = 6.0;
+        let mut bottom = ctx.container.y + ctx.container.h - padding;
+        for line in lines.iter().rev() {
+            let text = truncate_pixel_text(&line.text, ctx.container.w * 0.5, grid);
+            let size = measure_pixel_text(&text, grid);
+            let origin = vec2(ctx.container.x + padding, bottom - size.y);
+            let color = if line.warning {
+                ctx.theme.status.pending
+            } else {
+                ctx.theme.active_color
+            };
+            draw_pixel_text(
+                &text,
+                origin,
+                grid,
+                Color::new(color.r, color.g, color.b, 0.8),
+                true,
+            );
+            bottom -= size.y + grid.step() * 2.0;
+        }
+    });
+}
+
+// The contribution strip, centered along the bottom edge: a column per week,
+// unlit cells for quiet days and the accent greens for busier ones.
+pub fn draw_heatmap(cells: &[(usize, usize, u8)], layout: ClockLayout) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = layout.board_grid;
+        let step = grid.step();
+        let padding = layout.left_x - ctx.container.x;
+        let columns = cells
+            .iter()
+            .map(|(column, _, _)| column + 1)
+            .max()
+            .unwrap_or(0);
+        let left = snap_to_grid(
+            ctx.container.x,
+            ctx.container.x + (ctx.container.w - columns as f32 * step) / 2.0,
+            step,
+        );
+        let top = snap_to_grid(
+            ctx.container.y,
+            ctx.container.y + ctx.container.h - padding - 7.0 * step,
+            step,
+        );
+        for &(column, row, level) in cells {
+            let color = match level {
+                0 => ctx.theme.inactive_color,
+                level => ACCENT_PALETTE[level as usize - 1],
+            };
+            draw_cell(
+                left + column as f32 * step,
+                top + row as f32 * step,
+                grid.cell,
+                color,
+            );
+        }
+    });
+}
+
+// The calendar page: the month and week number over the days of the month
+// under their weekday letters, today lit.
+pub fn draw_calendar(today: NaiveDate, first_day: FirstDay) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let container = ctx.container;
+        let padding = 16.0;
+        draw_background(grid_from_height(42.0, 0.25));
+
+        let weeks = calendar::month_grid(today.year(), today.month(), first_day);
+        let title = format!(
+            "{}  W{}",
+            today.format("%B %Y"),
+            calendar::week_number(today, first_day)
+        )
+        .to_uppercase();
+        // The title, the weekday letters, then a row per week.
+        let rows = 2.0 + weeks.len() as f32;
+        let column = (container.w - padding * 2.0) / 7.0;
+        let base = grid_from_height(14.0, 0.25);
+        let scale = (column * 0.8 / measure_pixel_text("00", base).x)
+            .min((container.h - padding * 2.0) / (rows * 14.0 * 1.6))
+            .clamp(0.5, 3.0);
+        let grid = grid_from_height(14.0 * scale, 0.25);
+        let title_grid = grid_from_height(
+            14.0 * scale.min((container.w - padding * 2.0) / measure_pixel_text(&title, base).x),
+            0.25,
+        );
+        let row_height = measure_pixel_text("0", grid).y * 1.6;
+        let top = container.y + ((container.h - row_height * rows) / 2.0).max(padding);
+
+        let active = ctx.theme.active_color;
+        let faded = |alpha: f32| Color::new(active.r, active.g, active.b, alpha);
+        draw_pixel_text(
+            &title,
+            vec2(container.x + padding, top),
+            title_grid,
+            active,
+            false,
+        );
+        let centered = |text: &str, col: usize, row: f32| {
+            let width = measure_pixel_text(text, grid).x;
+            vec2(
+                container.x + padding + column * (col as f32 + 0.5) - width / 2.0,
+                top + row_height * row,
+            )
+        };
+        for (col, letter) in first_day.weekday_letters().iter().enumerate() {
+            let letter = letter.to_string();
+            draw_pixel_text(
+                &letter,
+                centered(&letter, col, 1.0),
+                grid,
+                faded(0.45),
+                false,
+            );
+        }
+        for (week, days) in weeks.iter().enumerate() {
+            for (col, day) in days.iter().enumerate() {
+                let Some(day) = day else {
+                    continue;
+                };
+                let color = if *day == today.day() {
+                    active
+                } else {
+                    faded(0.6)
+                };
+                let label = day.to_string();
+                let origin = centered(&label, col, 2.0 + week as f32);
+                draw_pixel_text(&label, origin, grid, color, false);
+            }
+        }
+    });
+}
+
+// The stats page: a large line per count, stacked in the middle.
+pub fn draw_stats(lines: &[String]) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let container = ctx.container;
+        let padding = 16.0;
+        draw_background(grid_from_height(42.0, 0.25));
+
+        let base = grid_from_height(24.0, 0.25);
+        let widest = lines
+            .iter()
+            .map(|line| measure_pixel_text(line, base).x)
+            .fold(1.0, f32::max);
+        let scale = ((container.w - padding * 2.0) / widest)
+            .min((container.h - padding * 2.0) / (lines.len().max(1) as f32 * 24.0 * 1.5))
+            .clamp(0.3, 1.5);
+        let grid = grid_from_height(24.0 * scale, 0.25);
+        let line_height = measure_pixel_text("0", grid).y + grid.step() * 3.0;
+        let mut y = container.y
+            + ((container.h - line_height * lines.len() as f32 + grid.step() * 3.0) / 2.0)
+                .max(padding);
+        for line in lines {
+            draw_pixel_text(
+                line,
+                vec2(container.x + padding, y),
+                grid,
+                ctx.theme.active_color,
+                false,
+            );
+            y += line_height;
+        }
+    });
+}
+
+// A small cell per page along the bottom edge, the current one lit.
+pub fn draw_page_dots(count: usize, current: usize) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let container = ctx.container;
+        let grid = grid_from_height(21.0, 0.25);
+        let step = grid.step();
+        let width = (count as f32 * 2.0 - 1.0) * step;
+        let left = container.x + ((container.w - width) / 2.0).round();
+        let y = container.y + container.h - 6.0 - grid.cell;
+        let active = ctx.theme.active_color;
+        for index in 0..count {
+            let color = if index == current {
+                active
+            } else {
+                Color::new(active.r, active.g, active.b, 0.3)
+            };
+            draw_cell(left + index as f32 * step * 2.0, y, grid.cell, color);
+        }
+    });
+}
+
+fn ci_color(state: CiState, status: &StatusPalette) -> Color {
+    match state {
+        CiState::Success => status.success,
+        CiState::Pending => status.pending,
+        CiState::Failure => status.failure,
+    }
+}
+
+// GitHub Actions workflows along the top edge, a row each: the label, then
+// a dot per recent run with the newest on the right. Dots open their run.
+pub fn draw_workflows(workflows: &[WorkflowRuns]) -> Vec<ItemHit> {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let grid = grid_from_height(7.0, 0.25);
+        let padding = 6.0;
+        let dot = grid.step() * 4.0;
+        let label_width = workflows
+            .iter()
+            .map(|workflow| measure_pixel_text(&workflow.label, grid).x)
+            .fold(0.0, f32::max);
+        let width = label_width + dot * (2 * crate::actions::RUNS) as f32;
+        let left = ctx.container.x + (ctx.container.w - width) / 2.0;
+        let color = ctx.theme.active_color;
+        let mut hits = Vec::new();
+        let mut y = ctx.container.y + padding;
+        for workflow in workflows {
+            draw_pixel_text(
+                &workflow.label,
+                vec2(left, y),
+                grid,
+                Color::new(color.r, color.g, color.b, 0.6),
+                true,
+            );
+            for (idx, (state, url)) in workflow.runs.iter().enumerate() {
+                let rect = Rect::new(
+                    left + label_width + dot * (2 * idx + 1) as f32,
+                    y + (measure_pixel_text("W", grid).y - dot) / 2.0,
+                    dot,
+                    dot,
+                );
+                draw_rectangle(
+                    rect.x,
+                    rect.y,
+                    rect.w,
+                    rect.h,
+                    ci_color(*state, &ctx.theme.status),
+                );
+                hits.push(ItemHit {
+                    rect,
+                    url: url.clone(),
+                });
+            }
+            y += dot * 2.0;
+        }
+        hits
+    })
+}
+
+// A tooltip box below and right of the cursor, flipped to stay inside the
+// window. Long lines wrap at half the window width.
+pub fn draw_tooltip(tooltip: &Tooltip) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let font_size = 13u16;
+        let line_height = font_size as f32 + 4.0;
+        let padding = 6.0;
+        let lines = tooltip
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, line)| {
+                wrap_text_to_width(line, screen_width() * 0.5, font_size)
+                    .into_iter()
+                    .map(move |wrapped| (wrapped, idx == 0))
+            })
+            .collect::<Vec<_>>();
+        let width = lines
+            .iter()
+            .map(|(line, _)| measure_text(line, None, font_size, 1.0).width)
+            .fold(0.0, f32::max)
+            + padding * 2.0;
+        let height = line_height * lines.len() as f32 + padding * 2.0;
+        let (mx, my) = mouse_position();
+        let mut x = mx + 12.0;
+        let mut y = my + 16.0;
+        if x + width > screen_width() {
+            x = (mx - width - 4.0).max(0.0);
+        }
+        if y + height > screen_height() {
+            y = (my - height - 4.0).max(0.0);
+        }
+        let accent = ctx.theme.active_color;
+        draw_rectangle(x, y, width, height, ctx.theme.background_color);
+        draw_rectangle_lines(
+            x,
+            y,
+            width,
+            height,
+            1.0,
+            Color::new(accent.r, accent.g, accent.b, 0.5),
+        );
+        // The first line is the subject; the rest is detail.
+        for (idx, (line, first)) in lines.iter().enumerate() {
+            let color = if *first {
+                WHITE
+            } else {
+                Color::new(1.0, 1.0, 1.0, 0.6)
+            };
+            draw_text(
+                line,
+                x + padding,
+                y + padding + line_height * idx as f32 + font_size as f32,
+                font_size as f32,
+                color,
+            );
+        }
+    });
+}
+
+// Entries of the right-click menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Refresh,
+    CopyTime,
+    ToggleHours,
+    Settings,
+    Quit,
+}
+
+impl MenuAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuAction::Refresh => "Refresh",
+            MenuAction::CopyTime => "Copy time",
+            MenuAction::ToggleHours => "12h / 24h",
+            MenuAction::Settings => "Settings",
+            MenuAction::Quit => "Quit",
+        }
+    }
+}
+
+// The right-click menu, opening at `origin` and shifted to stay inside the
+// window. Drawn over the cached scene each frame so hovering tracks the
+// cursor; returns each entry's rect for the next click.
+pub fn draw_context_menu(origin: Vec2, actions: &[MenuAction]) -> Vec<(Rect, MenuAction)> {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let font_size = 14u16;
+        let row_height = font_size as f32 + 8.0;
+        let padding = 8.0;
+        let width = actions
+            .iter()
+            .map(|action| measure_text(action.label(), None, font_size, 1.0).width)
+            .fold(0.0, f32::max)
+            + padding * 2.0;
+        let height = row_height * actions.len() as f32;
+        let screen = vec2(screen_width(), screen_height());
+        let x = origin.x.min(screen.x - width).max(0.0);
+        let y = origin.y.min(screen.y - height).max(0.0);
+        let accent = ctx.theme.active_color;
+        draw_rectangle(x, y, width, height, ctx.theme.background_color);
+        draw_rectangle_lines(
+            x,
+            y,
+            width,
+            height,
+            1.0,
+            Color::new(accent.r, accent.g, accent.b, 0.5),
+        );
+        let (mx, my) = mouse_position();
+        actions
+            .iter()
+            .enumerate()
+            .map(|(idx, action)| {
+                let rect = Rect::new(x, y + row_height * idx as f32, width, row_height);
+                if point_in_rect(vec2(mx, my), rect) {
+                    draw_rectangle(
+                        rect.x,
+                        rect.y,
+                        rect.w,
+                        rect.h,
+                        Color::new(accent.r, accent.g, accent.b, 0.25),
+                    );
+                }
+                draw_text(
+                    action.label(),
+                    rect.x + padding,
+                    rect.y + (row_height + font_size as f32) / 2.0 - 2.0,
+                    font_size as f32,
+                    WHITE,
+                );
+                (rect, *action)
+            })
+            .collect()
+    })
+}
+
+pub fn draw_loader_indicator(layout: ClockLayout) {
+    FRAME_CONTEXT.with(|ctx| {
+        let ctx = ctx.borrow();
+        let size = layout.board_grid.step() * 3.0;
+        let padding = layout.left_x - ctx.container.x;
+        let mut x = ctx.container.x + ctx.container.w - size - padding;
+        let mut y = ctx.container.y + ctx.container.h - size - padding;
+        x = snap_to_grid(ctx.container.x, x, layout.board_grid.step());
+        y = snap_to_grid(ctx.container.y, y, layout.board_grid.step());
+
+        // Soft bubble pulses around the loader.
+        let t = get_time() as f32;
+        let offsets = [vec2(-0.6, 0.08), vec2(0.45, -0.45), vec2(0.6, 0.5)];
+        let angle = t * 1.4;
+        let cos_a = angle.cos();
+        let sin_a = angle.sin();
+        for (idx, offset) in offsets.iter().enumerate() {
+            let phase = t * 0.9 + idx as f32 * 1.3;
+            let pulse = (phase.sin() * 0.5 + 0.5).clamp(0.0, 1.0);
+            let bubble_size = (layout.board_grid.cell * (0.5 + pulse * 0.5)).max(2.0);
+            let alpha = (0.15 + pulse * 0.35).min(0.5);
+            let rot_x = offset.x * cos_a - offset.y * sin_a;
+            let rot_y = offset.x * sin_a + offset.y * cos_a;
+            let bx = x + size * 0.5 + rot_x * size * 0.45 - bubble_size * 0.5;
+            let by = y + size * 0.5 + rot_y * size * 0.45 - bubble_size * 0.5;
+            draw_rectangle(
+                bx,
+                by,
+                bubble_size,
+                bubble_size,
+                Color::new(1.0, 1.0, 1.0, alpha),
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_first_jira_key() {
+        assert_eq!(
+            find_jira_in_line("Fix ABC-123 and XY-9"),
+            Some((4, 11, "ABC-123".to_string()))
+        );
+        assert_eq!(
+            find_jira_in_line("PROJ-42"),
+            Some((0, 7, "PROJ-42".to_string()))
+        );
+        assert_eq!(find_jira_in_line("A-1 abc-12 ABC- ABC-12x"), None);
+    }
+
+    #[test]
+    fn badge_caps_at_two_digits() {
+        assert_eq!(notification_badge_label(7), "7");
+        assert_eq!(notification_badge_label(150), "99");
+    }
+
+    fn item(url: &str) -> WorkItem {
+        WorkItem {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn leaving_rows_stay_in_place_until_gone() {
+        let old = [item("a"), item("b"), item("c")];
+        let new = [item("a"), item("c"), item("d")];
+        let change = ListChange::between(&old, &new);
+        let urls = change
+            .merged(&new)
+            .into_iter()
+            .map(|item| item.url)
+            .collect::<Vec<_>>();
+        assert_eq!(urls, ["a", "b", "c", "d"]);
+        assert!(ListChange::between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn rows_ease_in_and_out() {
+        let change = ListChange::between(&[item("a"), item("b")], &[item("a"), item("c")]);
+        assert_eq!(change.presence("a", 0.0), 1.0);
+        assert_eq!(change.presence("c", 0.0), 0.0);
+        assert_eq!(change.presence("c", 1.0), 1.0);
+        assert_eq!(change.presence("b", 0.0), 1.0);
+        assert_eq!(change.presence("b", 1.0), 0.0);
+        assert_eq!(change.presence("c", 0.5), 0.5);
+    }
+}
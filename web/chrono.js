@@ -0,0 +1,128 @@
+"use strict";
+
+// miniquad plugin backing src/platform.rs in the browser build.
+(function () {
+    const TOKEN_KEY = "chrono_github_token";
+
+    function githubToken() {
+        const params = new URLSearchParams(window.location.search);
+        const fromQuery = params.get("token");
+        if (fromQuery) {
+            localStorage.setItem(TOKEN_KEY, fromQuery);
+            // Keep the token out of the address bar and history.
+            params.delete("token");
+            const query = params.toString();
+            window.history.replaceState(null, "", window.location.pathname + (query ? "?" + query : ""));
+            return fromQuery;
+        }
+        return localStorage.getItem(TOKEN_KEY) || "";
+    }
+
+    // In-flight and answered fetch() calls by id, until Rust frees them.
+    const requests = new Map();
+    let nextRequest = 1;
+
+    function register_plugin(importObject) {
+        importObject.env.chrono_open_url = function (ptr, len) {
+            window.open(UTF8ToString(ptr, len), "_blank", "noopener");
+        };
+        importObject.env.chrono_token_length = function () {
+            return new TextEncoder().encode(githubToken()).length;
+        };
+        importObject.env.chrono_token_copy = function (ptr) {
+            const bytes = new TextEncoder().encode(githubToken());
+            new Uint8Array(wasm_memory.buffer, ptr, bytes.length).set(bytes);
+        };
+        importObject.env.chrono_timezone_offset_minutes = function () {
+            return -new Date().getTimezoneOffset();
+        };
+        // 1 for a 12-hour clock, 0 for 24 hours, -1 when the browser does not say.
+        importObject.env.chrono_prefers_12_hour = function () {
+            const hour12 = new Intl.DateTimeFormat(undefined, { hour: "numeric" })
+                .resolvedOptions().hour12;
+            return hour12 === undefined ? -1 : Number(hour12);
+        };
+        // 1 in dark mode, 0 in light mode, -1 when the browser does not say.
+        importObject.env.chrono_prefers_dark = function () {
+            if (!window.matchMedia) {
+                return -1;
+            }
+            return Number(window.matchMedia("(prefers-color-scheme: dark)").matches);
+        };
+        // Asks for permission the first time; clicking opens the PR.
+        importObject.env.chrono_notify = function (titlePtr, titleLen, bodyPtr, bodyLen, urlPtr, urlLen) {
+            if (!("Notification" in window)) {
+                return;
+            }
+            const title = UTF8ToString(titlePtr, titleLen);
+            const body = UTF8ToString(bodyPtr, bodyLen);
+            const url = UTF8ToString(urlPtr, urlLen);
+            const show = function () {
+                const notification = new Notification(title, { body });
+                notification.onclick = function () {
+                    window.open(url, "_blank", "noopener");
+                    notification.close();
+                };
+            };
+            if (Notification.permission === "granted") {
+                show();
+            } else if (Notification.permission === "default") {
+                Notification.requestPermission().then(function (permission) {
+                    if (permission === "granted") {
+                        show();
+                    }
+                });
+            }
+        };
+        importObject.env.chrono_set_title = function (ptr, len) {
+            document.title = UTF8ToString(ptr, len);
+        };
+        importObject.env.chrono_page_query_length = function () {
+            return new TextEncoder().encode(window.location.search).length;
+        };
+        importObject.env.chrono_page_query_copy = function (ptr) {
+            const bytes = new TextEncoder().encode(window.location.search);
+            new Uint8Array(wasm_memory.buffer, ptr, bytes.length).set(bytes);
+        };
+        // A GitHub API GET; Rust polls the status each frame: -1 while
+        // pending, 0 when no answer came, the HTTP status otherwise.
+        importObject.env.chrono_http_get = function (urlPtr, urlLen, tokenPtr, tokenLen) {
+            const id = nextRequest++;
+            const request = { status: -1, body: new Uint8Array(0) };
+            requests.set(id, request);
+            fetch(UTF8ToString(urlPtr, urlLen), {
+                headers: {
+                    "Accept": "application/vnd.github+json",
+                    "Authorization": "Bearer " + UTF8ToString(tokenPtr, tokenLen),
+                },
+            })
+                .then(function (response) {
+                    return response.arrayBuffer().then(function (body) {
+                        request.body = new Uint8Array(body);
+                        request.status = response.status;
+                    });
+                })
+                .catch(function () {
+                    request.status = 0;
+                });
+            return id;
+        };
+        importObject.env.chrono_http_status = function (id) {
+            const request = requests.get(id);
+            return request ? request.status : 0;
+        };
+        importObject.env.chrono_http_body_length = function (id) {
+            const request = requests.get(id);
+            return request ? request.body.length : 0;
+        };
+        importObject.env.chrono_http_body_copy = function (id, ptr) {
+            const body = requests.get(id).body;
+            new Uint8Array(wasm_memory.buffer, ptr, body.length).set(body);
+        };
+        importObject.env.chrono_http_free = function (id) {
+            requests.delete(id);
+        };
+    }
+
+    miniquad_add_plugin({ register_plugin, name: "chrono", version: 1 });
+})();